@@ -5,9 +5,17 @@ use std::sync::Arc;
 
 use stellar_ledger::emulator_test_support::*;
 
+use sha2::{Digest, Sha256};
 use soroban_cli::{
+    print::Print,
+    signer::{self, ledger, Signer, SignerKind},
     tx::builder::TxExt,
-    xdr::{self, Limits, OperationBody, ReadXdr, TransactionEnvelope, WriteXdr},
+    xdr::{
+        self, AccountId, ContractId, Hash, HostFunction, InvokeContractArgs, InvokeHostFunctionOp,
+        Limits, OperationBody, PublicKey, ReadXdr, ScAddress, ScMap, ScSymbol, ScVal,
+        SorobanAddressCredentials, SorobanAuthorizationEntry, SorobanAuthorizedFunction,
+        SorobanAuthorizedInvocation, SorobanCredentials, TransactionEnvelope, Uint256, WriteXdr,
+    },
 };
 
 use test_case::test_case;
@@ -88,3 +96,95 @@ async fn test_signer(ledger_device_model: &str, hd_path: u32) {
         )
         .unwrap();
 }
+
+#[test_case("nanos", 0; "when the device is NanoS")]
+#[test_case("nanox", 1; "when the device is NanoX")]
+#[test_case("nanosp", 2; "when the device is NanoS Plus")]
+#[tokio::test]
+async fn test_sign_soroban_authorizations(ledger_device_model: &str, hd_path: u32) {
+    let container = TestEnv::speculos_container(ledger_device_model).await;
+    let host_port = container.get_host_port_ipv4(9998).await.unwrap();
+    let ui_host_port = container.get_host_port_ipv4(5000).await.unwrap();
+
+    let signer = Signer {
+        kind: SignerKind::Ledger(ledger::emulator(hd_path, host_port).await),
+        print: Print::new(true),
+    };
+    let key = signer.get_public_key().await.unwrap();
+
+    let invoke_args = InvokeContractArgs {
+        contract_address: ScAddress::Contract(ContractId(Hash([1; 32]))),
+        function_name: "transfer".try_into().unwrap(),
+        args: xdr::VecM::default(),
+    };
+    let invocation = SorobanAuthorizedInvocation {
+        function: SorobanAuthorizedFunction::ContractFn(invoke_args.clone()),
+        sub_invocations: xdr::VecM::default(),
+    };
+    let auth = SorobanAuthorizationEntry {
+        credentials: SorobanCredentials::Address(SorobanAddressCredentials {
+            address: ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key)))),
+            nonce: 1,
+            signature_expiration_ledger: 0,
+            signature: ScVal::Void,
+        }),
+        root_invocation: invocation.clone(),
+    };
+    let operation = xdr::Operation {
+        source_account: None,
+        body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+            host_function: HostFunction::InvokeContract(invoke_args),
+            auth: vec![auth].try_into().unwrap(),
+        }),
+    };
+    let tx = xdr::Transaction::new_tx(xdr::MuxedAccount::Ed25519(key.into()), 100, 100, operation);
+
+    let network_passphrase = "Test SDF Network ; September 2015";
+    let sign = tokio::task::spawn(async move {
+        signer::sign_soroban_authorizations(&tx, &signer, &[], &[], 100, network_passphrase).await
+    });
+    let approve = tokio::task::spawn(approve_tx_hash_signature(
+        ui_host_port,
+        ledger_device_model.to_string(),
+    ));
+
+    let signed_tx = sign.await.unwrap().unwrap().expect("an auth entry to sign");
+    approve.await.unwrap();
+
+    let OperationBody::InvokeHostFunction(InvokeHostFunctionOp { auth, .. }) =
+        &signed_tx.operations[0].body
+    else {
+        panic!("expected an invoke host function operation");
+    };
+    let SorobanCredentials::Address(credentials) = &auth[0].credentials else {
+        panic!("expected address credentials");
+    };
+    assert_eq!(credentials.signature_expiration_ledger, 100);
+    let ScVal::Vec(Some(signatures)) = &credentials.signature else {
+        panic!("expected a vec of signatures");
+    };
+    let ScVal::Map(Some(ScMap(fields))) = &signatures[0] else {
+        panic!("expected a signature map");
+    };
+    let field = |name: &str| {
+        let entry = fields
+            .iter()
+            .find(|e| e.key == ScVal::Symbol(ScSymbol(name.try_into().unwrap())))
+            .unwrap();
+        let ScVal::Bytes(bytes) = &entry.val else {
+            panic!("expected bytes for {name}");
+        };
+        bytes.to_vec()
+    };
+    assert_eq!(field("public_key"), key.to_vec());
+
+    let network_id = Hash(Sha256::digest(network_passphrase).into());
+    let payload = signer::authorization_payload(&invocation, 1, 100, &network_id).unwrap();
+    let verifying_key = ed25519_dalek::VerifyingKey::from_bytes(&key).unwrap();
+    verifying_key
+        .verify_strict(
+            &payload,
+            &ed25519_dalek::Signature::from_slice(&field("signature")).unwrap(),
+        )
+        .unwrap();
+}
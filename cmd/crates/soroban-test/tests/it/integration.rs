@@ -1,4 +1,5 @@
 mod bindings;
+mod channels;
 mod constructor;
 mod contract;
 mod cookbook;
@@ -11,6 +12,7 @@ mod init;
 mod keys;
 mod ledger;
 mod network;
+mod offline;
 mod secure_store;
 mod snapshot;
 mod tx;
@@ -0,0 +1,96 @@
+use soroban_test::{AssertExt, TestEnv};
+
+use crate::integration::util::{deploy_contract, setup_accounts, DeployOptions, HELLO_WORLD};
+
+#[tokio::test]
+async fn invoke_and_send_through_channel_accounts() {
+    let sandbox = &TestEnv::new();
+    let (test, test1) = setup_accounts(sandbox);
+    sandbox
+        .generate_account("channel1", None)
+        .assert()
+        .success();
+    sandbox
+        .generate_account("channel2", None)
+        .assert()
+        .success();
+    let id = deploy_contract(sandbox, HELLO_WORLD, DeployOptions::default()).await;
+    let client = sandbox.network.rpc_client().unwrap();
+    let test_seq_num = client.get_account(&test).await.unwrap().seq_num;
+
+    sandbox
+        .new_assert_cmd("contract")
+        .args([
+            "invoke",
+            "--id",
+            &id,
+            "--channel",
+            "channel1,channel2",
+            "--send=yes",
+            "--",
+            "hello",
+            "--world=world",
+        ])
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("Using channel account channel"))
+        .stdout("[\"Hello\",\"world\"]\n");
+
+    let tx = sandbox
+        .new_assert_cmd("tx")
+        .args([
+            "new",
+            "payment",
+            "--destination",
+            &test1,
+            "--amount",
+            "10_000_000",
+            "--build-only",
+        ])
+        .assert()
+        .success()
+        .stdout_as_str();
+    sandbox
+        .new_assert_cmd("tx")
+        .args(["send", "--channel", "channel2"])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("must be signed again"));
+    sandbox
+        .new_assert_cmd("tx")
+        .args(["send", "--channel", "channel2", "--sign-with-key", "test"])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success();
+
+    // The channel accounts used their own sequence numbers
+    assert_eq!(
+        client.get_account(&test).await.unwrap().seq_num,
+        test_seq_num
+    );
+}
+
+#[tokio::test]
+async fn channel_accounts_are_not_leased_to_build_only() {
+    let sandbox = &TestEnv::new();
+    let id = stellar_strkey::Contract([0; 32]).to_string();
+    sandbox
+        .new_assert_cmd("contract")
+        .args([
+            "invoke",
+            "--id",
+            &id,
+            "--channel",
+            "channel1",
+            "--build-only",
+            "--",
+            "hello",
+            "--world=world",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "`--build-only` can't be used with `--channel`",
+        ));
+}
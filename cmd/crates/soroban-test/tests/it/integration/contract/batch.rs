@@ -0,0 +1,89 @@
+use crate::integration::util::{deploy_contract, DeployOptions, HELLO_WORLD};
+
+use soroban_test::{AssertExt, TestEnv};
+
+fn get_count(sandbox: &TestEnv, id: &str) -> String {
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["invoke", "--id", id, "--", "get_count"])
+        .assert()
+        .success()
+        .stdout_as_str()
+}
+
+#[tokio::test]
+async fn batch_with_captured_results() {
+    let sandbox = &TestEnv::new();
+    let id = deploy_contract(sandbox, HELLO_WORLD, DeployOptions::default()).await;
+    let batch = sandbox.dir().join("batch.toml");
+    std::fs::write(
+        &batch,
+        format!(
+            r#"
+            [[steps]]
+            name = "count"
+            id = "{id}"
+            fn = "inc"
+
+            [[steps]]
+            id = "{id}"
+            fn = "prng_u64_in_range"
+            args = {{ low = "${{count}}", high = "${{count}}" }}
+            "#
+        ),
+    )
+    .unwrap();
+
+    let output = sandbox
+        .new_assert_cmd("contract")
+        .args(["batch", "--dry-run"])
+        .arg(&batch)
+        .assert()
+        .success()
+        .stdout_as_str();
+    assert_eq!(output, "1\n1");
+    assert_eq!(get_count(sandbox, &id), "0");
+
+    let output = sandbox
+        .new_assert_cmd("contract")
+        .arg("batch")
+        .arg(&batch)
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("Ran 2 step(s)"))
+        .stdout_as_str();
+    assert_eq!(output, "1\n1");
+    assert_eq!(get_count(sandbox, &id), "1");
+}
+
+#[tokio::test]
+async fn batch_stops_at_failed_step() {
+    let sandbox = &TestEnv::new();
+    let id = deploy_contract(sandbox, HELLO_WORLD, DeployOptions::default()).await;
+    let batch = sandbox.dir().join("batch.json");
+    std::fs::write(
+        &batch,
+        serde_json::json!({
+            "steps": [
+                { "id": id, "fn": "hello", "args": { "world": "batch" } },
+                { "id": id, "fn": "missing" },
+                { "id": id, "fn": "inc" },
+            ]
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("batch")
+        .arg(&batch)
+        .assert()
+        .failure()
+        .stdout(predicates::str::contains(r#"["Hello","batch"]"#))
+        .stderr(predicates::str::contains(
+            "Skipping the 1 remaining step(s)",
+        ))
+        .stderr(predicates::str::contains("step 2 (`missing` on"));
+    assert_eq!(get_count(sandbox, &id), "0");
+}
@@ -0,0 +1,109 @@
+use crate::integration::util::{CONSTRUCTOR, HELLO_WORLD};
+
+use soroban_test::{AssertExt, TestEnv};
+
+fn invoke(sandbox: &TestEnv, id: &str, function: &str) -> String {
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["invoke", "--id", id, "--", function])
+        .assert()
+        .success()
+        .stdout_as_str()
+}
+
+#[tokio::test]
+async fn deploy_from_manifest() {
+    let sandbox = &TestEnv::new();
+    let manifest = sandbox.dir().join("contracts.toml");
+    std::fs::write(
+        &manifest,
+        format!(
+            r#"
+            [contracts.hello]
+            wasm = '{}'
+            init = [
+                {{ fn = "inc" }},
+                {{ fn = "multi_word_cmd", args = {{ contract_owner = "${{counter}}" }} }},
+            ]
+
+            [contracts.counter]
+            wasm = '{}'
+            constructor = {{ counter = 7 }}
+            "#,
+            HELLO_WORLD.path().display(),
+            CONSTRUCTOR.path().display(),
+        ),
+    )
+    .unwrap();
+
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["deploy", "--manifest"])
+        .arg(&manifest)
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::is_match("^counter: C[A-Z0-9]{55}\nhello: C[A-Z0-9]{55}\n$").unwrap(),
+        )
+        .stderr(predicates::str::contains(
+            "Deployed 2 contract(s), 0 already deployed",
+        ));
+    assert_eq!(invoke(sandbox, "counter", "counter"), "7");
+    assert_eq!(invoke(sandbox, "hello", "get_count"), "1");
+
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["deploy", "--manifest"])
+        .arg(&manifest)
+        .assert()
+        .success()
+        .stderr(predicates::str::contains(
+            "Skipping hello, already deployed",
+        ))
+        .stderr(predicates::str::contains(
+            "Deployed 0 contract(s), 2 already deployed",
+        ));
+    assert_eq!(invoke(sandbox, "hello", "get_count"), "1");
+}
+
+#[tokio::test]
+async fn deploy_from_manifest_resumes_init() {
+    let sandbox = &TestEnv::new();
+    let manifest = sandbox.dir().join("contracts.toml");
+    let write = |init: &str| {
+        std::fs::write(
+            &manifest,
+            format!(
+                "[contracts.hello]\nwasm = '{}'\ninit = [{init}]\n",
+                HELLO_WORLD.path().display(),
+            ),
+        )
+        .unwrap();
+    };
+
+    write(r#"{ fn = "inc" }, { fn = "missing" }"#);
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["deploy", "--manifest"])
+        .arg(&manifest)
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "contract `hello` was deployed, but calling `missing` on it failed",
+        ));
+    assert_eq!(invoke(sandbox, "hello", "get_count"), "1");
+
+    write(r#"{ fn = "inc" }, { fn = "inc" }"#);
+    for _ in 0..2 {
+        sandbox
+            .new_assert_cmd("contract")
+            .args(["deploy", "--manifest"])
+            .arg(&manifest)
+            .assert()
+            .success()
+            .stderr(predicates::str::contains(
+                "Deployed 0 contract(s), 1 already deployed",
+            ));
+        assert_eq!(invoke(sandbox, "hello", "get_count"), "2");
+    }
+}
@@ -0,0 +1,69 @@
+use crate::integration::util::{deploy_contract, DeployOptions, CUSTOM_TYPES, HELLO_WORLD};
+
+use soroban_test::{AssertExt, TestEnv};
+
+#[tokio::test]
+async fn diff_interfaces() {
+    let sandbox = &TestEnv::new();
+
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["info", "diff"])
+        .arg(HELLO_WORLD.path())
+        .arg(HELLO_WORLD.path())
+        .assert()
+        .success()
+        .stdout("")
+        .stderr(predicates::str::contains("The interfaces are the same"));
+
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["info", "diff"])
+        .arg(HELLO_WORLD.path())
+        .arg(CUSTOM_TYPES.path())
+        .assert()
+        .failure()
+        .stdout(predicates::str::contains(
+            "breaking: removed function `inc`",
+        ))
+        .stderr(predicates::str::contains("breaking change(s)"));
+
+    let json = sandbox
+        .new_assert_cmd("contract")
+        .args(["info", "diff", "--output", "json"])
+        .arg(HELLO_WORLD.path())
+        .arg(CUSTOM_TYPES.path())
+        .assert()
+        .failure()
+        .stdout_as_str();
+    let report: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(report["breaking"], true);
+    assert!(report["changes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|change| change["kind"] == "removed" && change["name"] == "inc"));
+}
+
+#[tokio::test]
+async fn diff_deployed_contract() {
+    let sandbox = &TestEnv::new();
+    let id = deploy_contract(sandbox, HELLO_WORLD, DeployOptions::default()).await;
+
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["info", "diff", &id])
+        .arg(HELLO_WORLD.path())
+        .assert()
+        .success()
+        .stdout("");
+
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["info", "diff"])
+        .arg(CUSTOM_TYPES.path())
+        .arg(&id)
+        .assert()
+        .failure()
+        .stdout(predicates::str::contains("added function `inc`"));
+}
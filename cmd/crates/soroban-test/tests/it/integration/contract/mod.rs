@@ -1 +1,5 @@
+mod batch;
+mod deploy_manifest;
+mod diff;
 mod fetch;
+mod upgrade;
@@ -0,0 +1,47 @@
+use crate::integration::util::{deploy_contract, DeployOptions, CUSTOM_TYPES, HELLO_WORLD};
+
+use soroban_test::TestEnv;
+
+#[tokio::test]
+async fn upgrade_warns_about_breaking_changes() {
+    let sandbox = &TestEnv::new();
+    let id = deploy_contract(sandbox, HELLO_WORLD, DeployOptions::default()).await;
+
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["upgrade", "--id", &id, "--wasm"])
+        .arg(CUSTOM_TYPES.path())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "has no `upgrade` function to upgrade it with",
+        ));
+
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["upgrade", "--id", &id, "--upgrade-fn", "upgrade_contract"])
+        .arg("--wasm")
+        .arg(CUSTOM_TYPES.path())
+        .assert()
+        .success()
+        .stderr(predicates::str::contains(
+            "Breaking: removed function `inc`",
+        ))
+        .stderr(predicates::str::contains(
+            "Breaking: changed function `hello`: removed input `world`",
+        ))
+        .stderr(predicates::str::contains(
+            "the contract can't be upgraded with it again",
+        ))
+        .stderr(predicates::str::contains(format!(
+            "Upgraded contract {id} to wasm {}",
+            CUSTOM_TYPES.hash().unwrap()
+        )));
+
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["invoke", "--id", &id, "--", "u32_", "--u32_", "7"])
+        .assert()
+        .success()
+        .stdout("7\n");
+}
@@ -210,3 +210,246 @@ async fn unset_default_identity() {
         .stdout(predicate::str::contains("STELLAR_ACCOUNT=").not())
         .success();
 }
+
+#[tokio::test]
+async fn encrypted_identity() {
+    let sandbox = &TestEnv::new();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["generate", "encrypted", "--encrypt"])
+        .env("STELLAR_IDENTITY_PASSPHRASE", "correct horse")
+        .assert()
+        .success();
+    let identity =
+        std::fs::read_to_string(sandbox.config_dir().join("identity").join("encrypted.toml"))
+            .unwrap();
+    assert!(identity.contains("ciphertext"));
+    assert!(!identity.contains("seed_phrase"));
+
+    // the address doesn't need the passphrase
+    let address = pubkey_for_identity(sandbox, "encrypted");
+    assert!(address.starts_with('G'));
+
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["secret", "encrypted"])
+        .env("STELLAR_IDENTITY_PASSPHRASE", "wrong horse")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("wrong passphrase"));
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["secret", "encrypted"])
+        .env("STELLAR_IDENTITY_PASSPHRASE", "correct horse")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("S"));
+}
+
+#[tokio::test]
+async fn encrypt_plaintext_identities() {
+    let sandbox = &TestEnv::new();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["generate", "plain"])
+        .assert()
+        .success();
+    let address = pubkey_for_identity(sandbox, "plain");
+    let secret = sandbox
+        .new_assert_cmd("keys")
+        .args(["secret", "plain"])
+        .assert()
+        .success()
+        .stdout_as_str();
+
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["encrypt", "plain"])
+        .env("STELLAR_IDENTITY_PASSPHRASE", "correct horse")
+        .assert()
+        .success();
+
+    assert_eq!(pubkey_for_identity(sandbox, "plain"), address);
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["secret", "plain"])
+        .env("STELLAR_IDENTITY_PASSPHRASE", "correct horse")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(secret));
+}
+
+#[tokio::test]
+async fn export_import_bundle() {
+    let source = &TestEnv::new();
+    source
+        .new_assert_cmd("keys")
+        .args(["generate", "alice"])
+        .assert()
+        .success();
+    source
+        .new_assert_cmd("keys")
+        .args([
+            "add",
+            "bob",
+            "--public-key",
+            "GAREAZZQWHOCBJS236KIE3AWYBVFLSBK7E5UW3ICI3TCRWQKT5LNLCEZ",
+        ])
+        .assert()
+        .success();
+    let bundle = source.dir().join("keys.json");
+    source
+        .new_assert_cmd("keys")
+        .args(["export", "--output"])
+        .arg(&bundle)
+        .env("STELLAR_BUNDLE_PASSPHRASE", "correct horse")
+        .assert()
+        .success();
+    assert!(!std::fs::read_to_string(&bundle).unwrap().contains("alice"));
+
+    let target = &TestEnv::new();
+    target
+        .new_assert_cmd("keys")
+        .args(["generate", "alice"])
+        .assert()
+        .success();
+    target
+        .new_assert_cmd("keys")
+        .arg("import")
+        .arg(&bundle)
+        .env("STELLAR_BUNDLE_PASSPHRASE", "wrong horse")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("wrong passphrase"));
+    target
+        .new_assert_cmd("keys")
+        .arg("import")
+        .arg(&bundle)
+        .env("STELLAR_BUNDLE_PASSPHRASE", "correct horse")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("identity 'alice' already exists"));
+    target
+        .new_assert_cmd("keys")
+        .args(["import", "--on-conflict", "overwrite"])
+        .arg(&bundle)
+        .env("STELLAR_BUNDLE_PASSPHRASE", "correct horse")
+        .assert()
+        .success();
+
+    assert_eq!(
+        pubkey_for_identity(target, "alice"),
+        pubkey_for_identity(source, "alice")
+    );
+    assert_eq!(
+        pubkey_for_identity(target, "bob"),
+        pubkey_for_identity(source, "bob")
+    );
+}
+
+#[tokio::test]
+async fn sign_and_verify_message() {
+    let sandbox = &TestEnv::new();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["generate", "alice"])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["generate", "bob"])
+        .assert()
+        .success();
+
+    let signature = sandbox
+        .new_assert_cmd("keys")
+        .args(["sign-message", "alice", "I own this account"])
+        .assert()
+        .success()
+        .stdout_as_str();
+
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["verify-message", &pubkey_for_identity(sandbox, "alice")])
+        .args(["I own this account", "--signature", &signature])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Signature is valid"));
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["verify-message", "alice", "I own another account"])
+        .args(["--signature", &signature])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("signature is not valid"));
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["verify-message", "bob", "I own this account"])
+        .args(["--signature", &signature])
+        .assert()
+        .failure();
+
+    // Binary messages are passed base64 encoded
+    let signature = sandbox
+        .new_assert_cmd("keys")
+        .args(["sign-message", "alice", "--base64", "AAECAw=="])
+        .assert()
+        .success()
+        .stdout_as_str();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["verify-message", "alice", "--base64", "AAECAw=="])
+        .args(["--signature", &signature])
+        .assert()
+        .success();
+}
+
+#[tokio::test]
+async fn split_and_recover_seed_phrase() {
+    let sandbox = &TestEnv::new();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["generate", "alice"])
+        .assert()
+        .success();
+    let dir = sandbox.dir().join("shares");
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["split", "alice", "--threshold", "2", "--shares", "3"])
+        .arg("--output-dir")
+        .arg(&dir)
+        .assert()
+        .success();
+    let share = |i: usize| dir.join(format!("alice-share-{i}-of-3.txt"));
+
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["recover", "bob"])
+        .arg(share(3))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("2 shares are needed"));
+
+    // A mistyped word is caught before anything is recovered
+    let words = std::fs::read_to_string(share(1)).unwrap();
+    let mut mistyped = words.split_whitespace().collect::<Vec<_>>();
+    mistyped[4] = if mistyped[4] == "zoo" { "zone" } else { "zoo" };
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["recover", "bob", &mistyped.join(" ")])
+        .arg(share(3))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("share 1"));
+
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["recover", "bob", &words])
+        .arg(share(3))
+        .assert()
+        .success();
+    assert_eq!(
+        pubkey_for_identity(sandbox, "bob"),
+        pubkey_for_identity(sandbox, "alice")
+    );
+}
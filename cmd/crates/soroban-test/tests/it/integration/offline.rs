@@ -0,0 +1,158 @@
+use soroban_cli::xdr::{Limits, ReadXdr, TransactionEnvelope, TransactionExt, WriteXdr};
+use soroban_test::{AssertExt, TestEnv};
+
+use crate::integration::util::{deploy_contract, setup_accounts, DeployOptions, HELLO_WORLD};
+
+// An RPC server that can't be reached, to check that offline building makes no RPC call
+const OFFLINE: [&str; 4] = [
+    "--rpc-url",
+    "http://localhost:1",
+    "--network-passphrase",
+    "Standalone Network ; February 2017",
+];
+
+fn envelope(xdr: &str) -> TransactionEnvelope {
+    TransactionEnvelope::from_xdr_base64(xdr.trim(), Limits::none()).unwrap()
+}
+
+fn seq_num(tx_env: &TransactionEnvelope) -> i64 {
+    let TransactionEnvelope::Tx(envelope) = tx_env else {
+        panic!("expected a transaction envelope");
+    };
+    envelope.tx.seq_num.0
+}
+
+#[tokio::test]
+async fn build_classic_transaction_offline() {
+    let sandbox = &TestEnv::new();
+    let (_, test1) = setup_accounts(sandbox);
+    let tx = sandbox
+        .new_assert_cmd("tx")
+        .args([
+            "new",
+            "payment",
+            "--destination",
+            &test1,
+            "--amount",
+            "10_000_000",
+            "--build-only",
+            "--sequence-number",
+            "100+1",
+        ])
+        .args(OFFLINE)
+        .assert()
+        .success()
+        .stdout_as_str();
+    assert_eq!(seq_num(&envelope(&tx)), 101);
+
+    let tx = sandbox
+        .new_assert_cmd("tx")
+        .args([
+            "operation",
+            "add",
+            "bump-sequence",
+            "--bump-to",
+            "200",
+            "--build-only",
+            "--sequence-number",
+            "102",
+        ])
+        .args(OFFLINE)
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+    assert_eq!(seq_num(&envelope(&tx)), 102);
+
+    sandbox
+        .new_assert_cmd("tx")
+        .args(["new", "payment", "--destination", &test1, "--amount", "1"])
+        .args(["--build-only", "--sequence-number", "1+x"])
+        .args(OFFLINE)
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("invalid sequence number"));
+}
+
+#[tokio::test]
+async fn invoke_built_offline_from_a_prior_simulation() {
+    let sandbox = &TestEnv::new();
+    let (test, _) = setup_accounts(sandbox);
+    let id = deploy_contract(sandbox, HELLO_WORLD, DeployOptions::default()).await;
+    let client = sandbox.network.rpc_client().unwrap();
+    let seq = client.get_account(&test).await.unwrap().seq_num.0;
+    let wasm = HELLO_WORLD.path();
+    let invoke = |simulation: Option<&str>| {
+        let mut cmd = sandbox.new_assert_cmd("contract");
+        cmd.args(["invoke", "--id", &id, "--build-only", "--wasm"])
+            .arg(&wasm)
+            .args(["--sequence-number", &format!("{seq}+1")])
+            .args(OFFLINE);
+        if let Some(simulation) = simulation {
+            cmd.args(["--simulation", simulation]);
+        }
+        cmd.args(["--", "hello", "--world=world"])
+            .assert()
+            .success()
+            .stdout_as_str()
+    };
+
+    let tx = envelope(&invoke(None));
+    assert_eq!(seq_num(&tx), seq + 1);
+    let simulation = client
+        .simulate_transaction_envelope(&tx, None)
+        .await
+        .unwrap();
+    let simulation_path = sandbox.dir().join("simulation.json");
+    std::fs::write(
+        &simulation_path,
+        serde_json::to_string(&simulation).unwrap(),
+    )
+    .unwrap();
+
+    let tx = invoke(Some(&simulation_path.to_string_lossy()));
+    let TransactionEnvelope::Tx(built) = envelope(&tx) else {
+        panic!("expected a transaction envelope");
+    };
+    assert!(matches!(built.tx.ext, TransactionExt::V1(_)));
+
+    sandbox
+        .new_assert_cmd("tx")
+        .args(["send", "--sign-with-key", "test"])
+        .write_stdin(
+            TransactionEnvelope::Tx(built)
+                .to_xdr_base64(Limits::none())
+                .unwrap()
+                .as_bytes(),
+        )
+        .assert()
+        .success();
+    assert_eq!(client.get_account(&test).await.unwrap().seq_num.0, seq + 1);
+}
+
+#[tokio::test]
+async fn invoke_built_offline_needs_the_wasm() {
+    let sandbox = &TestEnv::new();
+    let id = stellar_strkey::Contract([0; 32]).to_string();
+    for simulation in [None, Some("simulation.json")] {
+        let mut cmd = sandbox.new_assert_cmd("contract");
+        cmd.args([
+            "invoke",
+            "--id",
+            &id,
+            "--build-only",
+            "--sequence-number",
+            "1",
+        ])
+        .args(OFFLINE);
+        if let Some(simulation) = simulation {
+            cmd.args(["--simulation", simulation]);
+        }
+        cmd.args(["--", "hello", "--world=world"])
+            .assert()
+            .failure()
+            .stderr(predicates::str::contains(
+                "pass the contract's wasm with `--wasm`",
+            ));
+    }
+}
@@ -377,3 +377,97 @@ fn snapshot_merge_multiple() {
         .max(snapshot_2.ledger_entries.len());
     assert!(merged.ledger_entries.len() >= max_individual);
 }
+
+#[test]
+fn invoke_against_snapshot() {
+    let sandbox = &TestEnv::new();
+    sandbox
+        .new_assert_cmd("keys")
+        .arg("generate")
+        .arg("--fund")
+        .arg("a")
+        .assert()
+        .success();
+    let account_a = sandbox
+        .new_assert_cmd("keys")
+        .arg("address")
+        .arg("a")
+        .assert()
+        .success()
+        .stdout_as_str();
+    sandbox
+        .new_assert_cmd("keys")
+        .arg("generate")
+        .arg("b")
+        .assert()
+        .success();
+    let account_b = sandbox
+        .new_assert_cmd("keys")
+        .arg("address")
+        .arg("b")
+        .assert()
+        .success()
+        .stdout_as_str();
+    let contract = sandbox
+        .new_assert_cmd("contract")
+        .arg("asset")
+        .arg("deploy")
+        .arg(format!("--asset=A1:{account_a}"))
+        .assert()
+        .success()
+        .stdout_as_str();
+    // Wait 8 ledgers for a checkpoint
+    for i in 1..=8 {
+        sandbox
+            .new_assert_cmd("keys")
+            .arg("generate")
+            .arg("--fund")
+            .arg(format!("k{i}"))
+            .assert()
+            .success();
+    }
+    sandbox
+        .new_assert_cmd("snapshot")
+        .arg("create")
+        .arg("--address")
+        .arg(&account_a)
+        .arg("--address")
+        .arg(&contract)
+        .assert()
+        .success();
+
+    // Without a network, to check the invocation is local
+    let invoke = |args: &[&str]| {
+        let mut cmd = sandbox.new_assert_cmd("contract");
+        cmd.args(["invoke", "--id", &contract, "--source", "a"])
+            .args(["--rpc-url", "http://localhost:1"])
+            .args(["--network-passphrase", "Standalone Network ; February 2017"])
+            .args(args);
+        cmd
+    };
+    invoke(&["--ledger-snapshot=snapshot.json", "--", "symbol"])
+        .assert()
+        .success()
+        .stdout("\"A1\"\n")
+        .stderr(predicates::str::contains("Ledger changes: none"));
+    invoke(&[
+        "--ledger-snapshot=snapshot.json",
+        "--ledger-snapshot-out=changed.json",
+        "--",
+        "set_admin",
+        "--new_admin",
+        &account_b,
+    ])
+    .assert()
+    .success()
+    .stderr(predicates::str::contains("Ledger changes: 1"))
+    .stderr(predicates::str::contains("updated"));
+    invoke(&["--ledger-snapshot=changed.json", "--", "admin"])
+        .assert()
+        .success()
+        .stdout(format!("\"{account_b}\"\n"));
+    invoke(&["--ledger-snapshot=snapshot.json", "--", "admin"])
+        .assert()
+        .success()
+        .stdout(format!("\"{account_a}\"\n"));
+}
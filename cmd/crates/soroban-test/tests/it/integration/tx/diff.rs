@@ -0,0 +1,53 @@
+use soroban_test::{AssertExt, TestEnv};
+
+use crate::integration::util::setup_accounts;
+
+#[tokio::test]
+async fn diff_signed_and_changed_envelopes() {
+    let sandbox = &TestEnv::new();
+    let (_, test1) = setup_accounts(sandbox);
+    let payment = |amount: &str| {
+        sandbox
+            .new_assert_cmd("tx")
+            .args([
+                "new",
+                "payment",
+                "--destination",
+                &test1,
+                "--amount",
+                amount,
+                "--build-only",
+            ])
+            .assert()
+            .success()
+            .stdout_as_str()
+    };
+    let tx = payment("10_000_000");
+    let signed = sandbox
+        .new_assert_cmd("tx")
+        .args(["sign", "--sign-with-key=test"])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+
+    sandbox
+        .new_assert_cmd("tx")
+        .args(["diff", &tx])
+        .write_stdin(signed.as_bytes())
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Signing hash: same"))
+        .stdout(predicates::str::contains("Operation 1: same"))
+        .stdout(predicates::str::contains("Signatures: 0 → 1"));
+
+    sandbox
+        .new_assert_cmd("tx")
+        .args(["diff", &tx, &payment("20_000_000")])
+        .assert()
+        .failure()
+        .stdout(predicates::str::contains("Signing hash: differs"))
+        .stdout(predicates::str::contains(
+            "body.payment.amount: \"10000000\" → \"20000000\"",
+        ));
+}
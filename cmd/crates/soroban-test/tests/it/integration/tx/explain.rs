@@ -0,0 +1,65 @@
+use soroban_test::{AssertExt, TestEnv};
+
+use crate::integration::util::{deploy_contract, setup_accounts, DeployOptions, HELLO_WORLD};
+
+#[tokio::test]
+async fn explain_payment_and_invocation() {
+    let sandbox = &TestEnv::new();
+    let (test, test1) = setup_accounts(sandbox);
+    let tx = sandbox
+        .new_assert_cmd("tx")
+        .args([
+            "new",
+            "payment",
+            "--destination",
+            &test1,
+            "--amount",
+            "12_500_000",
+            "--memo-text",
+            "invoice 42",
+            "--build-only",
+        ])
+        .assert()
+        .success()
+        .stdout_as_str();
+    sandbox
+        .new_assert_cmd("tx")
+        .arg("explain")
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Memo: text \"invoice 42\""))
+        .stdout(predicates::str::contains(format!(
+            "1. test ({test}) pays 1.25 XLM to test1 ({test1})"
+        )));
+
+    let id = deploy_contract(sandbox, HELLO_WORLD, DeployOptions::default()).await;
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["alias", "add", "hello", "--id", &id])
+        .assert()
+        .success();
+    let tx = sandbox
+        .new_assert_cmd("contract")
+        .args([
+            "invoke",
+            "--id",
+            "hello",
+            "--build-only",
+            "--",
+            "hello",
+            "--world=world",
+        ])
+        .assert()
+        .success()
+        .stdout_as_str();
+    sandbox
+        .new_assert_cmd("tx")
+        .arg("explain")
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(format!(
+            "invokes hello(world: \"world\") on hello ({id})"
+        )));
+}
@@ -0,0 +1,91 @@
+use soroban_cli::xdr::{Limits, ReadXdr, TransactionEnvelope};
+use soroban_test::{AssertExt, TestEnv};
+
+use crate::integration::util::setup_accounts;
+
+#[tokio::test]
+async fn fee_bump_paid_by_another_account() {
+    let sandbox = &TestEnv::new();
+    let client = sandbox.client();
+    let (test, test1) = setup_accounts(sandbox);
+    let tx = sandbox
+        .new_assert_cmd("tx")
+        .args([
+            "new",
+            "payment",
+            "--destination",
+            &test1,
+            "--amount",
+            "10_000_000",
+            "--build-only",
+        ])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let signed = sandbox
+        .new_assert_cmd("tx")
+        .args(["sign", "--sign-with-key=test"])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+
+    sandbox
+        .new_assert_cmd("tx")
+        .args(["fee-bump", "--fee-source=test1", "--fee=199"])
+        .write_stdin(signed.as_bytes())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("minimum of 200"));
+    sandbox
+        .new_assert_cmd("tx")
+        .args([
+            "fee-bump",
+            "--fee-source=test1",
+            "--fee=999",
+            "--base-fee=500",
+            "--build-only",
+        ])
+        .write_stdin(signed.as_bytes())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("minimum of 1000"));
+
+    let fee_bump = sandbox
+        .new_assert_cmd("tx")
+        .args(["fee-bump", "--fee-source=test1", "--fee=1000"])
+        .write_stdin(signed.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+    let TransactionEnvelope::TxFeeBump(envelope) =
+        TransactionEnvelope::from_xdr_base64(&fee_bump, Limits::none()).unwrap()
+    else {
+        panic!("expected a fee bump envelope");
+    };
+    assert_eq!(envelope.tx.fee, 1000);
+    assert_eq!(envelope.signatures.len(), 1);
+
+    let hash = sandbox
+        .new_assert_cmd("tx")
+        .arg("hash")
+        .write_stdin(fee_bump.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+
+    let test_before = client.get_account(&test).await.unwrap();
+    let test1_before = client.get_account(&test1).await.unwrap();
+    sandbox
+        .new_assert_cmd("tx")
+        .arg("send")
+        .write_stdin(fee_bump.as_bytes())
+        .assert()
+        .success()
+        .stderr(predicates::str::contains(hash.trim()));
+    let test_after = client.get_account(&test).await.unwrap();
+    let test1_after = client.get_account(&test1).await.unwrap();
+    assert_eq!(test_before.balance - 10_000_000, test_after.balance);
+    assert!(test1_after.balance > test1_before.balance + 10_000_000 - 1000);
+    assert!(test1_after.balance < test1_before.balance + 10_000_000);
+}
@@ -0,0 +1,94 @@
+use soroban_cli::{
+    commands::tx::xdr::unwrap_envelope_v1,
+    xdr::{Limits, Memo, ReadXdr, TransactionEnvelope},
+};
+use soroban_test::{AssertExt, TestEnv};
+
+use crate::integration::util::{deploy_contract, setup_accounts, DeployOptions, HELLO_WORLD};
+
+fn memo(tx: &str) -> Memo {
+    let tx_env = TransactionEnvelope::from_xdr_base64(tx, Limits::none()).unwrap();
+    unwrap_envelope_v1(tx_env).unwrap().memo
+}
+
+#[tokio::test]
+async fn payment_with_memo() {
+    let sandbox = &TestEnv::new();
+    let (_, test1) = setup_accounts(sandbox);
+    let tx = sandbox
+        .new_assert_cmd("tx")
+        .args([
+            "new",
+            "payment",
+            "--destination",
+            &test1,
+            "--amount",
+            "10_000_000",
+            "--memo-id",
+            "1234",
+            "--build-only",
+        ])
+        .assert()
+        .success()
+        .stdout_as_str();
+    assert_eq!(memo(&tx), Memo::Id(1234));
+
+    let tx = sandbox
+        .new_assert_cmd("tx")
+        .args(["update", "memo", "--memo-text", "deposit 42"])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+    assert!(matches!(memo(&tx), Memo::Text(text) if text.to_string() == "deposit 42"));
+
+    sandbox
+        .new_assert_cmd("tx")
+        .args([
+            "update",
+            "memo",
+            "--memo-text",
+            "a memo that is too long to fit",
+        ])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("at most 28 bytes"));
+
+    let signed = sandbox
+        .new_assert_cmd("tx")
+        .args(["sign", "--sign-with-key=test"])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+    sandbox
+        .new_assert_cmd("tx")
+        .arg("send")
+        .write_stdin(signed.as_bytes())
+        .assert()
+        .success();
+}
+
+#[tokio::test]
+async fn invoke_with_memo() {
+    let sandbox = &TestEnv::new();
+    let id = deploy_contract(sandbox, HELLO_WORLD, DeployOptions::default()).await;
+    let tx = sandbox
+        .new_assert_cmd("contract")
+        .args([
+            "invoke",
+            "--id",
+            &id,
+            "--memo-hash",
+            &"ab".repeat(32),
+            "--build-only",
+            "--",
+            "hello",
+            "--world=world",
+        ])
+        .assert()
+        .success()
+        .stdout_as_str();
+    assert_eq!(memo(&tx), Memo::Hash([0xab; 32].into()));
+}
@@ -5,11 +5,20 @@ pub mod claimable_balance;
 pub mod clawback;
 pub mod create_account;
 pub mod decode_encode;
+pub mod diff;
+pub mod explain;
+pub mod fee_bump;
 pub mod fetch;
 pub mod general;
 pub mod manage_data;
+pub mod memo;
 pub mod offers;
+pub mod op;
 pub mod path_payments;
+pub mod preconditions;
 pub mod payment;
+pub mod send;
 pub mod set_options;
+pub mod sign;
+pub mod soroban;
 pub mod sponsorship;
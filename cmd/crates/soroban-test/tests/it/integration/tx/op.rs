@@ -0,0 +1,99 @@
+use soroban_test::{AssertExt, TestEnv};
+
+use crate::integration::util::setup_accounts;
+
+#[tokio::test]
+async fn remove_move_and_set_source_of_operations() {
+    let sandbox = &TestEnv::new();
+    let (_, test1) = setup_accounts(sandbox);
+    let tx = sandbox
+        .new_assert_cmd("tx")
+        .args([
+            "new",
+            "payment",
+            "--destination",
+            &test1,
+            "--amount",
+            "10_000_000",
+            "--build-only",
+        ])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let tx = sandbox
+        .new_assert_cmd("tx")
+        .args([
+            "op",
+            "add",
+            "manage-data",
+            "--data-name",
+            "msg",
+            "--data-value",
+            "beef",
+        ])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+    let tx = sandbox
+        .new_assert_cmd("tx")
+        .args(["op", "add", "bump-sequence", "--bump-to", "1"])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+
+    let tx = sandbox
+        .new_assert_cmd("tx")
+        .args(["op", "move", "--from", "1", "--to", "0"])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+    let tx = sandbox
+        .new_assert_cmd("tx")
+        .args(["op", "remove", "--index", "2"])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+    let tx = sandbox
+        .new_assert_cmd("tx")
+        .args(["op", "set-source", "--index", "0", "--op-source", "test1"])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+    sandbox
+        .new_assert_cmd("tx")
+        .args(["op", "ls"])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success()
+        .stdout(format!(
+            "0: ManageData name=\"msg\" value=beef (source: {test1})\n\
+             1: Payment destination={test1} asset=native amount=10000000\n"
+        ));
+
+    sandbox
+        .new_assert_cmd("tx")
+        .args(["op", "remove", "--index", "2"])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("no operation at index 2"));
+
+    let signed = sandbox
+        .new_assert_cmd("tx")
+        .args(["sign", "--sign-with-key=test", "--signer=test1"])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+    sandbox
+        .new_assert_cmd("tx")
+        .arg("send")
+        .write_stdin(signed.as_bytes())
+        .assert()
+        .success();
+}
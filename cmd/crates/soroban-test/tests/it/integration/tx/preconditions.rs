@@ -0,0 +1,111 @@
+use soroban_cli::{
+    commands::tx::xdr::unwrap_envelope_v1,
+    xdr::{Limits, Preconditions, ReadXdr, TransactionEnvelope},
+};
+use soroban_test::{AssertExt, TestEnv};
+
+use crate::integration::util::setup_accounts;
+
+fn preconditions(tx: &str) -> Preconditions {
+    let tx_env = TransactionEnvelope::from_xdr_base64(tx, Limits::none()).unwrap();
+    unwrap_envelope_v1(tx_env).unwrap().cond
+}
+
+#[tokio::test]
+async fn time_bounds_and_update_preconditions() {
+    let sandbox = &TestEnv::new();
+    let (_, test1) = setup_accounts(sandbox);
+    let tx = sandbox
+        .new_assert_cmd("tx")
+        .args([
+            "new",
+            "payment",
+            "--destination",
+            &test1,
+            "--amount",
+            "10_000_000",
+            "--valid-after",
+            "1h",
+            "--valid-until",
+            "2h",
+            "--build-only",
+        ])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let Preconditions::Time(time_bounds) = preconditions(&tx) else {
+        panic!("expected time bounds");
+    };
+    assert_eq!(time_bounds.max_time.0 - time_bounds.min_time.0, 3600);
+
+    let signed = sandbox
+        .new_assert_cmd("tx")
+        .args(["sign", "--sign-with-key=test"])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+    sandbox
+        .new_assert_cmd("tx")
+        .arg("send")
+        .write_stdin(signed.as_bytes())
+        .assert()
+        .failure();
+
+    let tx = sandbox
+        .new_assert_cmd("tx")
+        .args([
+            "update",
+            "preconditions",
+            "--clear",
+            "--valid-until",
+            "10m",
+            "--min-seq-ledger-gap",
+            "0",
+            "--extra-signer",
+            &test1,
+        ])
+        .write_stdin(signed.as_bytes())
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("signature(s) were removed"))
+        .stdout_as_str();
+    let Preconditions::V2(cond) = preconditions(&tx) else {
+        panic!("expected v2 preconditions");
+    };
+    assert_eq!(cond.time_bounds.unwrap().min_time.0, 0);
+    assert_eq!(cond.extra_signers.len(), 1);
+
+    let signed = sandbox
+        .new_assert_cmd("tx")
+        .args(["sign", "--sign-with-key=test", "--signer=test1"])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+    sandbox
+        .new_assert_cmd("tx")
+        .arg("send")
+        .write_stdin(signed.as_bytes())
+        .assert()
+        .success();
+
+    sandbox
+        .new_assert_cmd("tx")
+        .args([
+            "new",
+            "payment",
+            "--destination",
+            &test1,
+            "--amount",
+            "10_000_000",
+            "--min-ledger",
+            "20",
+            "--max-ledger",
+            "10",
+            "--build-only",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("would never be valid"));
+}
@@ -0,0 +1,100 @@
+use soroban_test::{AssertExt, TestEnv};
+
+use crate::integration::util::setup_accounts;
+
+#[tokio::test]
+async fn send_raises_insufficient_fee() {
+    let sandbox = &TestEnv::new();
+    let (_, test1) = setup_accounts(sandbox);
+    let tx = sandbox
+        .new_assert_cmd("tx")
+        .args([
+            "new",
+            "payment",
+            "--destination",
+            &test1,
+            "--amount",
+            "10_000_000",
+            "--fee=50",
+            "--build-only",
+        ])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let signed = sandbox
+        .new_assert_cmd("tx")
+        .args(["sign", "--sign-with-key=test"])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+
+    sandbox
+        .new_assert_cmd("tx")
+        .arg("send")
+        .write_stdin(signed.as_bytes())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("--max-fee"));
+
+    sandbox
+        .new_assert_cmd("tx")
+        .args(["send", "--max-fee=1000", "--sign-with-key=test"])
+        .write_stdin(signed.as_bytes())
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("SUCCESS"));
+
+    let actions = sandbox
+        .new_assert_cmd("cache")
+        .args(["actionlog", "ls"])
+        .assert()
+        .success()
+        .stdout_as_str();
+    assert!(actions.contains("Submit   ERROR"));
+    assert!(actions.contains("Submit   PENDING"));
+}
+
+#[tokio::test]
+async fn send_fee_bumps_insufficient_fee() {
+    let sandbox = &TestEnv::new();
+    let (_, test1) = setup_accounts(sandbox);
+    let tx = sandbox
+        .new_assert_cmd("tx")
+        .args([
+            "new",
+            "payment",
+            "--destination",
+            &test1,
+            "--amount",
+            "10_000_000",
+            "--fee=50",
+            "--build-only",
+        ])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let signed = sandbox
+        .new_assert_cmd("tx")
+        .args(["sign", "--sign-with-key=test"])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+
+    sandbox
+        .new_assert_cmd("tx")
+        .args(["send", "--max-fee=150", "--fee-bump-source=test1"])
+        .write_stdin(signed.as_bytes())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("cannot be raised"));
+
+    sandbox
+        .new_assert_cmd("tx")
+        .args(["send", "--max-fee=1000", "--fee-bump-source=test1"])
+        .write_stdin(signed.as_bytes())
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("SUCCESS"));
+}
@@ -0,0 +1,197 @@
+use predicates::prelude::PredicateBooleanExt;
+use soroban_cli::xdr::{Limits, ReadXdr, TransactionEnvelope};
+use soroban_test::{AssertExt, TestEnv};
+
+use crate::integration::util::{new_account, setup_accounts};
+
+fn payments_from_both_accounts(sandbox: &TestEnv, test1: &str) -> String {
+    let tx = sandbox
+        .new_assert_cmd("tx")
+        .args([
+            "new",
+            "payment",
+            "--destination",
+            test1,
+            "--amount",
+            "10_000_000",
+            "--build-only",
+        ])
+        .assert()
+        .success()
+        .stdout_as_str();
+    sandbox
+        .new_assert_cmd("tx")
+        .args([
+            "op",
+            "add",
+            "payment",
+            "--destination",
+            "test",
+            "--amount",
+            "5_000_000",
+            "--operation-source-account",
+            "test1",
+        ])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str()
+}
+
+#[tokio::test]
+async fn sign_with_multiple_signers() {
+    let sandbox = &TestEnv::new();
+    let (_, test1) = setup_accounts(sandbox);
+    let tx = payments_from_both_accounts(sandbox, &test1);
+
+    let assert = sandbox
+        .new_assert_cmd("tx")
+        .args([
+            "sign",
+            "--sign-with-key=test",
+            "--signer=test1",
+            "--signer=test",
+        ])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success();
+    let stderr = String::from_utf8_lossy(&assert.get_output().stderr).to_string();
+    assert!(stderr.contains("already signed"), "{stderr}");
+    assert!(stderr.contains("enough signature weight"), "{stderr}");
+    let signed = assert.stdout_as_str();
+
+    sandbox
+        .new_assert_cmd("tx")
+        .arg("send")
+        .write_stdin(signed.as_bytes())
+        .assert()
+        .success();
+}
+
+#[tokio::test]
+async fn sign_reports_missing_weight_and_refuses_other_keys() {
+    let sandbox = &TestEnv::new();
+    let (_, test1) = setup_accounts(sandbox);
+    new_account(sandbox, "other");
+    let tx = payments_from_both_accounts(sandbox, &test1);
+
+    let assert = sandbox
+        .new_assert_cmd("tx")
+        .args(["sign", "--sign-with-key=test"])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success();
+    let stderr = String::from_utf8_lossy(&assert.get_output().stderr).to_string();
+    assert!(stderr.contains("operation 1 (Payment)"), "{stderr}");
+
+    sandbox
+        .new_assert_cmd("tx")
+        .args(["sign", "--signer=other"])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("is not a signer"));
+
+    sandbox
+        .new_assert_cmd("tx")
+        .args(["sign", "--signer=other", "--no-check"])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("Missing signatures").not());
+}
+
+#[tokio::test]
+async fn merge_signatures_from_each_signer() {
+    let sandbox = &TestEnv::new();
+    let (_, test1) = setup_accounts(sandbox);
+    let tx = payments_from_both_accounts(sandbox, &test1);
+
+    let sign = |key: &str| {
+        sandbox
+            .new_assert_cmd("tx")
+            .args(["sign", &format!("--sign-with-key={key}")])
+            .write_stdin(tx.as_bytes())
+            .assert()
+            .success()
+            .stdout_as_str()
+    };
+    let signed_by_test = sign("test");
+    let signed_by_test1 = sign("test1");
+
+    let merged = sandbox
+        .new_assert_cmd("tx")
+        .args(["merge", &signed_by_test, &signed_by_test1, &signed_by_test])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let TransactionEnvelope::Tx(envelope) =
+        TransactionEnvelope::from_xdr_base64(&merged, Limits::none()).unwrap()
+    else {
+        panic!("expected a v1 envelope");
+    };
+    assert_eq!(envelope.signatures.len(), 2);
+
+    let other_tx = sandbox
+        .new_assert_cmd("tx")
+        .args([
+            "new",
+            "payment",
+            "--destination",
+            &test1,
+            "--amount",
+            "1",
+            "--build-only",
+        ])
+        .assert()
+        .success()
+        .stdout_as_str();
+    sandbox
+        .new_assert_cmd("tx")
+        .args(["merge", &signed_by_test, &other_tx])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("is for transaction"));
+
+    sandbox
+        .new_assert_cmd("tx")
+        .arg("send")
+        .write_stdin(merged.as_bytes())
+        .assert()
+        .success();
+}
+
+#[tokio::test]
+async fn verify_signatures() {
+    let sandbox = &TestEnv::new();
+    let (_, test1) = setup_accounts(sandbox);
+    let tx = payments_from_both_accounts(sandbox, &test1);
+
+    sandbox
+        .new_assert_cmd("tx")
+        .arg("verify")
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("Signed by source account")
+                .and(predicates::str::contains(": no")),
+        );
+
+    let signed = sandbox
+        .new_assert_cmd("tx")
+        .args(["sign", "--sign-with-key=test1"])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+    sandbox
+        .new_assert_cmd("tx")
+        .arg("verify")
+        .write_stdin(signed.as_bytes())
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(format!(
+            "Signature 0: valid, signed by {test1}"
+        )));
+}
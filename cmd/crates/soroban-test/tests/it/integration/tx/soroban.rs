@@ -0,0 +1,105 @@
+use soroban_cli::xdr::{Limits, OperationBody, ReadXdr, TransactionEnvelope, TransactionExt};
+use soroban_test::{AssertExt, TestEnv};
+
+use crate::integration::util::deploy_hello;
+
+#[tokio::test]
+async fn invoke_host_function() {
+    let sandbox = &TestEnv::new();
+    let id = deploy_hello(sandbox).await;
+
+    sandbox
+        .new_assert_cmd("tx")
+        .args(["new", "invoke-host-function", "--id", &id])
+        .args(["--", "hello", "--world=world"])
+        .assert()
+        .success()
+        .stdout("[\"Hello\",\"world\"]\n");
+
+    let tx = sandbox
+        .new_assert_cmd("tx")
+        .args(["new", "invoke-host-function", "--id", &id, "--build-only"])
+        .args(["--", "hello", "--world=pipe"])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let simulated = sandbox
+        .new_assert_cmd("tx")
+        .arg("simulate")
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+    let signed = sandbox
+        .new_assert_cmd("tx")
+        .args(["sign", "--sign-with-key=test"])
+        .write_stdin(simulated.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+    sandbox
+        .new_assert_cmd("tx")
+        .arg("send")
+        .write_stdin(signed.as_bytes())
+        .assert()
+        .success();
+}
+
+#[tokio::test]
+async fn extend_and_restore_footprint() {
+    let sandbox = &TestEnv::new();
+    let id = deploy_hello(sandbox).await;
+
+    sandbox
+        .new_assert_cmd("tx")
+        .args([
+            "new",
+            "extend-footprint-ttl",
+            "--id",
+            &id,
+            "--ledgers-to-extend",
+            "100000",
+        ])
+        .assert()
+        .success();
+
+    let tx = sandbox
+        .new_assert_cmd("tx")
+        .args(["new", "restore-footprint", "--id", &id, "--build-only"])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let TransactionEnvelope::Tx(envelope) =
+        TransactionEnvelope::from_xdr_base64(&tx, Limits::none()).unwrap()
+    else {
+        panic!("expected a v1 envelope");
+    };
+    assert!(matches!(
+        envelope.tx.operations[0].body,
+        OperationBody::RestoreFootprint(_)
+    ));
+    let TransactionExt::V1(data) = envelope.tx.ext else {
+        panic!("expected Soroban transaction data");
+    };
+    assert_eq!(data.resources.footprint.read_write.len(), 1);
+    assert!(data.resources.footprint.read_only.is_empty());
+
+    let assert = sandbox
+        .new_assert_cmd("tx")
+        .args(["op", "add", "invoke-host-function", "--id", &id])
+        .args(["--", "hello", "--world=world"])
+        .write_stdin(tx.as_bytes())
+        .assert()
+        .success();
+    let stderr = String::from_utf8_lossy(&assert.get_output().stderr).to_string();
+    assert!(stderr.contains("must be the only operation"), "{stderr}");
+    sandbox
+        .new_assert_cmd("tx")
+        .args(["op", "ls"])
+        .write_stdin(assert.stdout_as_str().as_bytes())
+        .assert()
+        .success()
+        .stdout(format!(
+            "0: RestoreFootprint\n1: InvokeHostFunction contract={id} function=hello\n"
+        ));
+}
@@ -6,7 +6,8 @@ use crate::commands::contract::arg_parsing::Error::HelpMessage;
 use crate::commands::contract::deploy::wasm::Error::ArgParse;
 use crate::commands::contract::invoke::Error::ArgParsing;
 use crate::commands::contract::Error::{Deploy, Invoke};
-use crate::commands::Error::Contract;
+use crate::commands::tx::{self, args::Error::ArgParsing as ArgParsingTx};
+use crate::commands::Error::{Contract, Tx};
 use crate::config::Config;
 use crate::print::Print;
 use crate::upgrade_check::upgrade_check;
@@ -96,6 +97,17 @@ pub async fn main() {
             println!("{help}");
             std::process::exit(1);
         }
+        if let Tx(tx::Error::New(tx::new::Error::Tx(ArgParsingTx(HelpMessage(help))))) = e {
+            println!("{help}");
+            std::process::exit(1);
+        }
+        if let Tx(tx::Error::Op(tx::op::Error::Add(tx::op::add::Error::Tx(ArgParsingTx(
+            HelpMessage(help),
+        ))))) = e
+        {
+            println!("{help}");
+            std::process::exit(1);
+        }
         printer.errorln(format!("error: {e}"));
         std::process::exit(1);
     }
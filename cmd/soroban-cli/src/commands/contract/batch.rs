@@ -0,0 +1,249 @@
+use std::{collections::BTreeMap, ffi::OsString, path::PathBuf};
+
+use clap::Parser;
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{calls, invoke};
+use crate::{
+    commands::{global, NetworkRunnable},
+    config,
+    print::Print,
+    signer::custom_account::{AuthSigner, SignatureFormat},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("batch file: {0}")]
+    File(calls::Error),
+    #[error("batch file {0} has no steps")]
+    NoSteps(PathBuf),
+    #[error("step {step} is named `{name}` like an earlier step")]
+    DuplicateStepName { step: usize, name: String },
+    #[error("step {step} uses `${{{variable}}}`, which is not the name of an earlier step")]
+    UnknownVariable { step: usize, variable: String },
+    #[error("step {step}: {error}")]
+    Variable { step: usize, error: calls::Error },
+    #[error("step {step} ({description}) failed: {error}")]
+    Step {
+        step: usize,
+        description: String,
+        error: Box<invoke::Error>,
+    },
+}
+
+pub const LONG_ABOUT: &str = "\
+Invoke several contract functions, one after the other, as listed in a batch file. Each step is its own transaction, signed by the same source account and auth signers. The batch stops at the first step that fails.
+
+The batch file is TOML, or JSON if its extension is `.json`, with a list of steps:
+
+    [[steps]]
+    name = \"allowance\"           # optional, to use the result in later steps
+    id = \"token\"                 # contract ID or alias
+    fn = \"approve\"
+    args = { from = \"alice\", spender = \"pool\", amount = \"100\", expiration_ledger = 1000000 }
+
+    [[steps]]
+    id = \"pool\"
+    fn = \"deposit\"
+    args = { from = \"alice\", amount = \"${allowance}\" }
+    send = \"yes\"                 # optional, like `--send`
+
+`${name}` is replaced with the result of the step with that name, and `${name.field}` with a field of it.
+";
+
+/// Invoke contract functions in order, as listed in a batch file
+/// e.g. `stellar contract batch steps.toml --source alice`
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Batch file listing the steps to run
+    pub file: PathBuf,
+
+    /// Simulate every step without sending any transaction. Each step is simulated against the
+    /// current state of the network, without the changes of the steps before it
+    #[arg(long)]
+    pub dry_run: bool,
+
+    #[command(flatten)]
+    pub config: config::Args,
+
+    #[command(flatten)]
+    pub resources: crate::resources::Args,
+
+    /// Sign the auth entries of a custom account contract (C-address) with a key, as
+    /// `CONTRACT=KEY`, e.g. `--auth-signer CC…=alice`. The contract can be an address or an alias,
+    /// the key an identity, a secret key or a seed phrase. Pass multiple times to sign with several
+    /// keys or for several contracts.
+    #[arg(
+        long = "auth-signer",
+        env = "STELLAR_AUTH_SIGNER",
+        value_name = "CONTRACT=KEY",
+        value_delimiter = ','
+    )]
+    pub auth_signers: Vec<AuthSigner>,
+
+    /// How signatures are passed to a custom account contract's `__check_auth`
+    #[arg(long, value_enum, default_value_t)]
+    pub auth_signature_format: SignatureFormat,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+struct Batch {
+    steps: Vec<Step>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+struct Step {
+    name: Option<String>,
+    id: String,
+    #[serde(rename = "fn")]
+    function: String,
+    #[serde(default)]
+    args: BTreeMap<String, Value>,
+    send: Option<invoke::Send>,
+}
+
+impl Step {
+    fn description(&self) -> String {
+        match &self.name {
+            Some(name) => format!("`{name}`, `{}` on {}", self.function, self.id),
+            None => format!("`{}` on {}", self.function, self.id),
+        }
+    }
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let batch = self.read()?;
+        check(&batch)?;
+
+        let total = batch.steps.len();
+        let mut results = BTreeMap::new();
+        for (i, step) in batch.steps.iter().enumerate() {
+            let n = i + 1;
+            print.infoln(format!("Step {n}/{total}: {}", step.description()));
+            let variable = |error| Error::Variable { step: n, error };
+            let slop = [OsString::from(&step.function)]
+                .into_iter()
+                .chain(calls::slop_args(&step.args, &results).map_err(variable)?)
+                .collect();
+            let Ok(contract_id) = calls::resolve_str(&step.id, &results)
+                .map_err(variable)?
+                .parse();
+            let send = if self.dry_run {
+                invoke::Send::No
+            } else {
+                step.send.unwrap_or_default()
+            };
+            let invoke = invoke::Cmd {
+                contract_id,
+                slop,
+                config: self.config.clone(),
+                resources: self.resources.clone(),
+                send,
+                auth_signers: self.auth_signers.clone(),
+                auth_signature_format: self.auth_signature_format,
+                ..Default::default()
+            };
+            let output = match invoke.run_against_rpc_server(Some(global_args), None).await {
+                // Steps are never built only, so they always have a result
+                Ok(res) => res.into_result().unwrap_or_default(),
+                Err(error) => {
+                    if n < total {
+                        print.warnln(format!("Skipping the {} remaining step(s)", total - n));
+                    }
+                    return Err(Error::Step {
+                        step: n,
+                        description: step.description(),
+                        error: Box::new(error),
+                    });
+                }
+            };
+            println!("{output}");
+            if let Some(name) = &step.name {
+                let value = serde_json::from_str(&output).unwrap_or(Value::String(output));
+                results.insert(name.clone(), value);
+            }
+        }
+        if self.dry_run {
+            print.checkln(format!("Simulated {total} step(s)"));
+        } else {
+            print.checkln(format!("Ran {total} step(s)"));
+        }
+        Ok(())
+    }
+
+    fn read(&self) -> Result<Batch, Error> {
+        let batch: Batch = calls::read(&self.file).map_err(Error::File)?;
+        if batch.steps.is_empty() {
+            return Err(Error::NoSteps(self.file.clone()));
+        }
+        Ok(batch)
+    }
+}
+
+/// Check that step names are unique and that steps only use the results of earlier steps,
+/// before running any of them.
+fn check(batch: &Batch) -> Result<(), Error> {
+    let mut names = BTreeMap::new();
+    for (i, step) in batch.steps.iter().enumerate() {
+        let n = i + 1;
+        let variable = |error| Error::Variable { step: n, error };
+        let mut variables = calls::variables(&step.id).map_err(variable)?;
+        for value in step.args.values() {
+            variables.extend(calls::value_variables(value).map_err(variable)?);
+        }
+        for variable in variables {
+            if !names.contains_key(calls::name(variable)) {
+                return Err(Error::UnknownVariable {
+                    step: n,
+                    variable: variable.to_string(),
+                });
+            }
+        }
+        if let Some(name) = &step.name {
+            if names.insert(name.clone(), n).is_some() {
+                return Err(Error::DuplicateStepName {
+                    step: n,
+                    name: name.clone(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_batch() {
+        let batch: Batch = toml::from_str(
+            r#"
+            [[steps]]
+            name = "count"
+            id = "counter"
+            fn = "inc"
+
+            [[steps]]
+            id = "counter"
+            fn = "prng_u64_in_range"
+            args = { low = "${count}", high = "${count}" }
+            "#,
+        )
+        .unwrap();
+        check(&batch).unwrap();
+
+        let mut batch = batch;
+        batch.steps.swap(0, 1);
+        assert!(matches!(
+            check(&batch),
+            Err(Error::UnknownVariable { step: 1, variable }) if variable == "count"
+        ));
+    }
+}
@@ -0,0 +1,251 @@
+//! Files listing contract calls, the steps of `contract batch` and the contracts of
+//! `contract deploy --manifest`: reading them, and turning the arguments of their calls into the
+//! arguments of a contract function's CLI.
+//!
+//! Arguments can use variables, `${name}` for the value named `name`, and `${name.field}` or
+//! `${name.0}` for a field or an element of it.
+use std::{
+    collections::BTreeMap,
+    ffi::OsString,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("cannot read {path}: {error}")]
+    CannotRead {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    #[error("cannot parse {path}: {error}")]
+    CannotParse { path: PathBuf, error: String },
+    #[error("unclosed `${{` in `{0}`")]
+    UnclosedVariable(String),
+    #[error("`${{{0}}}` does not refer to any value")]
+    UnknownVariable(String),
+    #[error("`${{{variable}}}` refers to `{field}`, which the value of `{name}` has not")]
+    MissingField {
+        variable: String,
+        name: String,
+        field: String,
+    },
+}
+
+/// Read a TOML file, or JSON if its extension is `.json`.
+pub fn read<T: DeserializeOwned>(path: &Path) -> Result<T, Error> {
+    let contents = fs::read_to_string(path).map_err(|error| Error::CannotRead {
+        path: path.to_path_buf(),
+        error,
+    })?;
+    if path.extension().is_some_and(|ext| ext == "json") {
+        serde_json::from_str(&contents).map_err(|e| e.to_string())
+    } else {
+        toml::from_str(&contents).map_err(|e| e.to_string())
+    }
+    .map_err(|error| Error::CannotParse {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+/// The variables used in a string, e.g. `name` or `name.field.0`.
+pub fn variables(s: &str) -> Result<Vec<&str>, Error> {
+    let mut variables = Vec::new();
+    let mut rest = s;
+    while let Some((_, variable, after)) = next_variable(rest, s)? {
+        variables.push(variable);
+        rest = after;
+    }
+    Ok(variables)
+}
+
+/// The variables used in a value, and in the values it contains.
+pub fn value_variables(value: &Value) -> Result<Vec<&str>, Error> {
+    Ok(match value {
+        Value::String(s) => variables(s)?,
+        Value::Array(values) => values
+            .iter()
+            .map(value_variables)
+            .collect::<Result<Vec<_>, _>>()?
+            .concat(),
+        Value::Object(map) => map
+            .values()
+            .map(value_variables)
+            .collect::<Result<Vec<_>, _>>()?
+            .concat(),
+        _ => Vec::new(),
+    })
+}
+
+/// The name of the value a variable refers to, `name` for `name.field.0`.
+pub fn name(variable: &str) -> &str {
+    variable.split('.').next().unwrap_or_default()
+}
+
+/// The first variable in `rest`, a part of `s`, with what precedes and follows it.
+fn next_variable<'a>(rest: &'a str, s: &str) -> Result<Option<(&'a str, &'a str, &'a str)>, Error> {
+    let Some(start) = rest.find("${") else {
+        return Ok(None);
+    };
+    let end = rest[start..]
+        .find('}')
+        .ok_or_else(|| Error::UnclosedVariable(s.to_string()))?;
+    Ok(Some((
+        &rest[..start],
+        &rest[start + 2..start + end],
+        &rest[start + end + 1..],
+    )))
+}
+
+/// Look up a variable in the named values.
+fn lookup(variable: &str, values: &BTreeMap<String, Value>) -> Result<Value, Error> {
+    let mut path = variable.split('.');
+    let name = path.next().unwrap_or_default();
+    let mut value = values
+        .get(name)
+        .ok_or_else(|| Error::UnknownVariable(variable.to_string()))?;
+    for field in path {
+        let next = match value {
+            Value::Object(map) => map.get(field),
+            Value::Array(values) => field.parse::<usize>().ok().and_then(|i| values.get(i)),
+            _ => None,
+        };
+        value = next.ok_or_else(|| Error::MissingField {
+            variable: variable.to_string(),
+            name: name.to_string(),
+            field: field.to_string(),
+        })?;
+    }
+    Ok(value.clone())
+}
+
+/// Replace the variables in a string with the values they refer to. Strings are inserted as is,
+/// other values as JSON.
+pub fn resolve_str(s: &str, values: &BTreeMap<String, Value>) -> Result<String, Error> {
+    let mut resolved = String::new();
+    let mut rest = s;
+    while let Some((before, variable, after)) = next_variable(rest, s)? {
+        resolved.push_str(before);
+        match lookup(variable, values)? {
+            Value::String(value) => resolved.push_str(&value),
+            value => resolved.push_str(&value.to_string()),
+        }
+        rest = after;
+    }
+    resolved.push_str(rest);
+    Ok(resolved)
+}
+
+/// Replace the variables in an argument. A string that is only a variable is replaced by the
+/// value itself, so that numbers, maps and lists keep their type.
+pub fn resolve(value: &Value, values: &BTreeMap<String, Value>) -> Result<Value, Error> {
+    Ok(match value {
+        Value::String(s) => match variables(s)?.as_slice() {
+            [variable] if s.len() == variable.len() + 3 => lookup(variable, values)?,
+            _ => Value::String(resolve_str(s, values)?),
+        },
+        Value::Array(array) => Value::Array(
+            array
+                .iter()
+                .map(|v| resolve(v, values))
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| Ok((k.clone(), resolve(v, values)?)))
+                .collect::<Result<_, Error>>()?,
+        ),
+        value => value.clone(),
+    })
+}
+
+/// Arguments for the implicit CLI of a contract function, as `--arg-name=value`, with their
+/// variables resolved. Arguments that are `false` are left out, like flags that aren't passed.
+pub fn slop_args(
+    args: &BTreeMap<String, Value>,
+    values: &BTreeMap<String, Value>,
+) -> Result<Vec<OsString>, Error> {
+    let mut slop = Vec::new();
+    for (arg, value) in args {
+        let value = match resolve(value, values)? {
+            Value::Bool(false) => continue,
+            Value::String(s) => s,
+            value => value.to_string(),
+        };
+        slop.push(OsString::from(format!("--{arg}={value}")));
+    }
+    Ok(slop)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn resolve_variables() {
+        let values = BTreeMap::from([
+            (
+                "token".to_string(),
+                json!("CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE"),
+            ),
+            (
+                "pool".to_string(),
+                json!({ "shares": "100", "ids": [1, 2] }),
+            ),
+        ]);
+        assert_eq!(
+            resolve(&json!("${token}"), &values).unwrap(),
+            json!("CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE")
+        );
+        assert_eq!(resolve(&json!("${pool.ids.1}"), &values).unwrap(), json!(2));
+        assert_eq!(
+            resolve(&json!({ "amount": "${pool.shares}" }), &values).unwrap(),
+            json!({ "amount": "100" })
+        );
+        assert_eq!(
+            resolve(&json!("ids ${pool.ids} of ${pool.shares}"), &values).unwrap(),
+            json!("ids [1,2] of 100")
+        );
+        assert!(matches!(
+            resolve(&json!("${pool.fee}"), &values),
+            Err(Error::MissingField { field, .. }) if field == "fee"
+        ));
+        assert!(matches!(
+            resolve(&json!("${token"), &values),
+            Err(Error::UnclosedVariable(_))
+        ));
+        assert!(matches!(
+            resolve(&json!("${coin}"), &values),
+            Err(Error::UnknownVariable(variable)) if variable == "coin"
+        ));
+    }
+
+    #[test]
+    fn slop_with_variables() {
+        let coin = "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE";
+        let values = BTreeMap::from([("coin".to_string(), json!(coin))]);
+        let args = BTreeMap::from([
+            ("token".to_string(), json!("${coin}")),
+            ("rate".to_string(), json!(5)),
+            ("paused".to_string(), json!(false)),
+            ("tokens".to_string(), json!(["${coin}"])),
+        ]);
+        assert_eq!(
+            slop_args(&args, &values).unwrap(),
+            vec![
+                OsString::from("--rate=5"),
+                OsString::from(format!("--token={coin}")),
+                OsString::from(format!("--tokens=[\"{coin}\"]")),
+            ]
+        );
+        assert_eq!(
+            value_variables(&json!({ "a": ["${coin}", "${pool.ids.0} ${coin}"] })).unwrap(),
+            vec!["coin", "pool.ids.0", "coin"]
+        );
+    }
+}
@@ -1,9 +1,12 @@
 use crate::commands::global;
 
 pub mod asset;
+pub mod manifest;
 pub mod utils;
 pub mod wasm;
 
+pub const ABOUT: &str = "Deploy a wasm contract";
+
 #[derive(Debug, clap::Subcommand)]
 pub enum Cmd {
     /// Deploy builtin Soroban Asset Contract
@@ -0,0 +1,480 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use super::{utils, wasm};
+use crate::{
+    commands::{
+        contract::{
+            calls,
+            id::wasm::{contract_preimage, get_contract_id},
+            invoke,
+        },
+        global,
+        txn_result::TxnResult,
+        NetworkRunnable,
+    },
+    config::{self, alias, locator, network, UnresolvedContract},
+    print::Print,
+    rpc,
+    xdr::MuxedAccount,
+};
+
+pub const LONG_ABOUT: &str = "
+
+With `--manifest`, deploys several contracts described in a TOML file, or JSON if its extension is `.json`, keyed by the alias to save each contract's ID under:
+
+    [contracts.pi_coin]
+    wasm = \"target/wasm32v1-none/release/pi_coin.wasm\"   # relative to the manifest, or wasm_hash
+    constructor = { admin = \"alice\", symbol = \"PI\" }
+
+    [contracts.pi_staking]
+    wasm = \"target/wasm32v1-none/release/pi_staking.wasm\"
+    salt = \"1\"                                           # optional
+    constructor = { token = \"${pi_coin}\" }
+    init = [{ fn = \"set_rate\", args = { rate = 5 } }]
+
+`${alias}` is replaced with the ID of the contract with that alias, in the manifest or saved, and contracts are deployed after the ones they refer to. Each contract's ID is derived from the source account and its salt, the hash of its alias by default, so contracts already deployed are skipped. The init functions called on a contract are recorded with its alias, so only the ones not called yet, e.g. after one failed, are called again.
+";
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("manifest: {0}")]
+    File(calls::Error),
+    #[error("manifest {0} has no contracts")]
+    NoContracts(PathBuf),
+    #[error("contract `{0}` needs one of `wasm` or `wasm_hash`")]
+    WasmNotProvided(String),
+    #[error("cannot parse salt of contract `{alias}`: {salt}")]
+    CannotParseSalt { alias: String, salt: String },
+    #[error("contract `{alias}` refers to `${{{reference}}}`, which is neither in the manifest nor a saved contract alias")]
+    UnknownReference { alias: String, reference: String },
+    #[error("contract `{alias}`: {error}")]
+    Variable { alias: String, error: calls::Error },
+    #[error("contracts refer to each other in a cycle: {}", .0.join(", "))]
+    Cycle(Vec<String>),
+    #[error("Only ed25519 accounts are allowed")]
+    OnlyEd25519AccountsAllowed,
+    #[error("cannot deploy contract `{alias}`: {error}")]
+    Deploy {
+        alias: String,
+        error: Box<wasm::Error>,
+    },
+    #[error("contract `{alias}` was built but not deployed")]
+    NotDeployed { alias: String },
+    #[error("contract `{alias}` was deployed, but calling `{function}` on it failed, deploy the manifest again to call it and the init functions after it: {error}")]
+    Init {
+        alias: String,
+        function: String,
+        error: Box<invoke::Error>,
+    },
+    #[error(transparent)]
+    Alias(#[from] utils::Error),
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    WasmId(#[from] crate::commands::contract::id::wasm::Error),
+}
+
+/// Contracts to deploy together, keyed by the alias to save each contract's ID under.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    pub contracts: BTreeMap<String, Contract>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Contract {
+    /// WASM file to deploy, relative to the manifest
+    pub wasm: Option<PathBuf>,
+    /// Hash of an already uploaded WASM
+    pub wasm_hash: Option<String>,
+    /// Salt of the contract ID, the hash of the alias by default
+    pub salt: Option<String>,
+    /// Arguments of the contract's `__constructor`
+    #[serde(default)]
+    pub constructor: BTreeMap<String, Value>,
+    /// Functions to call once the contract is deployed
+    #[serde(default)]
+    pub init: Vec<Call>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Call {
+    #[serde(rename = "fn")]
+    pub function: String,
+    #[serde(default)]
+    pub args: BTreeMap<String, Value>,
+}
+
+impl Manifest {
+    pub fn read(path: &Path) -> Result<Self, Error> {
+        let manifest: Manifest = calls::read(path).map_err(Error::File)?;
+        if manifest.contracts.is_empty() {
+            return Err(Error::NoContracts(path.to_path_buf()));
+        }
+        for (alias, contract) in &manifest.contracts {
+            utils::alias_validator(alias)?;
+            if contract.wasm.is_some() == contract.wasm_hash.is_some() {
+                return Err(Error::WasmNotProvided(alias.clone()));
+            }
+        }
+        Ok(manifest)
+    }
+
+    /// Aliases of the contracts in the order to deploy them, each after the contracts of the
+    /// manifest its arguments refer to.
+    pub fn order(&self) -> Result<Vec<&str>, Error> {
+        let mut pending: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+        for (alias, contract) in &self.contracts {
+            let references = contract
+                .references(alias)?
+                .into_iter()
+                .filter(|r| r != alias && self.contracts.contains_key(r))
+                .collect();
+            pending.insert(alias, references);
+        }
+        let mut order = Vec::new();
+        while !pending.is_empty() {
+            let ready: Vec<&str> = pending
+                .iter()
+                .filter(|(_, references)| references.is_empty())
+                .map(|(alias, _)| *alias)
+                .collect();
+            if ready.is_empty() {
+                return Err(Error::Cycle(
+                    pending.keys().map(ToString::to_string).collect(),
+                ));
+            }
+            for alias in ready {
+                pending.remove(alias);
+                for references in pending.values_mut() {
+                    references.remove(alias);
+                }
+                order.push(alias);
+            }
+        }
+        Ok(order)
+    }
+}
+
+impl Contract {
+    /// Aliases that the arguments of the constructor and init calls refer to with `${alias}`.
+    pub fn references(&self, alias: &str) -> Result<BTreeSet<String>, Error> {
+        let mut references = BTreeSet::new();
+        for value in self
+            .constructor
+            .values()
+            .chain(self.init.iter().flat_map(|call| call.args.values()))
+        {
+            let variables = calls::value_variables(value).map_err(|error| Error::Variable {
+                alias: alias.to_string(),
+                error,
+            })?;
+            references.extend(variables.into_iter().map(|v| calls::name(v).to_string()));
+        }
+        Ok(references)
+    }
+
+    fn salt(&self, alias: &str) -> Result<[u8; 32], Error> {
+        let Some(salt) = &self.salt else {
+            return Ok(Sha256::digest(alias.as_bytes()).into());
+        };
+        soroban_spec_tools::utils::padded_hex_from_str(salt, 32)
+            .ok()
+            .and_then(|salt| salt.try_into().ok())
+            .ok_or_else(|| Error::CannotParseSalt {
+                alias: alias.to_string(),
+                salt: salt.clone(),
+            })
+    }
+}
+
+/// Arguments for the implicit CLI of a contract function, as `--arg-name=value`, with
+/// `${alias}` replaced by the ID of the contract.
+fn slop_args(
+    alias: &str,
+    args: &BTreeMap<String, Value>,
+    ids: &BTreeMap<String, Value>,
+) -> Result<Vec<OsString>, Error> {
+    calls::slop_args(args, ids).map_err(|error| Error::Variable {
+        alias: alias.to_string(),
+        error,
+    })
+}
+
+/// Deploy the contracts of a manifest that aren't deployed yet, call their init functions not
+/// called yet and save their aliases.
+pub async fn deploy(path: &Path, cmd: &wasm::Cmd, global_args: &global::Args) -> Result<(), Error> {
+    let print = Print::new(global_args.quiet);
+    let manifest = Manifest::read(path)?;
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let order = manifest.order()?;
+    let config = &cmd.config;
+    let network = config.get_network()?;
+    let network_passphrase = &network.network_passphrase;
+    let client = network.rpc_client()?;
+    client
+        .verify_network_passphrase(Some(network_passphrase))
+        .await?;
+
+    let ids = contract_ids(&manifest, config, network_passphrase).await?;
+    let values = ids
+        .iter()
+        .map(|(alias, id)| (alias.clone(), Value::String(id.to_string())))
+        .collect();
+
+    let called = plan(
+        &manifest,
+        &order,
+        &ids,
+        config,
+        &client,
+        network_passphrase,
+        &print,
+    )
+    .await?;
+
+    for alias in &order {
+        let contract = &manifest.contracts[*alias];
+        let mut id = ids[*alias];
+        if called.contains_key(alias) {
+            print.infoln(format!("Skipping {alias}, already deployed at {id}"));
+        } else {
+            print.infoln(format!("Deploying {alias}"));
+            let deploy = wasm::Cmd {
+                wasm: contract.wasm.as_ref().map(|wasm| dir.join(wasm)),
+                wasm_hash: contract.wasm_hash.clone(),
+                salt: Some(hex::encode(contract.salt(alias)?)),
+                alias: None,
+                build_only: false,
+                manifest: None,
+                slop: slop_args(alias, &contract.constructor, &values)?,
+                ..cmd.clone()
+            };
+            let res = deploy
+                .run_against_rpc_server(Some(global_args), None)
+                .await
+                .map_err(|error| Error::Deploy {
+                    alias: alias.to_string(),
+                    error: Box::new(error),
+                })?;
+            let TxnResult::Res(deployed_id) = res else {
+                return Err(Error::NotDeployed {
+                    alias: alias.to_string(),
+                });
+            };
+            id = deployed_id;
+        }
+        config
+            .locator
+            .save_contract_id(network_passphrase, &id, alias)?;
+        let calls = called.get(alias).copied().unwrap_or_default();
+        for (i, call) in contract.init.iter().enumerate().skip(calls) {
+            print.infoln(format!("Calling `{}` on {alias}", call.function));
+            invoke::Cmd {
+                contract_id: UnresolvedContract::Resolved(id),
+                slop: [OsString::from(&call.function)]
+                    .into_iter()
+                    .chain(slop_args(alias, &call.args, &values)?)
+                    .collect(),
+                config: config.clone(),
+                resources: cmd.resources.clone(),
+                ..Default::default()
+            }
+            .run_against_rpc_server(Some(global_args), None)
+            .await
+            .map_err(|error| Error::Init {
+                alias: alias.to_string(),
+                function: call.function.clone(),
+                error: Box::new(error),
+            })?;
+            config.locator.save_contract_init(
+                network_passphrase,
+                alias,
+                alias::Init {
+                    contract_id: id.to_string(),
+                    calls: i + 1,
+                },
+            )?;
+        }
+        println!("{alias}: {id}");
+    }
+    print.checkln(format!(
+        "Deployed {} contract(s), {} already deployed",
+        order.len() - called.len(),
+        called.len()
+    ));
+    Ok(())
+}
+
+/// Print what deploying the manifest will do, and return the contracts already deployed, with
+/// the number of their init functions already called.
+async fn plan<'a>(
+    manifest: &Manifest,
+    order: &[&'a str],
+    ids: &BTreeMap<String, stellar_strkey::Contract>,
+    config: &config::Args,
+    client: &rpc::Client,
+    network_passphrase: &str,
+    print: &Print,
+) -> Result<BTreeMap<&'a str, usize>, Error> {
+    let mut called = BTreeMap::new();
+    print.infoln("Plan:");
+    for alias in order {
+        let id = ids[*alias];
+        let init = &manifest.contracts[*alias].init;
+        match client.get_contract_data(&id.0).await {
+            Ok(_) => {
+                let calls = config
+                    .locator
+                    .get_contract_init(alias, network_passphrase)?
+                    .filter(|init| init.contract_id == id.to_string())
+                    .map_or(0, |init| init.calls);
+                if calls < init.len() {
+                    print.plusln(format!(
+                        "{alias}: {id}, already deployed, to call {}",
+                        describe_calls(&init[calls..])
+                    ));
+                } else {
+                    print.blankln(format!("{alias}: {id}, already deployed"));
+                }
+                called.insert(*alias, calls);
+            }
+            Err(rpc::Error::NotFound(..)) => {
+                print.plusln(format!(
+                    "{alias}: {id}, to deploy{}",
+                    if init.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" and call {}", describe_calls(init))
+                    }
+                ));
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(called)
+}
+
+/// IDs of the contracts of the manifest, deployed by the source account with their salts, and
+/// of the saved contract aliases their arguments refer to.
+async fn contract_ids(
+    manifest: &Manifest,
+    config: &config::Args,
+    network_passphrase: &str,
+) -> Result<BTreeMap<String, stellar_strkey::Contract>, Error> {
+    let MuxedAccount::Ed25519(source_account) = config.source_account().await? else {
+        return Err(Error::OnlyEd25519AccountsAllowed);
+    };
+    let source_account = stellar_strkey::ed25519::PublicKey(source_account.0);
+    let mut ids = BTreeMap::new();
+    for (alias, contract) in &manifest.contracts {
+        let preimage = contract_preimage(&source_account, contract.salt(alias)?);
+        ids.insert(
+            alias.clone(),
+            get_contract_id(preimage, network_passphrase)?,
+        );
+    }
+    for (alias, contract) in &manifest.contracts {
+        for reference in contract.references(alias)? {
+            if ids.contains_key(&reference) {
+                continue;
+            }
+            let id = config
+                .locator
+                .get_contract_id(&reference, network_passphrase)?
+                .ok_or_else(|| Error::UnknownReference {
+                    alias: alias.clone(),
+                    reference: reference.clone(),
+                })?;
+            ids.insert(reference, id);
+        }
+    }
+    Ok(ids)
+}
+
+fn describe_calls(calls: &[Call]) -> String {
+    calls
+        .iter()
+        .map(|call| format!("`{}`", call.function))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(toml: &str) -> Manifest {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn deploy_order() {
+        let m = manifest(
+            r#"
+            [contracts.staking]
+            wasm = "staking.wasm"
+            constructor = { token = "${coin}", oracle = "${oracle}" }
+
+            [contracts.coin]
+            wasm = "coin.wasm"
+            constructor = { admin = "alice" }
+
+            [contracts.governance]
+            wasm_hash = "0000000000000000000000000000000000000000000000000000000000000000"
+            init = [{ fn = "set_staking", args = { staking = "${staking}" } }]
+            "#,
+        );
+        assert_eq!(
+            m.contracts["staking"].references("staking").unwrap(),
+            BTreeSet::from(["coin".to_string(), "oracle".to_string()])
+        );
+        assert_eq!(m.order().unwrap(), vec!["coin", "staking", "governance"]);
+
+        let m = manifest(
+            r#"
+            [contracts.a]
+            wasm = "a.wasm"
+            constructor = { b = "${b}" }
+
+            [contracts.b]
+            wasm = "b.wasm"
+            init = [{ fn = "set_a", args = { a = "${a}" } }]
+            "#,
+        );
+        assert!(matches!(m.order(), Err(Error::Cycle(aliases)) if aliases == ["a", "b"]));
+    }
+
+    #[test]
+    fn slop_with_unknown_reference() {
+        let coin = stellar_strkey::Contract([1; 32]);
+        let ids = BTreeMap::from([("coin".to_string(), Value::from(coin.to_string()))]);
+        let args = BTreeMap::from([("token".to_string(), Value::from("${coin}"))]);
+        assert_eq!(
+            slop_args("pool", &args, &ids).unwrap(),
+            vec![OsString::from(format!("--token={coin}"))]
+        );
+        let args = BTreeMap::from([("token".to_string(), Value::from("${oracle}"))]);
+        assert!(matches!(
+            slop_args("pool", &args, &ids),
+            Err(Error::Variable { alias, error: calls::Error::UnknownVariable(variable) })
+                if alias == "pool" && variable == "oracle"
+        ));
+    }
+}
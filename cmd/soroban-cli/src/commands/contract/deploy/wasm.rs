@@ -1,4 +1,4 @@
-use crate::commands::contract::deploy::utils::alias_validator;
+use crate::commands::contract::deploy::{manifest, utils::alias_validator};
 use crate::resources;
 use std::array::TryFromSliceError;
 use std::ffi::OsString;
@@ -38,7 +38,7 @@ pub const CONSTRUCTOR_FUNCTION_NAME: &str = "__constructor";
 #[command(group(
     clap::ArgGroup::new("wasm_src")
         .required(true)
-        .args(&["wasm", "wasm_hash"]),
+        .args(&["wasm", "wasm_hash", "manifest"]),
 ))]
 #[group(skip)]
 pub struct Cmd {
@@ -48,6 +48,15 @@ pub struct Cmd {
     /// Hash of the already installed/deployed WASM file
     #[arg(long = "wasm-hash", conflicts_with = "wasm", group = "wasm_src")]
     pub wasm_hash: Option<String>,
+    /// Manifest of several contracts to deploy, with their WASM files, constructor arguments and
+    /// init calls, in TOML, or JSON if its extension is `.json`. Contracts already deployed are
+    /// skipped, and each contract's ID is saved under its alias in the manifest
+    #[arg(
+        long,
+        group = "wasm_src",
+        conflicts_with_all = ["wasm", "wasm_hash", "salt", "alias", "build_only", "CONTRACT_CONSTRUCTOR_ARGS"]
+    )]
+    pub manifest: Option<std::path::PathBuf>,
     /// Custom salt 32-byte salt for the token id
     #[arg(long)]
     pub salt: Option<String>,
@@ -147,10 +156,18 @@ pub enum Error {
 
     #[error(transparent)]
     Fetch(#[from] fetch::Error),
+
+    #[error(transparent)]
+    Manifest(Box<manifest::Error>),
 }
 
 impl Cmd {
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        if let Some(manifest) = &self.manifest {
+            return manifest::deploy(manifest, self, global_args)
+                .await
+                .map_err(|e| Error::Manifest(Box::new(e)));
+        }
         let res = self
             .run_against_rpc_server(Some(global_args), None)
             .await?
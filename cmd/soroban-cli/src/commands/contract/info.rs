@@ -3,6 +3,7 @@ use std::fmt::Debug;
 use crate::commands::global;
 
 pub mod build;
+pub mod diff;
 pub mod env_meta;
 pub mod interface;
 pub mod meta;
@@ -56,6 +57,16 @@ pub enum Cmd {
     /// If the contract has a meta entry like `source_repo=github:user/repo`, this command will try
     /// to fetch the attestation information for the WASM file.
     Build(build::Cmd),
+
+    /// Compare the interfaces of two contracts.
+    ///
+    /// Each contract is read from a local wasm file, the hash of a wasm on
+    /// the network, or a contract ID/alias. Lists the added, removed and
+    /// changed functions, types, error codes and events, and which of them
+    /// can break callers built for the base interface.
+    ///
+    /// Exits with an error if there are breaking changes.
+    Diff(diff::Cmd),
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -71,6 +82,9 @@ pub enum Error {
 
     #[error(transparent)]
     Build(#[from] build::Error),
+
+    #[error(transparent)]
+    Diff(#[from] diff::Error),
 }
 
 impl Cmd {
@@ -80,6 +94,7 @@ impl Cmd {
             Cmd::Meta(meta) => meta.run(global_args).await?,
             Cmd::EnvMeta(env_meta) => env_meta.run(global_args).await?,
             Cmd::Build(build) => build.run(global_args).await?,
+            Cmd::Diff(diff) => diff.run(global_args).await?,
         }
 
         Ok(())
@@ -0,0 +1,140 @@
+use std::{convert::Infallible, fmt::Debug, path::PathBuf, str::FromStr};
+
+use clap::Parser;
+use serde::Serialize;
+use soroban_spec_tools::contract::{self, Spec};
+
+use crate::commands::contract::info::shared::{self, fetch, Fetched};
+use crate::commands::contract::spec_diff::{self, Change};
+use crate::commands::global;
+use crate::config::{self, locator, network};
+use crate::print::Print;
+use crate::xdr::ScSpecEntry;
+
+/// Compare the interfaces of two contracts, exiting with an error if the new one has breaking
+/// changes
+/// e.g. `stellar contract info diff old.wasm new.wasm --output json`
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Base interface: a wasm file, the hash of a wasm on the network, or a contract ID/alias
+    pub base: SpecSource,
+    /// New interface, compared against the base: a wasm file, the hash of a wasm on the network,
+    /// or a contract ID/alias
+    pub new: SpecSource,
+    /// Format of the output
+    #[arg(long, default_value = "text")]
+    pub output: DiffOutput,
+    #[command(flatten)]
+    pub network: network::Args,
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, clap::ValueEnum, Default)]
+pub enum DiffOutput {
+    /// One line per change, breaking ones prefixed with `breaking:`
+    #[default]
+    Text,
+    /// JSON output of the changes (one line, not formatted)
+    Json,
+    /// Formatted (multiline) JSON output of the changes
+    JsonFormatted,
+}
+
+/// Where to read a contract interface from.
+#[derive(Debug, Clone)]
+pub enum SpecSource {
+    Wasm(PathBuf),
+    WasmHash(String),
+    Contract(config::UnresolvedContract),
+}
+
+impl FromStr for SpecSource {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let path = PathBuf::from(s);
+        if path.is_file() {
+            Ok(SpecSource::Wasm(path))
+        } else if s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit()) {
+            Ok(SpecSource::WasmHash(s.to_string()))
+        } else {
+            Ok(SpecSource::Contract(s.parse()?))
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Report<'a> {
+    breaking: bool,
+    changes: &'a [Change],
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Wasm(#[from] shared::Error),
+    #[error(transparent)]
+    Spec(#[from] contract::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("the new interface has {0} breaking change(s)")]
+    Breaking(usize),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let base = self.spec(&self.base, &print).await?;
+        let new = self.spec(&self.new, &print).await?;
+        let changes = spec_diff::diff(&base, &new);
+        let breaking = changes.iter().filter(|change| change.breaking).count();
+
+        let report = Report {
+            breaking: breaking > 0,
+            changes: &changes,
+        };
+        match self.output {
+            DiffOutput::Text => {
+                if changes.is_empty() {
+                    print.infoln("The interfaces are the same");
+                }
+                for change in &changes {
+                    if change.breaking {
+                        println!("breaking: {change}");
+                    } else {
+                        println!("{change}");
+                    }
+                }
+            }
+            DiffOutput::Json => println!("{}", serde_json::to_string(&report)?),
+            DiffOutput::JsonFormatted => println!("{}", serde_json::to_string_pretty(&report)?),
+        }
+
+        if breaking > 0 {
+            return Err(Error::Breaking(breaking));
+        }
+        Ok(())
+    }
+
+    async fn spec(&self, source: &SpecSource, print: &Print) -> Result<Vec<ScSpecEntry>, Error> {
+        let mut args = shared::Args {
+            network: self.network.clone(),
+            locator: self.locator.clone(),
+            ..Default::default()
+        };
+        match source {
+            SpecSource::Wasm(path) => args.wasm = Some(path.clone()),
+            SpecSource::WasmHash(hash) => args.wasm_hash = Some(hash.clone()),
+            SpecSource::Contract(contract) => args.contract_id = Some(contract.clone()),
+        }
+        let Fetched { contract, .. } = fetch(&args, print).await?;
+        Ok(match contract {
+            shared::Contract::Wasm { wasm_bytes } => Spec::new(&wasm_bytes)?.spec,
+            shared::Contract::StellarAssetContract => {
+                Spec::spec_to_base64(stellar_asset_spec::xdr())?.1
+            }
+        })
+    }
+}
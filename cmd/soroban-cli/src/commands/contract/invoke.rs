@@ -25,20 +25,26 @@ use crate::{
         txn_result::{TxnEnvelopeResult, TxnResult},
         NetworkRunnable,
     },
-    config::{self, data, locator, network},
+    config::{self, data, locator, network, secret},
     get_spec::{self, get_remote_contract_spec},
     print, rpc,
+    signer::{
+        custom_account::{self, AuthSigner, CustomAccount, SignatureFormat},
+        Signer,
+    },
     xdr::{
         self, AccountEntry, AccountEntryExt, AccountId, ContractEvent, ContractEventType,
-        DiagnosticEvent, HostFunction, InvokeContractArgs, InvokeHostFunctionOp, Limits, Memo,
-        MuxedAccount, Operation, OperationBody, Preconditions, PublicKey, ScSpecEntry,
-        SequenceNumber, String32, StringM, Thresholds, Transaction, TransactionExt, Uint256, VecM,
-        WriteXdr,
+        ContractId, DiagnosticEvent, Hash, HostFunction, InvokeContractArgs, InvokeHostFunctionOp,
+        Limits, Memo, MuxedAccount, Operation, OperationBody, Preconditions, PublicKey, ScAddress,
+        ScSpecEntry, SequenceNumber, SorobanCredentials, String32, StringM, Thresholds,
+        Transaction, TransactionExt, Uint256, VecM, WriteXdr,
     },
     Pwd,
 };
 use soroban_spec_tools::contract;
 
+pub mod local;
+
 #[derive(Parser, Debug, Default, Clone)]
 #[allow(clippy::struct_excessive_bools)]
 #[group(skip)]
@@ -47,8 +53,8 @@ pub struct Cmd {
     #[arg(long = "id", env = "STELLAR_CONTRACT_ID")]
     pub contract_id: config::UnresolvedContract,
 
-    // For testing only
-    #[arg(skip)]
+    /// Path to the contract's wasm, to read its interface from instead of fetching it from the network
+    #[arg(long)]
     pub wasm: Option<std::path::PathBuf>,
 
     /// ⚠️ Deprecated, use `--send=no`. View the result simulating and do not sign and submit transaction.
@@ -72,6 +78,52 @@ pub struct Cmd {
     /// Build the transaction and only write the base64 xdr to stdout
     #[arg(long)]
     pub build_only: bool,
+
+    #[command(flatten)]
+    pub sequence: crate::commands::tx::sequence::Args,
+
+    /// Simulation of the transaction, to set its footprint, resources and fee without simulating
+    /// it again. Either the JSON response of `simulateTransaction`, or a simulation from the
+    /// action log, e.g. `stellar cache actionlog read --id <ID>`
+    #[arg(long, value_name = "FILE", requires = "build_only")]
+    pub simulation: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub memo: crate::commands::tx::memo::Args,
+
+    /// Invoke the contract locally against a ledger snapshot, as written by `snapshot create` or
+    /// `snapshot merge`, instead of on the network. Prints the result, events, footprint,
+    /// resources and ledger changes. Authorizations are recorded, not checked
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["build_only", "simulation", "channels"]
+    )]
+    pub ledger_snapshot: Option<PathBuf>,
+
+    /// Write the ledger snapshot, with the changes of the local invocation, to this file. Can be
+    /// the snapshot passed with `--ledger-snapshot`
+    #[arg(long, value_name = "FILE", requires = "ledger_snapshot")]
+    pub ledger_snapshot_out: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub channels: config::channels::Args,
+
+    /// Sign the auth entries of a custom account contract (C-address) with a key, as
+    /// `CONTRACT=KEY`, e.g. `--auth-signer CC…=alice`. The contract can be an address or an alias,
+    /// the key an identity, a secret key or a seed phrase. Pass multiple times to sign with several
+    /// keys or for several contracts.
+    #[arg(
+        long = "auth-signer",
+        env = "STELLAR_AUTH_SIGNER",
+        value_name = "CONTRACT=KEY",
+        value_delimiter = ','
+    )]
+    pub auth_signers: Vec<AuthSigner>,
+
+    /// How signatures are passed to a custom account contract's `__check_auth`
+    #[arg(long, value_enum, default_value_t)]
+    pub auth_signature_format: SignatureFormat,
 }
 
 impl FromStr for Cmd {
@@ -132,6 +184,10 @@ pub enum Error {
 
     #[error(transparent)]
     Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Memo(#[from] crate::commands::tx::memo::Error),
+    #[error(transparent)]
+    Channels(#[from] config::channels::Error),
 
     #[error("Contract Error\n{0}: {1}")]
     ContractInvoke(String, String),
@@ -162,6 +218,27 @@ pub enum Error {
 
     #[error(transparent)]
     Fetch(#[from] fetch::Error),
+
+    #[error(transparent)]
+    CustomAccount(#[from] custom_account::Error),
+
+    #[error(transparent)]
+    Secret(#[from] secret::Error),
+
+    #[error(transparent)]
+    Local(#[from] local::Error),
+
+    #[error("cannot read simulation {0}: {1}")]
+    CannotReadSimulation(PathBuf, String),
+
+    #[error("a channel account uses its own sequence number, `--sequence-number` can't be used with `--channel`")]
+    SequenceNumberWithChannel,
+
+    #[error("a channel account is leased only while its transaction is sent, `--build-only` can't be used with `--channel`")]
+    BuildOnlyWithChannel,
+
+    #[error("building the transaction offline, with `--build-only` and `--sequence-number`, needs the contract's interface, pass the contract's wasm with `--wasm`")]
+    MissingWasmOffline,
 }
 
 impl From<Infallible> for Error {
@@ -208,6 +285,38 @@ impl Cmd {
             .transpose()
     }
 
+    /// Read the simulation passed with `--simulation`, either a `simulateTransaction` response or
+    /// a simulation from the action log.
+    fn read_simulation(&self) -> Result<Option<SimulateTransactionResponse>, Error> {
+        let Some(path) = &self.simulation else {
+            return Ok(None);
+        };
+        let error = |e: String| Error::CannotReadSimulation(path.clone(), e);
+        let contents = fs::read_to_string(path).map_err(|e| error(e.to_string()))?;
+        let mut value: serde_json::Value =
+            serde_json::from_str(&contents).map_err(|e| error(e.to_string()))?;
+        if let Some(response) = value.pointer_mut("/action/simulate/response") {
+            value = response.take();
+        }
+        let sim_res: SimulateTransactionResponse =
+            serde_json::from_value(value).map_err(|e| error(e.to_string()))?;
+        if let Some(e) = &sim_res.error {
+            return Err(error(format!("the simulation failed, {e}")));
+        }
+        Ok(Some(sim_res))
+    }
+
+    /// Set the footprint, resources and fee of a transaction built with `--build-only` from the
+    /// simulation passed with `--simulation`, if any.
+    fn apply_simulation(&self, tx: Box<Transaction>) -> Result<Box<Transaction>, Error> {
+        let Some(sim_res) = self.read_simulation()? else {
+            return Ok(tx);
+        };
+        let assembled = Assembled::new(&tx, sim_res, self.resources.resource_fee)?;
+        let assembled = self.resources.apply_to_assembled_txn(assembled);
+        Ok(Box::new(assembled.transaction().clone()))
+    }
+
     fn should_send_tx(&self, sim_res: &SimulateTransactionResponse) -> Result<ShouldSend, Error> {
         Ok(match self.send {
             Send::Default => {
@@ -237,8 +346,13 @@ impl Cmd {
         let AccountId(PublicKey::PublicKeyTypeEd25519(account_id)) =
             account_details.account_id.clone();
 
-        let tx =
-            build_invoke_contract_tx(host_function_params.clone(), sequence + 1, 100, account_id)?;
+        let tx = build_invoke_contract_tx(
+            host_function_params.clone(),
+            sequence + 1,
+            100,
+            account_id,
+            Memo::None,
+        )?;
         Ok(simulate_and_assemble_transaction(
             rpc_client,
             &tx,
@@ -247,6 +361,81 @@ impl Cmd {
         )
         .await?)
     }
+
+    async fn sign_soroban_authorizations(
+        &self,
+        txn: &Transaction,
+        signers: &[Signer],
+        config: &config::Args,
+        global_args: Option<&global::Args>,
+    ) -> Result<Option<Transaction>, Error> {
+        let custom_accounts = self.custom_accounts(txn, config, global_args).await?;
+        Ok(config
+            .sign_soroban_authorizations(txn, signers, &custom_accounts)
+            .await?)
+    }
+
+    /// Build a custom account signer for every contract address that has to authorize the
+    /// transaction and has keys passed with `--auth-signer`. Signing fails for the contract
+    /// addresses without any.
+    async fn custom_accounts(
+        &self,
+        txn: &Transaction,
+        config: &config::Args,
+        global_args: Option<&global::Args>,
+    ) -> Result<Vec<CustomAccount>, Error> {
+        if self.auth_signers.is_empty() {
+            return Ok(Vec::new());
+        }
+        let quiet = global_args.is_some_and(|g| g.quiet);
+        let network = config.get_network()?;
+        let mut auth_signers = Vec::with_capacity(self.auth_signers.len());
+        for AuthSigner { contract, key } in &self.auth_signers {
+            let contract =
+                contract.resolve_contract_id(&config.locator, &network.network_passphrase)?;
+            auth_signers.push((contract, key));
+        }
+        let mut custom_accounts = Vec::new();
+        for address in contract_auth_addresses(txn) {
+            let keys = auth_signers
+                .iter()
+                .filter(|(contract, _)| *contract == address)
+                .map(|(_, key)| key)
+                .collect::<Vec<_>>();
+            if keys.is_empty() {
+                continue;
+            }
+            let spec = if self.auth_signature_format == SignatureFormat::Auto {
+                let entries = get_remote_contract_spec(
+                    &address.0,
+                    &config.locator,
+                    &config.network,
+                    global_args,
+                    Some(config),
+                )
+                .await?;
+                Some(soroban_spec_tools::Spec::new(&entries))
+            } else {
+                None
+            };
+            let encoding = self
+                .auth_signature_format
+                .encoding(&address, spec.as_ref())?;
+
+            let mut signers = Vec::with_capacity(keys.len());
+            for key_or_name in keys {
+                let secret = config.locator.get_secret_key(key_or_name)?;
+                signers.push(secret.signer(config.hd_path(), Print::new(quiet)).await?);
+            }
+
+            custom_accounts.push(CustomAccount {
+                address,
+                signers,
+                encoding,
+            });
+        }
+        Ok(custom_accounts)
+    }
 }
 
 #[async_trait::async_trait]
@@ -254,6 +443,7 @@ impl NetworkRunnable for Cmd {
     type Error = Error;
     type Result = TxnResult<String>;
 
+    #[allow(clippy::too_many_lines)]
     async fn run_against_rpc_server(
         &self,
         global_args: Option<&global::Args>,
@@ -262,37 +452,85 @@ impl NetworkRunnable for Cmd {
         let config = config.unwrap_or(&self.config);
         let quiet = global_args.is_some_and(|g| g.quiet);
         let print = print::Print::new(quiet);
-        let network = config.get_network()?;
-
-        tracing::trace!(?network);
+        if self.sequence.sequence_number.is_some() && self.channels.is_set() {
+            return Err(Error::SequenceNumberWithChannel);
+        }
+        if self.build_only && self.channels.is_set() {
+            return Err(Error::BuildOnlyWithChannel);
+        }
 
+        let network_passphrase = config.get_network_passphrase()?;
         let contract_id = self
             .contract_id
-            .resolve_contract_id(&config.locator, &network.network_passphrase)?;
+            .resolve_contract_id(&config.locator, &network_passphrase)?;
+
+        let mut ledger = self
+            .ledger_snapshot
+            .as_deref()
+            .map(local::Ledger::read)
+            .transpose()?;
+        let local_spec_entries = match (self.spec_entries()?, &ledger) {
+            (Some(spec_entries), _) => Some(spec_entries),
+            (None, Some(ledger)) => Some(ledger.spec_entries(&contract_id)?),
+            (None, None) => None,
+        };
+        let spec_entries = match local_spec_entries {
+            Some(spec_entries) => spec_entries,
+            None if self.build_only && self.sequence.sequence_number.is_some() => {
+                return Err(Error::MissingWasmOffline);
+            }
+            None => get_remote_contract_spec(
+                &contract_id.0,
+                &config.locator,
+                &config.network,
+                global_args,
+                Some(config),
+            )
+            .await
+            .map_err(Error::from)?,
+        };
 
-        let spec_entries = self.spec_entries()?;
+        let params =
+            build_host_function_parameters(&contract_id, &self.slop, &spec_entries, config).await?;
 
-        if let Some(spec_entries) = &spec_entries {
-            // For testing wasm arg parsing
-            build_host_function_parameters(&contract_id, &self.slop, spec_entries, config).await?;
+        let (function, spec, host_function_params, signers) = params;
+
+        if let Some(ledger) = &mut ledger {
+            let source_account = config.source_account().await?.account_id();
+            let invocation = ledger.invoke(
+                &HostFunction::InvokeContract(host_function_params),
+                &source_account,
+            )?;
+            crate::log::event::all(&invocation.diagnostic_events);
+            crate::log::event::contract(&invocation.diagnostic_events, &print);
+            invocation.print(&print);
+            let return_value = invocation.result.map_err(local::Error::Host)?;
+            if let Some(path) = &self.ledger_snapshot_out {
+                ledger.write(path)?;
+            }
+            return Ok(output_to_string(&spec, &return_value, &function)?);
         }
 
-        let client = network.rpc_client()?;
+        // With an explicit sequence number, a transaction built with `--build-only` needs nothing
+        // from the network
+        if let (true, Some(seq_num)) = (self.build_only, &self.sequence.sequence_number) {
+            let AccountId(PublicKey::PublicKeyTypeEd25519(account_id)) =
+                config.source_account().await?.account_id();
+            let tx = Box::new(build_invoke_contract_tx(
+                host_function_params,
+                seq_num.0,
+                config.get_inclusion_fee()?,
+                account_id,
+                self.memo.memo()?,
+            )?);
+            return Ok(TxnResult::Txn(self.apply_simulation(tx)?));
+        }
 
-        let spec_entries = get_remote_contract_spec(
-            &contract_id.0,
-            &config.locator,
-            &config.network,
-            global_args,
-            Some(config),
-        )
-        .await
-        .map_err(Error::from)?;
+        let network = config.get_network()?;
 
-        let params =
-            build_host_function_parameters(&contract_id, &self.slop, &spec_entries, config).await?;
+        tracing::trace!(?network);
 
-        let (function, spec, host_function_params, signers) = params;
+        let client = network.rpc_client()?;
 
         // `self.build_only` will be checked again below and the fn will return a TxnResult::Txn
         // if the user passed the --build-only flag
@@ -334,18 +572,30 @@ impl NetworkRunnable for Cmd {
             return Ok(output_to_string(&spec, &return_value[0].xdr, &function)?);
         };
 
-        let sequence: i64 = account_details.seq_num.into();
+        let sequence: i64 = match &self.sequence.sequence_number {
+            Some(seq_num) => seq_num.0,
+            None => i64::from(account_details.seq_num) + 1,
+        };
         let AccountId(PublicKey::PublicKeyTypeEd25519(account_id)) = account_details.account_id;
 
-        let tx = Box::new(build_invoke_contract_tx(
+        let mut tx = Box::new(build_invoke_contract_tx(
             host_function_params.clone(),
-            sequence + 1,
+            sequence,
             config.get_inclusion_fee()?,
             account_id,
+            self.memo.memo()?,
         )?);
+        let channel = self.channels.lease(&config.locator).await?;
+        if let Some(channel) = &channel {
+            print.infoln(format!("Using channel account {}", channel.name));
+            let seq_num = config
+                .next_sequence_number(channel.account.clone().account_id())
+                .await?;
+            channel.apply(&mut tx, seq_num)?;
+        }
 
         if self.build_only {
-            return Ok(TxnResult::Txn(tx));
+            return Ok(TxnResult::Txn(self.apply_simulation(tx)?));
         }
 
         let txn = simulate_and_assemble_transaction(
@@ -366,13 +616,18 @@ impl NetworkRunnable for Cmd {
         let global::Args { no_cache, .. } = global_args.cloned().unwrap_or_default();
 
         // Need to sign all auth entries
-        if let Some(tx) = config.sign_soroban_authorizations(&txn, &signers).await? {
+        if let Some(tx) = self
+            .sign_soroban_authorizations(&txn, &signers, config, global_args)
+            .await?
+        {
             *txn = tx;
         }
 
-        let res = client
-            .send_transaction_polling(&config.sign(*txn, quiet).await?)
-            .await?;
+        let mut tx_env = config.sign(*txn, quiet).await?;
+        if let Some(channel) = &channel {
+            tx_env = channel.sign(&tx_env, &network, quiet).await?;
+        }
+        let res = client.send_transaction_polling(&tx_env).await?;
 
         self.resources.print_cost_info(&res)?;
 
@@ -407,11 +662,33 @@ fn default_account_entry() -> AccountEntry {
     }
 }
 
+/// Contract addresses with address credentials in the transaction's auth entries
+fn contract_auth_addresses(txn: &Transaction) -> Vec<stellar_strkey::Contract> {
+    let mut addresses = Vec::new();
+    for op in txn.operations.iter() {
+        let OperationBody::InvokeHostFunction(InvokeHostFunctionOp { auth, .. }) = &op.body else {
+            continue;
+        };
+        for entry in auth.iter() {
+            if let SorobanCredentials::Address(credentials) = &entry.credentials {
+                if let ScAddress::Contract(ContractId(Hash(c))) = credentials.address {
+                    let address = stellar_strkey::Contract(c);
+                    if !addresses.contains(&address) {
+                        addresses.push(address);
+                    }
+                }
+            }
+        }
+    }
+    addresses
+}
+
 fn build_invoke_contract_tx(
     parameters: InvokeContractArgs,
     sequence: i64,
     fee: u32,
     source_account_id: Uint256,
+    memo: Memo,
 ) -> Result<Transaction, Error> {
     let op = Operation {
         source_account: None,
@@ -425,13 +702,14 @@ fn build_invoke_contract_tx(
         fee,
         seq_num: SequenceNumber(sequence),
         cond: Preconditions::None,
-        memo: Memo::None,
+        memo,
         operations: vec![op].try_into()?,
         ext: TransactionExt::V0,
     })
 }
 
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum, Default)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum, Default, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Send {
     /// Send transaction if simulation indicates there are ledger writes,
     /// published events, or auth required, otherwise return simulation result
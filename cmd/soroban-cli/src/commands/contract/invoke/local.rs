@@ -0,0 +1,351 @@
+use std::{path::Path, rc::Rc};
+
+use soroban_env_host::{
+    budget::Budget,
+    e2e_invoke::{self, LedgerEntryChange, RecordingInvocationAuthMode},
+};
+use soroban_ledger_snapshot::LedgerSnapshot;
+
+use crate::{
+    get_spec::contract_spec,
+    print::Print,
+    xdr::{
+        self, AccountId, ContractDataDurability, ContractDataEntry, ContractExecutable, ContractId,
+        DiagnosticEvent, Hash, HostFunction, LedgerEntry, LedgerEntryData, LedgerKey,
+        LedgerKeyContractCode, LedgerKeyContractData, Limits, ReadXdr, ScAddress,
+        ScContractInstance, ScSpecEntry, ScVal, SorobanResources,
+    },
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("reading ledger snapshot {path}: {error}")]
+    ReadSnapshot {
+        path: String,
+        error: soroban_ledger_snapshot::Error,
+    },
+    #[error("writing ledger snapshot {path}: {error}")]
+    WriteSnapshot {
+        path: String,
+        error: soroban_ledger_snapshot::Error,
+    },
+    #[error("contract {0} is not in the ledger snapshot")]
+    MissingContract(stellar_strkey::Contract),
+    #[error("wasm {0} of the contract is not in the ledger snapshot")]
+    MissingWasm(Hash),
+    #[error(transparent)]
+    ContractSpec(#[from] contract_spec::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error("invoking the contract: {0}")]
+    Host(String),
+}
+
+/// A ledger snapshot, as written by `snapshot create` or `snapshot merge`, to invoke contracts
+/// against without a network.
+pub struct Ledger {
+    snapshot: LedgerSnapshot,
+}
+
+/// The outcome of a local invocation.
+pub struct Invocation {
+    /// Return value of the invoked function, or the error the invocation failed with
+    pub result: Result<ScVal, String>,
+    pub diagnostic_events: Vec<DiagnosticEvent>,
+    pub resources: SorobanResources,
+    pub cpu_instructions: u64,
+    pub memory_bytes: u64,
+    pub changes: Vec<Change>,
+}
+
+/// A change an invocation made to a ledger entry.
+pub enum Change {
+    Created(LedgerKey),
+    Updated(LedgerKey),
+    Deleted(LedgerKey),
+    Extended { key: LedgerKey, live_until: u32 },
+}
+
+impl Ledger {
+    pub fn read(path: &Path) -> Result<Self, Error> {
+        let snapshot = LedgerSnapshot::read_file(path).map_err(|error| Error::ReadSnapshot {
+            path: path.display().to_string(),
+            error,
+        })?;
+        Ok(Self { snapshot })
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), Error> {
+        self.snapshot
+            .write_file(path)
+            .map_err(|error| Error::WriteSnapshot {
+                path: path.display().to_string(),
+                error,
+            })
+    }
+
+    fn get(&self, key: &LedgerKey) -> Option<&(Box<LedgerEntry>, Option<u32>)> {
+        self.snapshot
+            .ledger_entries
+            .iter()
+            .find(|(k, _)| **k == *key)
+            .map(|(_, entry)| entry)
+    }
+
+    /// Spec of a contract, read from the wasm of its instance in the snapshot.
+    pub fn spec_entries(
+        &self,
+        contract_id: &stellar_strkey::Contract,
+    ) -> Result<Vec<ScSpecEntry>, Error> {
+        let key = LedgerKey::ContractData(LedgerKeyContractData {
+            contract: ScAddress::Contract(ContractId(Hash(contract_id.0))),
+            key: ScVal::LedgerKeyContractInstance,
+            durability: ContractDataDurability::Persistent,
+        });
+        let Some((entry, _)) = self.get(&key) else {
+            return Err(Error::MissingContract(*contract_id));
+        };
+        let LedgerEntryData::ContractData(ContractDataEntry {
+            val: ScVal::ContractInstance(ScContractInstance { executable, .. }),
+            ..
+        }) = &entry.data
+        else {
+            return Err(Error::MissingContract(*contract_id));
+        };
+        match executable {
+            ContractExecutable::Wasm(hash) => {
+                let key = LedgerKey::ContractCode(LedgerKeyContractCode { hash: hash.clone() });
+                match self.get(&key).map(|(entry, _)| &entry.data) {
+                    Some(LedgerEntryData::ContractCode(code)) => {
+                        Ok(contract_spec::Spec::new(&code.code)?.spec)
+                    }
+                    _ => Err(Error::MissingWasm(hash.clone())),
+                }
+            }
+            ContractExecutable::StellarAsset => {
+                Ok(soroban_spec::read::parse_raw(stellar_asset_spec::xdr())?)
+            }
+        }
+    }
+
+    /// Invoke a host function against the snapshot, as simulation does, recording its footprint
+    /// and authorizations instead of enforcing them, then apply its changes to the snapshot.
+    pub fn invoke(
+        &mut self,
+        host_function: &HostFunction,
+        source_account: &AccountId,
+    ) -> Result<Invocation, Error> {
+        let budget = Budget::default();
+        let mut diagnostic_events = Vec::new();
+        let snapshot = Rc::new(self.snapshot.clone());
+        let res = e2e_invoke::invoke_host_function_in_recording_mode(
+            &budget,
+            true,
+            host_function,
+            source_account,
+            RecordingInvocationAuthMode::Recording(true),
+            self.snapshot.ledger_info(),
+            snapshot,
+            [0; 32],
+            &mut diagnostic_events,
+        )
+        .map_err(|e| Error::Host(e.to_string()))?;
+        let result = res.invoke_result.map_err(|e| e.to_string());
+        let changes = if result.is_ok() {
+            self.apply(&res.ledger_changes)?
+        } else {
+            Vec::new()
+        };
+        Ok(Invocation {
+            result,
+            diagnostic_events,
+            resources: res.resources,
+            cpu_instructions: budget.get_cpu_insns_consumed().unwrap_or_default(),
+            memory_bytes: budget.get_mem_bytes_consumed().unwrap_or_default(),
+            changes,
+        })
+    }
+
+    /// Apply the changes of an invocation to the snapshot, returning the entries that changed.
+    fn apply(&mut self, ledger_changes: &[LedgerEntryChange]) -> Result<Vec<Change>, Error> {
+        let mut changes = Vec::new();
+        let mut updates = Vec::new();
+        for change in ledger_changes {
+            let key = LedgerKey::from_xdr(&change.encoded_key, Limits::none())?;
+            let old = self.get(&key).cloned();
+            let new_live_until = change
+                .ttl_change
+                .as_ref()
+                .map(|ttl| ttl.new_live_until_ledger);
+            let new = if change.read_only {
+                old.clone()
+                    .map(|(entry, live_until)| (entry, new_live_until.or(live_until)))
+            } else {
+                change
+                    .encoded_new_value
+                    .as_ref()
+                    .map(|value| LedgerEntry::from_xdr(value, Limits::none()))
+                    .transpose()?
+                    .map(|entry| {
+                        let live_until = old.as_ref().and_then(|(_, live_until)| *live_until);
+                        (Box::new(entry), new_live_until.or(live_until))
+                    })
+            };
+            let change = match (&old, &new) {
+                (None, None) => continue,
+                (None, Some(_)) => Change::Created(key.clone()),
+                (Some(_), None) => Change::Deleted(key.clone()),
+                (Some((old_entry, old_live_until)), Some((new_entry, new_live_until))) => {
+                    if old_entry.data != new_entry.data {
+                        Change::Updated(key.clone())
+                    } else if let (Some(live_until), true) =
+                        (new_live_until, new_live_until != old_live_until)
+                    {
+                        Change::Extended {
+                            key: key.clone(),
+                            live_until: *live_until,
+                        }
+                    } else {
+                        continue;
+                    }
+                }
+            };
+            changes.push(change);
+            updates.push((
+                Rc::new(key),
+                new.map(|(entry, live_until)| (Rc::new(*entry), live_until)),
+            ));
+        }
+        self.snapshot.update_entries(&updates);
+        Ok(changes)
+    }
+}
+
+impl Invocation {
+    /// Print the footprint, resources and ledger changes of the invocation.
+    pub fn print(&self, print: &Print) {
+        let footprint = &self.resources.footprint;
+        print.infoln(format!(
+            "Footprint: {} read-only, {} read-write entries",
+            footprint.read_only.len(),
+            footprint.read_write.len()
+        ));
+        for key in footprint.read_only.iter() {
+            print.println(format!("  read-only  {}", compact(key)));
+        }
+        for key in footprint.read_write.iter() {
+            print.println(format!("  read-write {}", compact(key)));
+        }
+        print.infoln(format!(
+            "Resources: {} CPU instructions, {} bytes of memory, {} bytes read, {} bytes written",
+            self.cpu_instructions,
+            self.memory_bytes,
+            self.resources.disk_read_bytes,
+            self.resources.write_bytes
+        ));
+        if self.changes.is_empty() {
+            print.infoln("Ledger changes: none");
+            return;
+        }
+        print.infoln(format!("Ledger changes: {}", self.changes.len()));
+        for change in &self.changes {
+            let line = match change {
+                Change::Created(key) => format!("  created  {}", compact(key)),
+                Change::Updated(key) => format!("  updated  {}", compact(key)),
+                Change::Deleted(key) => format!("  deleted  {}", compact(key)),
+                Change::Extended { key, live_until } => {
+                    format!("  extended {} to ledger {live_until}", compact(key))
+                }
+            };
+            print.println(line);
+        }
+    }
+}
+
+fn compact(key: &LedgerKey) -> String {
+    serde_json::to_string(key).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdr::{ContractDataEntry, ExtensionPoint, LedgerEntryExt, WriteXdr};
+    use soroban_env_host::e2e_invoke::LedgerEntryLiveUntilChange;
+
+    fn key(name: &str) -> LedgerKey {
+        LedgerKey::ContractData(LedgerKeyContractData {
+            contract: ScAddress::Contract(ContractId(Hash([1; 32]))),
+            key: ScVal::Symbol(name.try_into().unwrap()),
+            durability: ContractDataDurability::Persistent,
+        })
+    }
+
+    fn entry(name: &str, val: u32) -> LedgerEntry {
+        LedgerEntry {
+            last_modified_ledger_seq: 0,
+            data: LedgerEntryData::ContractData(ContractDataEntry {
+                ext: ExtensionPoint::V0,
+                contract: ScAddress::Contract(ContractId(Hash([1; 32]))),
+                key: ScVal::Symbol(name.try_into().unwrap()),
+                durability: ContractDataDurability::Persistent,
+                val: ScVal::U32(val),
+            }),
+            ext: LedgerEntryExt::V0,
+        }
+    }
+
+    fn change(name: &str, read_only: bool, new: Option<u32>, live_until: u32) -> LedgerEntryChange {
+        LedgerEntryChange {
+            read_only,
+            encoded_key: key(name).to_xdr(Limits::none()).unwrap(),
+            encoded_new_value: new.map(|val| entry(name, val).to_xdr(Limits::none()).unwrap()),
+            ttl_change: Some(LedgerEntryLiveUntilChange {
+                key_hash: vec![],
+                durability: ContractDataDurability::Persistent,
+                entry_type: xdr::LedgerEntryType::ContractData,
+                old_live_until_ledger: 10,
+                new_live_until_ledger: live_until,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn apply_changes_to_snapshot() {
+        let mut ledger = Ledger {
+            snapshot: LedgerSnapshot::default(),
+        };
+        for name in ["same", "counter", "extended", "gone"] {
+            ledger
+                .snapshot
+                .ledger_entries
+                .push((Box::new(key(name)), (Box::new(entry(name, 1)), Some(10))));
+        }
+        let changes = ledger
+            .apply(&[
+                change("same", false, Some(1), 10),
+                change("counter", false, Some(2), 10),
+                change("extended", true, None, 20),
+                change("gone", false, None, 10),
+                change("new", false, Some(1), 30),
+            ])
+            .unwrap();
+        let changes = changes
+            .iter()
+            .map(|change| match change {
+                Change::Created(_) => "created".to_string(),
+                Change::Updated(_) => "updated".to_string(),
+                Change::Deleted(_) => "deleted".to_string(),
+                Change::Extended { live_until, .. } => format!("extended to {live_until}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(changes, ["updated", "extended to 20", "deleted", "created"]);
+        assert_eq!(
+            ledger.get(&key("counter")).unwrap().0.data,
+            entry("counter", 2).data
+        );
+        assert_eq!(ledger.get(&key("extended")).unwrap().1, Some(20));
+        assert!(ledger.get(&key("gone")).is_none());
+        assert_eq!(ledger.get(&key("new")).unwrap().1, Some(30));
+    }
+}
@@ -1,8 +1,10 @@
 pub mod alias;
 pub mod arg_parsing;
 pub mod asset;
+pub mod batch;
 pub mod bindings;
 pub mod build;
+pub mod calls;
 pub mod deploy;
 pub mod extend;
 pub mod fetch;
@@ -14,6 +16,8 @@ pub mod invoke;
 pub mod optimize;
 pub mod read;
 pub mod restore;
+pub mod spec_diff;
+pub mod upgrade;
 pub mod upload;
 
 use crate::{commands::global, print::Print, utils::deprecate_message};
@@ -28,6 +32,10 @@ pub enum Cmd {
     #[command(subcommand)]
     Alias(alias::Cmd),
 
+    /// Invoke contract functions in order, as listed in a batch file
+    #[command(long_about = batch::LONG_ABOUT)]
+    Batch(batch::Cmd),
+
     /// Generate code client bindings for a contract
     #[command(subcommand)]
     Bindings(bindings::Cmd),
@@ -39,7 +47,10 @@ pub enum Cmd {
     /// If no keys are specified the contract itself is extended.
     Extend(extend::Cmd),
 
-    /// Deploy a wasm contract
+    #[command(
+        about = deploy::ABOUT,
+        long_about = deploy::ABOUT.to_string() + deploy::manifest::LONG_ABOUT,
+    )]
     Deploy(deploy::wasm::Cmd),
 
     /// Fetch a contract's Wasm binary
@@ -65,6 +76,9 @@ pub enum Cmd {
     #[command(display_order = 100)]
     Inspect(inspect::Cmd),
 
+    /// Upgrade a contract to a new WASM, checking the changes to its interface first
+    Upgrade(upgrade::Cmd),
+
     /// Install a WASM file to the ledger without creating a contract instance
     Upload(upload::Cmd),
 
@@ -101,6 +115,9 @@ pub enum Error {
     #[error(transparent)]
     Alias(#[from] alias::Error),
 
+    #[error(transparent)]
+    Batch(#[from] batch::Error),
+
     #[error(transparent)]
     Bindings(#[from] bindings::Error),
 
@@ -134,6 +151,9 @@ pub enum Error {
     #[error(transparent)]
     Invoke(#[from] invoke::Error),
 
+    #[error(transparent)]
+    Upgrade(#[from] upgrade::Error),
+
     #[error(transparent)]
     Optimize(#[from] optimize::Error),
 
@@ -154,6 +174,7 @@ impl Cmd {
             Cmd::Build(build) => build.run(global_args)?,
             Cmd::Extend(extend) => extend.run(global_args).await?,
             Cmd::Alias(alias) => alias.run(global_args)?,
+            Cmd::Batch(batch) => batch.run(global_args).await?,
             Cmd::Deploy(deploy) => deploy.run(global_args).await?,
             Cmd::Id(id) => id.run().await?,
             Cmd::Info(info) => info.run(global_args).await?,
@@ -174,6 +195,7 @@ impl Cmd {
                 );
                 install.run(global_args).await?;
             }
+            Cmd::Upgrade(upgrade) => upgrade.run(global_args).await?,
             Cmd::Upload(upload) => upload.run(global_args).await?,
             Cmd::Invoke(invoke) => invoke.run(global_args).await?,
             Cmd::Optimize(optimize) => {
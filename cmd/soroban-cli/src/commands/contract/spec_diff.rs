@@ -0,0 +1,401 @@
+use std::fmt::Display;
+
+use serde::Serialize;
+
+use crate::xdr::{
+    ScSpecEntry, ScSpecFunctionInputV0, ScSpecTypeDef, ScSpecUdtUnionCaseV0, StringM,
+};
+
+/// A difference between two versions of a contract interface.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub kind: Kind,
+    pub entry: Entry,
+    /// Name of the function, type or event
+    pub name: String,
+    /// What changed inside the entry, e.g. the type of an input
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Whether callers or decoders built for the old interface can break
+    pub breaking: bool,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Kind {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Entry {
+    Function,
+    Struct,
+    Union,
+    Enum,
+    Error,
+    Event,
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Kind::Added => "added",
+            Kind::Removed => "removed",
+            Kind::Changed => "changed",
+        })
+    }
+}
+
+impl Display for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Entry::Function => "function",
+            Entry::Struct => "struct",
+            Entry::Union => "union",
+            Entry::Enum => "enum",
+            Entry::Error => "error enum",
+            Entry::Event => "event",
+        })
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} `{}`", self.kind, self.entry, self.name)?;
+        if let Some(detail) = &self.detail {
+            write!(f, ": {detail}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The changes from one contract interface to another, in the order of the entries of the old
+/// interface, then of the added ones. Documentation changes are ignored.
+pub fn diff(old: &[ScSpecEntry], new: &[ScSpecEntry]) -> Vec<Change> {
+    let mut changes = Vec::new();
+    for old_entry in old {
+        let (entry, name) = describe(old_entry);
+        match new.iter().find(|e| describe(e) == (entry, name.clone())) {
+            Some(new_entry) => {
+                for (detail, breaking) in entry_changes(old_entry, new_entry) {
+                    changes.push(Change {
+                        kind: Kind::Changed,
+                        entry,
+                        name: name.clone(),
+                        detail: Some(detail),
+                        breaking,
+                    });
+                }
+            }
+            None => changes.push(Change {
+                kind: Kind::Removed,
+                entry,
+                name,
+                detail: None,
+                breaking: true,
+            }),
+        }
+    }
+    for new_entry in new {
+        let (entry, name) = describe(new_entry);
+        if !old.iter().any(|e| describe(e) == (entry, name.clone())) {
+            changes.push(Change {
+                kind: Kind::Added,
+                entry,
+                name,
+                detail: None,
+                breaking: false,
+            });
+        }
+    }
+    changes
+}
+
+/// Whether any of the changes can break callers of the old interface.
+pub fn is_breaking(changes: &[Change]) -> bool {
+    changes.iter().any(|change| change.breaking)
+}
+
+fn describe(entry: &ScSpecEntry) -> (Entry, String) {
+    match entry {
+        ScSpecEntry::FunctionV0(f) => (Entry::Function, f.name.to_utf8_string_lossy()),
+        ScSpecEntry::UdtStructV0(s) => (Entry::Struct, udt_name(&s.lib, &s.name)),
+        ScSpecEntry::UdtUnionV0(u) => (Entry::Union, udt_name(&u.lib, &u.name)),
+        ScSpecEntry::UdtEnumV0(e) => (Entry::Enum, udt_name(&e.lib, &e.name)),
+        ScSpecEntry::UdtErrorEnumV0(e) => (Entry::Error, udt_name(&e.lib, &e.name)),
+        ScSpecEntry::EventV0(e) => (Entry::Event, udt_name(&e.lib, &e.name.0)),
+    }
+}
+
+fn udt_name<const L: u32, const N: u32>(lib: &StringM<L>, name: &StringM<N>) -> String {
+    if lib.is_empty() {
+        name.to_utf8_string_lossy()
+    } else {
+        format!(
+            "{}::{}",
+            lib.to_utf8_string_lossy(),
+            name.to_utf8_string_lossy()
+        )
+    }
+}
+
+/// The changes inside an entry present in both interfaces, each with whether it is breaking.
+fn entry_changes(old: &ScSpecEntry, new: &ScSpecEntry) -> Vec<(String, bool)> {
+    match (old, new) {
+        (ScSpecEntry::FunctionV0(old), ScSpecEntry::FunctionV0(new)) => {
+            let (old_inputs, new_inputs) = (inputs(&old.inputs), inputs(&new.inputs));
+            let mut changes = members("input", &old_inputs, &new_inputs, true);
+            // Arguments are passed by position, so the same inputs in another order break callers
+            if changes.is_empty() && old_inputs != new_inputs {
+                changes.push(("inputs reordered".to_string(), true));
+            }
+            let old_output = outputs(&old.outputs);
+            let new_output = outputs(&new.outputs);
+            if old_output != new_output {
+                changes.push((format!("output {old_output} → {new_output}"), true));
+            }
+            changes
+        }
+        (ScSpecEntry::UdtStructV0(old), ScSpecEntry::UdtStructV0(new)) => {
+            let fields = |s: &crate::xdr::ScSpecUdtStructV0| {
+                s.fields
+                    .iter()
+                    .map(|f| (f.name.to_utf8_string_lossy(), type_name(&f.type_)))
+                    .collect::<Vec<_>>()
+            };
+            // Structs convert from maps with exactly their fields, so any change is breaking
+            members("field", &fields(old), &fields(new), true)
+        }
+        (ScSpecEntry::UdtUnionV0(old), ScSpecEntry::UdtUnionV0(new)) => {
+            let cases = |u: &crate::xdr::ScSpecUdtUnionV0| {
+                u.cases
+                    .iter()
+                    .map(|c| match c {
+                        ScSpecUdtUnionCaseV0::VoidV0(c) => {
+                            (c.name.to_utf8_string_lossy(), String::new())
+                        }
+                        ScSpecUdtUnionCaseV0::TupleV0(c) => (
+                            c.name.to_utf8_string_lossy(),
+                            format!(
+                                "({})",
+                                c.type_.iter().map(type_name).collect::<Vec<_>>().join(", ")
+                            ),
+                        ),
+                    })
+                    .collect::<Vec<_>>()
+            };
+            members("case", &cases(old), &cases(new), false)
+        }
+        (ScSpecEntry::UdtEnumV0(old), ScSpecEntry::UdtEnumV0(new)) => {
+            let cases = |cases: &[crate::xdr::ScSpecUdtEnumCaseV0]| {
+                cases
+                    .iter()
+                    .map(|c| (c.name.to_utf8_string_lossy(), c.value.to_string()))
+                    .collect::<Vec<_>>()
+            };
+            members("case", &cases(&old.cases), &cases(&new.cases), false)
+        }
+        (ScSpecEntry::UdtErrorEnumV0(old), ScSpecEntry::UdtErrorEnumV0(new)) => {
+            let cases = |cases: &[crate::xdr::ScSpecUdtErrorEnumCaseV0]| {
+                cases
+                    .iter()
+                    .map(|c| (c.name.to_utf8_string_lossy(), c.value.to_string()))
+                    .collect::<Vec<_>>()
+            };
+            members("error", &cases(&old.cases), &cases(&new.cases), false)
+        }
+        (ScSpecEntry::EventV0(old), ScSpecEntry::EventV0(new)) => {
+            let signature = |e: &crate::xdr::ScSpecEventV0| {
+                (
+                    e.prefix_topics
+                        .iter()
+                        .map(|topic| topic.0.to_utf8_string_lossy())
+                        .collect::<Vec<_>>(),
+                    e.params
+                        .iter()
+                        .map(|p| {
+                            (
+                                p.name.to_utf8_string_lossy(),
+                                type_name(&p.type_),
+                                p.location,
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                    e.data_format,
+                )
+            };
+            if signature(old) == signature(new) {
+                vec![]
+            } else {
+                vec![("topics or data changed".to_string(), true)]
+            }
+        }
+        _ => vec![],
+    }
+}
+
+fn inputs(inputs: &[ScSpecFunctionInputV0]) -> Vec<(String, String)> {
+    inputs
+        .iter()
+        .map(|i| (i.name.to_utf8_string_lossy(), type_name(&i.type_)))
+        .collect()
+}
+
+fn outputs(outputs: &[ScSpecTypeDef]) -> String {
+    match outputs {
+        [] => "()".to_string(),
+        [output] => type_name(output),
+        outputs => format!(
+            "({})",
+            outputs.iter().map(type_name).collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+/// Changes to named members of an entry, e.g. the fields of a struct, with their types or values.
+/// Removing or changing a member is breaking, adding one only if `added_breaks`.
+fn members(
+    member: &str,
+    old: &[(String, String)],
+    new: &[(String, String)],
+    added_breaks: bool,
+) -> Vec<(String, bool)> {
+    let mut changes = Vec::new();
+    for (name, old_value) in old {
+        match new.iter().find(|(n, _)| n == name) {
+            Some((_, new_value)) if new_value != old_value => {
+                changes.push((format!("{member} `{name}` {old_value} → {new_value}"), true));
+            }
+            Some(_) => {}
+            None => changes.push((format!("removed {member} `{name}`"), true)),
+        }
+    }
+    for (name, value) in new {
+        if !old.iter().any(|(n, _)| n == name) {
+            let value = if value.is_empty() {
+                String::new()
+            } else {
+                format!(" {value}")
+            };
+            changes.push((format!("added {member} `{name}`{value}"), added_breaks));
+        }
+    }
+    changes
+}
+
+/// Rust-like name of a spec type, e.g. `Vec<Address>`.
+pub fn type_name(type_: &ScSpecTypeDef) -> String {
+    match type_ {
+        ScSpecTypeDef::Option(t) => format!("Option<{}>", type_name(&t.value_type)),
+        ScSpecTypeDef::Result(t) => format!(
+            "Result<{}, {}>",
+            type_name(&t.ok_type),
+            type_name(&t.error_type)
+        ),
+        ScSpecTypeDef::Vec(t) => format!("Vec<{}>", type_name(&t.element_type)),
+        ScSpecTypeDef::Map(t) => format!(
+            "Map<{}, {}>",
+            type_name(&t.key_type),
+            type_name(&t.value_type)
+        ),
+        ScSpecTypeDef::Tuple(t) => format!(
+            "({})",
+            t.value_types
+                .iter()
+                .map(type_name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ScSpecTypeDef::BytesN(b) => format!("BytesN<{}>", b.n),
+        ScSpecTypeDef::Udt(u) => u.name.to_utf8_string_lossy(),
+        ScSpecTypeDef::Val => "Val".to_string(),
+        _ => type_.name().to_lowercase(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdr::{ScSpecFunctionV0, ScSpecUdtErrorEnumCaseV0, ScSpecUdtErrorEnumV0};
+
+    fn function(
+        name: &str,
+        inputs: &[(&str, ScSpecTypeDef)],
+        output: ScSpecTypeDef,
+    ) -> ScSpecEntry {
+        ScSpecEntry::FunctionV0(ScSpecFunctionV0 {
+            doc: StringM::default(),
+            name: name.try_into().unwrap(),
+            inputs: inputs
+                .iter()
+                .map(|(name, type_)| ScSpecFunctionInputV0 {
+                    doc: StringM::default(),
+                    name: (*name).try_into().unwrap(),
+                    type_: type_.clone(),
+                })
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+            outputs: vec![output].try_into().unwrap(),
+        })
+    }
+
+    fn errors(cases: &[(&str, u32)]) -> ScSpecEntry {
+        ScSpecEntry::UdtErrorEnumV0(ScSpecUdtErrorEnumV0 {
+            doc: StringM::default(),
+            lib: StringM::default(),
+            name: "Error".try_into().unwrap(),
+            cases: cases
+                .iter()
+                .map(|(name, value)| ScSpecUdtErrorEnumCaseV0 {
+                    doc: StringM::default(),
+                    name: (*name).try_into().unwrap(),
+                    value: *value,
+                })
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+        })
+    }
+
+    #[test]
+    fn diff_interfaces() {
+        let old = [
+            function(
+                "hello",
+                &[("to", ScSpecTypeDef::Symbol)],
+                ScSpecTypeDef::Symbol,
+            ),
+            function("bye", &[], ScSpecTypeDef::Void),
+            errors(&[("NotFound", 1), ("Denied", 2)]),
+        ];
+        let new = [
+            function(
+                "hello",
+                &[("to", ScSpecTypeDef::String)],
+                ScSpecTypeDef::Symbol,
+            ),
+            errors(&[("NotFound", 1), ("Denied", 3), ("Expired", 4)]),
+            function("version", &[], ScSpecTypeDef::U32),
+        ];
+        let changes = diff(&old, &new);
+        assert_eq!(
+            changes.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            [
+                "changed function `hello`: input `to` symbol → string",
+                "removed function `bye`",
+                "changed error enum `Error`: error `Denied` 2 → 3",
+                "changed error enum `Error`: added error `Expired` 4",
+                "added function `version`",
+            ]
+        );
+        assert_eq!(
+            changes.iter().map(|c| c.breaking).collect::<Vec<_>>(),
+            [true, true, true, false, false]
+        );
+        assert_eq!(diff(&old, &old), Vec::<Change>::new());
+    }
+}
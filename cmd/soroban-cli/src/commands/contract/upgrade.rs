@@ -0,0 +1,201 @@
+use std::{ffi::OsString, path::PathBuf};
+
+use clap::Parser;
+
+use super::{invoke, spec_diff, upload};
+use crate::{
+    commands::{global, NetworkRunnable},
+    config::{self, locator, network},
+    get_spec::{self, contract_spec, get_remote_contract_spec},
+    print::Print,
+    rpc, utils,
+    utils::rpc::get_remote_wasm_from_hash,
+    wasm,
+    xdr::{self, Hash, ScSpecEntry, ScSpecTypeBytesN, ScSpecTypeDef},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    GetSpec(#[from] get_spec::Error),
+    #[error(transparent)]
+    ContractSpec(#[from] contract_spec::Error),
+    #[error(transparent)]
+    Wasm(#[from] wasm::Error),
+    #[error(transparent)]
+    Upload(#[from] upload::Error),
+    #[error(transparent)]
+    Invoke(#[from] invoke::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error("cannot parse WASM hash {wasm_hash}: {error}")]
+    CannotParseWasmHash {
+        wasm_hash: String,
+        error: stellar_strkey::DecodeError,
+    },
+    #[error("contract {contract_id} has no `{function}` function to upgrade it with, pass its name with `--upgrade-fn`")]
+    MissingUpgradeFunction {
+        contract_id: stellar_strkey::Contract,
+        function: String,
+    },
+    #[error("function `{0}` does not take the hash of the new wasm, a `BytesN<32>` argument")]
+    NotAnUpgradeFunction(String),
+}
+
+/// Upgrade a deployed contract to a new wasm, warning about breaking changes to its interface.
+/// Uploads the wasm if needed, then invokes the contract's upgrade function with its hash
+/// e.g. `stellar contract upgrade --id C... --wasm new.wasm --source admin`
+#[derive(Parser, Debug, Clone)]
+#[command(group(
+    clap::ArgGroup::new("wasm_src")
+        .required(true)
+        .args(&["wasm", "wasm_hash"]),
+))]
+#[group(skip)]
+pub struct Cmd {
+    /// ID of the contract to upgrade
+    #[arg(long = "id", env = "STELLAR_CONTRACT_ID")]
+    pub contract_id: config::UnresolvedContract,
+    /// New wasm of the contract, uploaded if it isn't already
+    #[arg(long, group = "wasm_src")]
+    pub wasm: Option<PathBuf>,
+    /// Hash of the new wasm, already uploaded
+    #[arg(long, conflicts_with = "wasm", group = "wasm_src")]
+    pub wasm_hash: Option<String>,
+    /// Function of the contract that upgrades it, taking the hash of the new wasm
+    #[arg(long, default_value = "upgrade")]
+    pub upgrade_fn: String,
+    #[arg(long, short = 'i', default_value = "false")]
+    /// Whether to ignore safety checks when uploading the wasm
+    pub ignore_checks: bool,
+    #[command(flatten)]
+    pub config: config::Args,
+    #[command(flatten)]
+    pub resources: crate::resources::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let config = &self.config;
+        let network = config.get_network()?;
+        let contract_id = self
+            .contract_id
+            .resolve_contract_id(&config.locator, &network.network_passphrase)?;
+        let client = network.rpc_client()?;
+
+        let old = get_remote_contract_spec(
+            &contract_id.0,
+            &config.locator,
+            &config.network,
+            Some(global_args),
+            Some(config),
+        )
+        .await?;
+        let input = upgrade_input(&old, &self.upgrade_fn).ok_or_else(|| {
+            Error::MissingUpgradeFunction {
+                contract_id,
+                function: self.upgrade_fn.clone(),
+            }
+        })??;
+
+        let (wasm_hash, new) = if let Some(wasm) = &self.wasm {
+            let wasm = wasm::Args { wasm: wasm.clone() };
+            (wasm.hash()?, wasm.parse()?.spec)
+        } else {
+            // clap requires `--wasm-hash` without `--wasm`
+            let wasm_hash = self.wasm_hash.clone().unwrap_or_default();
+            let hash = Hash(
+                utils::contract_id_from_str(&wasm_hash)
+                    .map_err(|error| Error::CannotParseWasmHash { wasm_hash, error })?
+                    .0,
+            );
+            let wasm = get_remote_wasm_from_hash(&client, &hash).await?;
+            (hash, contract_spec::Spec::new(&wasm)?.spec)
+        };
+
+        let changes = spec_diff::diff(&old, &new);
+        if changes.is_empty() {
+            print.infoln("The interface of the new wasm is the same");
+        }
+        for change in &changes {
+            if change.breaking {
+                print.warnln(format!("Breaking: {change}"));
+            } else {
+                print.infoln(change);
+            }
+        }
+        if upgrade_input(&new, &self.upgrade_fn).is_none() {
+            print.warnln(format!(
+                "The new wasm has no `{}` function, the contract can't be upgraded with it again",
+                self.upgrade_fn
+            ));
+        }
+        if spec_diff::is_breaking(&changes) {
+            print.warnln("The new interface can break callers of the contract");
+        }
+
+        if let Some(wasm) = &self.wasm {
+            upload::Cmd {
+                wasm: wasm::Args { wasm: wasm.clone() },
+                config: config.clone(),
+                resources: self.resources.clone(),
+                ignore_checks: self.ignore_checks,
+                build_only: false,
+            }
+            .run_against_rpc_server(Some(global_args), Some(config))
+            .await?;
+        }
+
+        print.infoln(format!(
+            "Upgrading contract {contract_id} to wasm {wasm_hash} with `{}`",
+            self.upgrade_fn
+        ));
+        let invoke = invoke::Cmd {
+            contract_id: self.contract_id.clone(),
+            slop: vec![
+                OsString::from(&self.upgrade_fn),
+                OsString::from(format!("--{input}")),
+                OsString::from(wasm_hash.to_string()),
+            ],
+            config: config.clone(),
+            resources: self.resources.clone(),
+            send: invoke::Send::Yes,
+            ..Default::default()
+        };
+        invoke.invoke(global_args).await?;
+        print.checkln(format!(
+            "Upgraded contract {contract_id} to wasm {wasm_hash}"
+        ));
+        Ok(())
+    }
+}
+
+/// Name of the argument of the upgrade function that takes the hash of the new wasm, or `None`
+/// if the interface has no such function.
+fn upgrade_input(spec: &[ScSpecEntry], function: &str) -> Option<Result<String, Error>> {
+    let f = spec.iter().find_map(|entry| match entry {
+        ScSpecEntry::FunctionV0(f) if f.name.to_utf8_string_lossy() == function => Some(f),
+        _ => None,
+    })?;
+    Some(
+        f.inputs
+            .iter()
+            .find(|input| {
+                matches!(
+                    input.type_,
+                    ScSpecTypeDef::BytesN(ScSpecTypeBytesN { n: 32 })
+                )
+            })
+            .map(|input| input.name.to_utf8_string_lossy())
+            .ok_or_else(|| Error::NotAnUpgradeFunction(function.to_string())),
+    )
+}
@@ -7,7 +7,7 @@ use crate::{
     config::{
         address::KeyName,
         key, locator,
-        secret::{self, Secret},
+        secret::{self, encrypted, Secret},
     },
     print::Print,
     signer::secure_store,
@@ -48,7 +48,12 @@ pub struct Cmd {
     pub config_locator: locator::Args,
 
     /// Add a public key, ed25519, or muxed account, e.g. G1.., M2..
-    #[arg(long, conflicts_with = "seed_phrase", conflicts_with = "secret_key")]
+    #[arg(
+        long,
+        conflicts_with = "seed_phrase",
+        conflicts_with = "secret_key",
+        conflicts_with = "encrypt"
+    )]
     pub public_key: Option<String>,
 
     /// Overwrite existing identity if it already exists.
@@ -71,7 +76,14 @@ impl Cmd {
         let key = if let Some(key) = self.public_key.as_ref() {
             key.parse()?
         } else {
-            self.read_secret(&print)?.into()
+            let secret = self.read_secret(&print)?;
+            if self.secrets.encrypt {
+                let passphrase = encrypted::read_passphrase("New passphrase", true)
+                    .map_err(secret::Error::from)?;
+                secret.encrypt(&passphrase)?.into()
+            } else {
+                secret.into()
+            }
         };
 
         let path = self.config_locator.write_key(&self.name, &key)?;
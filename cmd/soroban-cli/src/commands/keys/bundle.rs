@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{
+    key::Key,
+    network::Network,
+    secret::encrypted::{self, Sealed},
+};
+
+/// Environment variable read for the bundle passphrase before prompting for it.
+pub const PASSPHRASE_ENV: &str = "STELLAR_BUNDLE_PASSPHRASE";
+
+const VERSION: u32 = 1;
+const AAD: &[u8] = b"stellar keys bundle v1";
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(
+        "unsupported bundle version {0}, it was probably exported by a newer version of the CLI"
+    )]
+    UnsupportedVersion(u32),
+    #[error("wrong passphrase, or the bundle is corrupted")]
+    Decryption,
+    #[error(transparent)]
+    Encrypted(#[from] encrypted::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+/// What a bundle holds once decrypted. Seed phrases are exported whole, so every hd path can
+/// still be derived from them after import.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Contents {
+    pub identities: BTreeMap<String, Key>,
+    #[serde(default)]
+    pub networks: BTreeMap<String, Network>,
+    /// Contract ids by network passphrase, for each alias
+    #[serde(default)]
+    pub contract_aliases: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+/// The file written by `keys export`, with the contents sealed by a passphrase.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Bundle {
+    pub version: u32,
+    pub sealed: Sealed,
+}
+
+impl Bundle {
+    pub fn seal(contents: &Contents, passphrase: &str) -> Result<Self, Error> {
+        Self::seal_with_cost(contents, passphrase, encrypted::Encrypted::LOG_N)
+    }
+
+    pub fn seal_with_cost(contents: &Contents, passphrase: &str, log_n: u8) -> Result<Self, Error> {
+        let plaintext = zeroize::Zeroizing::new(serde_json::to_vec(contents)?);
+        Ok(Bundle {
+            version: VERSION,
+            sealed: Sealed::seal(&plaintext, AAD, passphrase, log_n)?,
+        })
+    }
+
+    pub fn open(&self, passphrase: &str) -> Result<Contents, Error> {
+        if self.version != VERSION {
+            return Err(Error::UnsupportedVersion(self.version));
+        }
+        let plaintext = self.sealed.open(AAD, passphrase).map_err(|e| match e {
+            encrypted::Error::Decryption => Error::Decryption,
+            e => Error::Encrypted(e),
+        })?;
+        Ok(serde_json::from_slice(&plaintext)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::secret::Secret;
+
+    #[test]
+    fn round_trip() {
+        let mut contents = Contents::default();
+        contents.identities.insert(
+            "alice".to_string(),
+            Key::Secret(Secret::SeedPhrase {
+                seed_phrase:
+                    "depth decade power loud smile spatial sign movie judge february rate broccoli"
+                        .to_string(),
+            }),
+        );
+        contents.identities.insert(
+            "bob".to_string(),
+            "GAREAZZQWHOCBJS236KIE3AWYBVFLSBK7E5UW3ICI3TCRWQKT5LNLCEZ"
+                .parse()
+                .unwrap(),
+        );
+        contents.contract_aliases.insert(
+            "token".to_string(),
+            BTreeMap::from([(
+                "Test SDF Network ; September 2015".to_string(),
+                "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE".to_string(),
+            )]),
+        );
+
+        let bundle = Bundle::seal_with_cost(&contents, "passphrase", 4).unwrap();
+        let json = serde_json::to_string(&bundle).unwrap();
+        assert!(!json.contains("alice"));
+
+        let opened: Bundle = serde_json::from_str(&json).unwrap();
+        let opened = opened.open("passphrase").unwrap();
+        assert_eq!(opened.identities, contents.identities);
+        assert_eq!(opened.contract_aliases, contents.contract_aliases);
+        assert!(Bundle::seal_with_cost(&contents, "passphrase", 4)
+            .unwrap()
+            .open("wrong")
+            .is_err_and(|e| matches!(e, Error::Decryption)));
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{
+    commands::global,
+    config::{
+        key::Key,
+        locator,
+        secret::{self, encrypted, Secret},
+    },
+    print::Print,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Secret(#[from] secret::Error),
+    #[error(transparent)]
+    Encrypted(#[from] encrypted::Error),
+    #[error("identity '{0}' has no secret key or seed phrase stored in the config directory")]
+    NotEncryptable(String),
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Identities to encrypt, all identities stored in plaintext if none are given
+    pub names: Vec<String>,
+
+    #[command(flatten)]
+    pub config_locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let names = if self.names.is_empty() {
+            self.plaintext_identities()?
+        } else {
+            self.names.clone()
+        };
+        if names.is_empty() {
+            print.infoln("No plaintext identities to encrypt");
+            return Ok(());
+        }
+
+        let mut secrets = Vec::new();
+        for name in names {
+            match self.config_locator.read_identity(&name)? {
+                Key::Secret(Secret::Encrypted { .. }) => {
+                    print.infoln(format!("Identity '{name}' is already encrypted"));
+                }
+                Key::Secret(secret @ (Secret::SecretKey { .. } | Secret::SeedPhrase { .. })) => {
+                    secrets.push((name, secret));
+                }
+                _ => return Err(Error::NotEncryptable(name)),
+            }
+        }
+        if secrets.is_empty() {
+            return Ok(());
+        }
+
+        let passphrase = encrypted::read_passphrase("New passphrase", true)?;
+        for (name, secret) in secrets {
+            let path = self
+                .config_locator
+                .write_identity(&name, &secret.encrypt(&passphrase)?)?;
+            print.checkln(format!("Identity '{name}' encrypted in {path:?}"));
+        }
+        Ok(())
+    }
+
+    fn plaintext_identities(&self) -> Result<Vec<String>, Error> {
+        Ok(self
+            .config_locator
+            .list_identities()?
+            .into_iter()
+            .filter(|name| {
+                matches!(
+                    self.config_locator.read_identity(name),
+                    Ok(Key::Secret(
+                        Secret::SecretKey { .. } | Secret::SeedPhrase { .. }
+                    ))
+                )
+            })
+            .collect())
+    }
+}
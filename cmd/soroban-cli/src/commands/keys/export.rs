@@ -0,0 +1,110 @@
+use std::path::PathBuf;
+
+use crate::{
+    commands::global,
+    config::{
+        key::Key,
+        locator,
+        secret::{encrypted, Secret},
+    },
+    print::Print,
+};
+
+use super::bundle::{self, Bundle, Contents};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Bundle(#[from] bundle::Error),
+    #[error(transparent)]
+    Encrypted(#[from] encrypted::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("failed to write bundle to {path:?}: {error}")]
+    Write {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+}
+
+/// e.g. `stellar keys export alice bob --networks --output keys.json`
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Identities to export, all identities if none are given
+    pub names: Vec<String>,
+
+    /// File to write the bundle to, stdout if not given
+    #[arg(long, short = 'o')]
+    pub output: Option<PathBuf>,
+
+    /// Include the networks saved in the config directory
+    #[arg(long)]
+    pub networks: bool,
+
+    /// Include the contract aliases saved in the config directory
+    #[arg(long)]
+    pub contract_aliases: bool,
+
+    #[command(flatten)]
+    pub config_locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let contents = self.contents(&print)?;
+        let passphrase =
+            encrypted::read_passphrase_from_env(bundle::PASSPHRASE_ENV, "Bundle passphrase", true)?;
+        let bundle = serde_json::to_string_pretty(&Bundle::seal(&contents, &passphrase)?)?;
+        if let Some(path) = &self.output {
+            std::fs::write(path, bundle).map_err(|error| Error::Write {
+                path: path.clone(),
+                error,
+            })?;
+            print.checkln(format!(
+                "Exported {} identities to {path:?}",
+                contents.identities.len()
+            ));
+        } else {
+            println!("{bundle}");
+        }
+        Ok(())
+    }
+
+    fn contents(&self, print: &Print) -> Result<Contents, Error> {
+        let names = if self.names.is_empty() {
+            self.config_locator.list_identities()?
+        } else {
+            self.names.clone()
+        };
+        let mut contents = Contents::default();
+        for name in names {
+            match self.config_locator.read_identity(&name)? {
+                Key::Secret(Secret::SecureStore { .. }) => print.warnln(format!(
+                    "Skipping '{name}', keys in the OS secure store cannot be exported"
+                )),
+                key => {
+                    contents.identities.insert(name, key);
+                }
+            }
+        }
+        if self.networks {
+            for (name, network, location) in self.config_locator.list_networks_long()? {
+                if location != "Default" {
+                    contents.networks.insert(name, network);
+                }
+            }
+        }
+        if self.contract_aliases {
+            for (alias, data) in self.config_locator.list_contract_aliases()? {
+                contents
+                    .contract_aliases
+                    .insert(alias, data.ids.into_iter().collect());
+            }
+        }
+        Ok(contents)
+    }
+}
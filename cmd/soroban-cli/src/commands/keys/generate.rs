@@ -2,7 +2,7 @@ use sep5::SeedPhrase;
 
 use super::super::config::{
     locator, network,
-    secret::{self, Secret},
+    secret::{self, encrypted, Secret},
 };
 
 use crate::{commands::global, config::address::KeyName, print::Print, signer::secure_store};
@@ -47,6 +47,10 @@ pub struct Cmd {
     #[arg(long)]
     pub secure_store: bool,
 
+    /// Encrypt the new key with a passphrase before saving it in the config directory. The passphrase is read from `STELLAR_IDENTITY_PASSPHRASE`, or prompted for.
+    #[arg(long, conflicts_with = "secure_store")]
+    pub encrypt: bool,
+
     #[command(flatten)]
     pub config_locator: locator::Args,
 
@@ -79,7 +83,14 @@ impl Cmd {
         }
 
         let secret = self.secret(&print)?;
-        let path = self.config_locator.write_identity(&self.name, &secret)?;
+        let stored = if self.encrypt {
+            let passphrase =
+                encrypted::read_passphrase("New passphrase", true).map_err(secret::Error::from)?;
+            secret.encrypt(&passphrase)?
+        } else {
+            secret.clone()
+        };
+        let path = self.config_locator.write_identity(&self.name, &stored)?;
         print.checkln(format!("Key saved with alias {} in {path:?}", self.name));
 
         if self.fund {
@@ -146,6 +157,7 @@ mod tests {
             seed: None,
             as_secret: false,
             secure_store: false,
+            encrypt: false,
             config_locator: locator.clone(),
             hd_path: None,
             network: super::network::Args::default(),
@@ -0,0 +1,209 @@
+use std::{
+    ffi::OsString,
+    io::{stdin, IsTerminal, Read},
+    path::PathBuf,
+};
+
+use crate::{
+    commands::global,
+    config::{locator, secret::encrypted},
+    print::Print,
+};
+
+use super::bundle::{self, Bundle};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Bundle(#[from] bundle::Error),
+    #[error(transparent)]
+    Encrypted(#[from] encrypted::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    StrKey(#[from] stellar_strkey::DecodeError),
+    #[error("failed to read bundle from {path:?}: {error}")]
+    Read {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    #[error("no bundle provided")]
+    NoStdin,
+    #[error("{kind} '{name}' already exists with a different value, use `--on-conflict skip` or `--on-conflict overwrite`")]
+    Conflict { kind: Kind, name: String },
+    #[error("contract alias '{alias}' already exists on network '{network_passphrase}' with a different contract, use `--on-conflict skip` or `--on-conflict overwrite`")]
+    ContractAliasConflict {
+        alias: String,
+        network_passphrase: String,
+    },
+}
+
+/// The kind of a named config entry in a bundle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Identity,
+    Network,
+}
+
+impl std::fmt::Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Kind::Identity => "identity",
+            Kind::Network => "network",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OnConflict {
+    /// Stop before importing anything
+    #[default]
+    Fail,
+    /// Keep the existing entry
+    Skip,
+    /// Replace the existing entry
+    Overwrite,
+}
+
+/// e.g. `stellar keys import keys.json --on-conflict skip`
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// File containing a bundle written by `keys export`, or stdin if empty
+    pub bundle: Option<OsString>,
+
+    /// What to do when an identity, network or contract alias already exists with a different value
+    #[arg(long, value_enum, default_value_t)]
+    pub on_conflict: OnConflict,
+
+    #[command(flatten)]
+    pub config_locator: locator::Args,
+}
+
+enum Plan {
+    New,
+    Same,
+    Conflict,
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let bundle: Bundle = serde_json::from_str(&self.read_bundle()?)?;
+        let passphrase = encrypted::read_passphrase_from_env(
+            bundle::PASSPHRASE_ENV,
+            "Bundle passphrase",
+            false,
+        )?;
+        let contents = bundle.open(&passphrase)?;
+        let locator = &self.config_locator;
+
+        // Check every entry before writing, so a failed import leaves the config untouched
+        let identities = contents
+            .identities
+            .into_iter()
+            .map(|(name, key)| {
+                let plan = match locator.read_identity(&name) {
+                    Ok(existing) if existing == key => Plan::Same,
+                    Ok(_) => Plan::Conflict,
+                    Err(locator::Error::ConfigMissing(..)) => Plan::New,
+                    Err(e) => return Err(e.into()),
+                };
+                let conflict = || Error::Conflict {
+                    kind: Kind::Identity,
+                    name: name.clone(),
+                };
+                Ok(self.resolve(key, plan, conflict)?.map(|key| (name, key)))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let networks = contents
+            .networks
+            .into_iter()
+            .map(|(name, network)| {
+                let plan = match locator.read_network(&name) {
+                    Ok(existing)
+                        if serde_json::to_value(&existing)? == serde_json::to_value(&network)? =>
+                    {
+                        Plan::Same
+                    }
+                    Ok(_) => Plan::Conflict,
+                    Err(locator::Error::ConfigMissing(..)) => Plan::New,
+                    Err(e) => return Err(e.into()),
+                };
+                let conflict = || Error::Conflict {
+                    kind: Kind::Network,
+                    name: name.clone(),
+                };
+                Ok(self
+                    .resolve(network, plan, conflict)?
+                    .map(|network| (name, network)))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let mut aliases = Vec::new();
+        for (alias, ids) in contents.contract_aliases {
+            for (network_passphrase, contract_id) in ids {
+                let contract: stellar_strkey::Contract = contract_id.parse()?;
+                let plan = match locator.get_contract_id(&alias, &network_passphrase)? {
+                    Some(existing) if existing == contract => Plan::Same,
+                    Some(_) => Plan::Conflict,
+                    None => Plan::New,
+                };
+                let conflict = || Error::ContractAliasConflict {
+                    alias: alias.clone(),
+                    network_passphrase: network_passphrase.clone(),
+                };
+                if let Some(contract) = self.resolve(contract, plan, conflict)? {
+                    aliases.push((alias.clone(), network_passphrase, contract));
+                }
+            }
+        }
+
+        for (name, key) in identities.into_iter().flatten() {
+            locator.write_key(&name, &key)?;
+            print.checkln(format!("Imported identity '{name}'"));
+        }
+        for (name, network) in networks.into_iter().flatten() {
+            locator.write_network(&name, &network)?;
+            print.checkln(format!("Imported network '{name}'"));
+        }
+        for (alias, network_passphrase, contract) in aliases {
+            locator.save_contract_id(&network_passphrase, &contract, &alias)?;
+            print.checkln(format!("Imported contract alias '{alias}'"));
+        }
+        Ok(())
+    }
+
+    /// The value to write, if any, according to `--on-conflict`.
+    fn resolve<T>(
+        &self,
+        value: T,
+        plan: Plan,
+        conflict: impl FnOnce() -> Error,
+    ) -> Result<Option<T>, Error> {
+        match (plan, self.on_conflict) {
+            (Plan::New, _) | (Plan::Conflict, OnConflict::Overwrite) => Ok(Some(value)),
+            (Plan::Same, _) | (Plan::Conflict, OnConflict::Skip) => Ok(None),
+            (Plan::Conflict, OnConflict::Fail) => Err(conflict()),
+        }
+    }
+
+    fn read_bundle(&self) -> Result<String, Error> {
+        if let Some(path) = &self.bundle {
+            let path = PathBuf::from(path);
+            return std::fs::read_to_string(&path).map_err(|error| Error::Read { path, error });
+        }
+        if stdin().is_terminal() {
+            return Err(Error::NoStdin);
+        }
+        let mut bundle = String::new();
+        stdin()
+            .read_to_string(&mut bundle)
+            .map_err(|error| Error::Read {
+                path: PathBuf::from("stdin"),
+                error,
+            })?;
+        Ok(bundle)
+    }
+}
@@ -0,0 +1,47 @@
+use std::io::{stdin, IsTerminal, Read};
+
+use base64::Engine as _;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("no message provided")]
+    NoStdin,
+    #[error("failed to read message from stdin: {0}")]
+    Stdin(std::io::Error),
+    #[error("invalid base64 message: {0}")]
+    Base64(base64::DecodeError),
+}
+
+/// A message to sign or verify, given as an argument or on stdin.
+#[derive(Debug, clap::Args, Clone)]
+#[group(skip)]
+pub struct Args {
+    /// Message to sign or verify, or stdin if empty
+    pub message: Option<String>,
+
+    /// The message is base64 encoded binary data
+    #[arg(long)]
+    pub base64: bool,
+}
+
+impl Args {
+    pub fn bytes(&self) -> Result<Vec<u8>, Error> {
+        let message = if let Some(message) = &self.message {
+            message.as_bytes().to_vec()
+        } else {
+            if stdin().is_terminal() {
+                return Err(Error::NoStdin);
+            }
+            let mut message = Vec::new();
+            stdin().read_to_end(&mut message).map_err(Error::Stdin)?;
+            message
+        };
+        if self.base64 {
+            base64::engine::general_purpose::STANDARD
+                .decode(message.trim_ascii())
+                .map_err(Error::Base64)
+        } else {
+            Ok(message)
+        }
+    }
+}
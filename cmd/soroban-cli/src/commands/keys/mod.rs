@@ -2,14 +2,23 @@ use crate::commands::global;
 use clap::Parser;
 
 pub mod add;
+pub mod bundle;
 pub mod default;
+pub mod encrypt;
+pub mod export;
 pub mod fund;
 pub mod generate;
+pub mod import;
 pub mod ls;
+pub mod message;
 pub mod public_key;
+pub mod recover;
 pub mod rm;
 pub mod secret;
+pub mod sign_message;
+pub mod split;
 pub mod unset;
+pub mod verify_message;
 
 #[derive(Debug, Parser)]
 pub enum Cmd {
@@ -23,10 +32,19 @@ pub enum Cmd {
     /// Fund an identity on a test network
     Fund(fund::Cmd),
 
+    /// Encrypt identities stored in plaintext with a passphrase
+    Encrypt(encrypt::Cmd),
+
+    /// Export identities, and optionally networks and contract aliases, to a passphrase-encrypted bundle
+    Export(export::Cmd),
+
     /// Generate a new identity using a 24-word seed phrase
     /// The seed phrase can be stored in a config file (default) or in an OS-specific secure store.
     Generate(generate::Cmd),
 
+    /// Import identities, networks and contract aliases from a bundle written by `keys export`
+    Import(import::Cmd),
+
     /// List identities
     Ls(ls::Cmd),
 
@@ -36,6 +54,18 @@ pub enum Cmd {
     /// Output an identity's secret key
     Secret(secret::Cmd),
 
+    /// Split an identity's seed phrase into shares, any threshold of which recover it
+    Split(split::Cmd),
+
+    /// Recover a seed phrase from shares written by `keys split`, and save it as an identity
+    Recover(recover::Cmd),
+
+    /// Sign an arbitrary message with an identity, following SEP-53, and output the base64 signature
+    SignMessage(sign_message::Cmd),
+
+    /// Verify a SEP-53 message signature against an address, without network access
+    VerifyMessage(verify_message::Cmd),
+
     /// Set the default identity that will be used on all commands.
     /// This allows you to skip `--source-account` or setting a environment
     /// variable, while reusing this value in all commands that require it.
@@ -57,9 +87,18 @@ pub enum Error {
     #[error(transparent)]
     Fund(#[from] fund::Error),
 
+    #[error(transparent)]
+    Encrypt(#[from] encrypt::Error),
+
+    #[error(transparent)]
+    Export(#[from] export::Error),
+
     #[error(transparent)]
     Generate(#[from] generate::Error),
 
+    #[error(transparent)]
+    Import(#[from] import::Error),
+
     #[error(transparent)]
     Rm(#[from] rm::Error),
 
@@ -69,6 +108,18 @@ pub enum Error {
     #[error(transparent)]
     Show(#[from] secret::Error),
 
+    #[error(transparent)]
+    Split(#[from] split::Error),
+
+    #[error(transparent)]
+    Recover(#[from] recover::Error),
+
+    #[error(transparent)]
+    SignMessage(#[from] sign_message::Error),
+
+    #[error(transparent)]
+    VerifyMessage(#[from] verify_message::Error),
+
     #[error(transparent)]
     Default(#[from] default::Error),
 
@@ -82,10 +133,17 @@ impl Cmd {
             Cmd::Add(cmd) => cmd.run(global_args)?,
             Cmd::PublicKey(cmd) => cmd.run().await?,
             Cmd::Fund(cmd) => cmd.run(global_args).await?,
+            Cmd::Encrypt(cmd) => cmd.run(global_args)?,
+            Cmd::Export(cmd) => cmd.run(global_args)?,
             Cmd::Generate(cmd) => cmd.run(global_args).await?,
+            Cmd::Import(cmd) => cmd.run(global_args)?,
             Cmd::Ls(cmd) => cmd.run()?,
             Cmd::Rm(cmd) => cmd.run(global_args)?,
             Cmd::Secret(cmd) => cmd.run()?,
+            Cmd::Split(cmd) => cmd.run(global_args)?,
+            Cmd::Recover(cmd) => cmd.run(global_args)?,
+            Cmd::SignMessage(cmd) => cmd.run(global_args).await?,
+            Cmd::VerifyMessage(cmd) => cmd.run(global_args).await?,
             Cmd::Default(cmd) => cmd.run(global_args)?,
             Cmd::Unset(cmd) => cmd.run(global_args)?,
         }
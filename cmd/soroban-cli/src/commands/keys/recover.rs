@@ -0,0 +1,133 @@
+use std::{
+    io::{stdin, BufRead, IsTerminal},
+    path::Path,
+};
+
+use crate::{
+    commands::global,
+    config::{
+        address::KeyName,
+        key::Key,
+        locator,
+        secret::{self, encrypted, shamir, Secret},
+    },
+    print::Print,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Secret(#[from] secret::Error),
+    #[error(transparent)]
+    Shamir(#[from] shamir::Error),
+    #[error("share {position}: {error}")]
+    InvalidShare {
+        position: usize,
+        error: shamir::Error,
+    },
+    #[error("failed to read share: {0}")]
+    Read(std::io::Error),
+    #[error("An identity with the name '{0}' already exists")]
+    IdentityAlreadyExists(String),
+}
+
+/// e.g. `stellar keys recover alice shares/alice-share-1-of-5.txt shares/alice-share-4-of-5.txt`
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Name of the identity to save the recovered seed phrase as
+    pub name: KeyName,
+
+    /// Shares written by `keys split`, as files or quoted words. Read from stdin, one per line, if none are given
+    pub shares: Vec<String>,
+
+    /// Encrypt the recovered seed phrase with a passphrase before saving it. The passphrase is read from `STELLAR_IDENTITY_PASSPHRASE`, or prompted for.
+    #[arg(long)]
+    pub encrypt: bool,
+
+    /// Overwrite existing identity if it already exists.
+    #[arg(long)]
+    pub overwrite: bool,
+
+    #[command(flatten)]
+    pub config_locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        if self.config_locator.read_identity(&self.name).is_ok() {
+            if !self.overwrite {
+                return Err(Error::IdentityAlreadyExists(self.name.to_string()));
+            }
+            print.exclaimln(format!("Overwriting identity '{}'", self.name));
+        }
+
+        let shares = if self.shares.is_empty() {
+            Self::read_shares(&print)?
+        } else {
+            self.shares
+                .iter()
+                .enumerate()
+                .map(|(i, share)| {
+                    let share = if Path::new(share).is_file() {
+                        std::fs::read_to_string(share).map_err(Error::Read)?
+                    } else {
+                        share.clone()
+                    };
+                    parse(i + 1, &share)
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        };
+        let seed_phrase = shamir::combine(&shares)?;
+
+        let secret = Secret::SeedPhrase {
+            seed_phrase: seed_phrase.phrase().to_string(),
+        };
+        print.infoln(format!("Recovered {}", secret.public_key(None)?));
+        let key: Key = if self.encrypt {
+            let passphrase =
+                encrypted::read_passphrase("New passphrase", true).map_err(secret::Error::from)?;
+            secret.encrypt(&passphrase)?.into()
+        } else {
+            secret.into()
+        };
+        let path = self.config_locator.write_key(&self.name, &key)?;
+        print.checkln(format!("Key saved with alias {} in {path:?}", self.name));
+        Ok(())
+    }
+
+    /// Read shares one per line until the threshold of the first one is reached.
+    fn read_shares(print: &Print) -> Result<Vec<shamir::Share>, Error> {
+        let interactive = stdin().is_terminal();
+        let mut shares: Vec<shamir::Share> = Vec::new();
+        loop {
+            if let Some(first) = shares.first() {
+                if shares.len() >= usize::from(first.threshold) {
+                    return Ok(shares);
+                }
+            }
+            let line = if interactive {
+                print.arrowln(format!("Type share {}:", shares.len() + 1));
+                rpassword::read_password().map_err(Error::Read)?
+            } else {
+                let mut line = String::new();
+                if stdin().lock().read_line(&mut line).map_err(Error::Read)? == 0 {
+                    return Ok(shares);
+                }
+                line
+            };
+            if !line.trim().is_empty() {
+                shares.push(parse(shares.len() + 1, &line)?);
+            }
+        }
+    }
+}
+
+fn parse(position: usize, share: &str) -> Result<shamir::Share, Error> {
+    share
+        .parse()
+        .map_err(|error| Error::InvalidShare { position, error })
+}
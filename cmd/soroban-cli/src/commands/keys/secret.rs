@@ -1,7 +1,7 @@
 use crate::config::{
     key::{self, Key},
     locator,
-    secret::Secret,
+    secret::{self, Secret},
 };
 
 #[derive(thiserror::Error, Debug)]
@@ -12,6 +12,9 @@ pub enum Error {
     #[error(transparent)]
     Key(#[from] key::Error),
 
+    #[error(transparent)]
+    Secret(#[from] secret::Error),
+
     #[error("identity is not tied to a seed phrase")]
     UnknownSeedPhrase,
 }
@@ -47,7 +50,10 @@ impl Cmd {
     }
 
     pub fn seed_phrase(&self) -> Result<String, Error> {
-        let key = self.locator.read_identity(&self.name)?;
+        let key = match self.locator.read_identity(&self.name)? {
+            Key::Secret(secret) => Key::Secret(secret.decrypt()?),
+            key => key,
+        };
 
         if let Key::Secret(Secret::SeedPhrase { seed_phrase }) = key {
             Ok(seed_phrase)
@@ -0,0 +1,61 @@
+use base64::Engine as _;
+
+use crate::{
+    commands::global,
+    config::{locator, secret},
+    print::Print,
+    signer,
+};
+
+use super::message;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Secret(#[from] secret::Error),
+    #[error(transparent)]
+    Signer(#[from] signer::Error),
+    #[error(transparent)]
+    Message(#[from] message::Error),
+}
+
+/// e.g. `stellar keys sign-message alice "I own this account"`
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Identity to sign with, can be a local key, a key in the OS secure store or a ledger
+    pub name: String,
+
+    #[command(flatten)]
+    pub message: message::Args,
+
+    /// If identity is a seed phrase or a ledger use this hd path, default is 0
+    #[arg(long)]
+    pub hd_path: Option<usize>,
+
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let message = self.message.bytes()?;
+        let signer = self
+            .locator
+            .get_secret_key(&self.name)?
+            .signer(self.hd_path, Print::new(global_args.quiet))
+            .await?;
+        let public_key = stellar_strkey::ed25519::PublicKey(signer.get_public_key().await?);
+        signer
+            .print
+            .infoln(format!("Signing message with {public_key}"));
+        let signature = signer.sign_message(&message).await?;
+        println!(
+            "{}",
+            base64::engine::general_purpose::STANDARD.encode(signature.to_bytes())
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+use std::path::PathBuf;
+
+use sep5::SeedPhrase;
+
+use crate::{
+    commands::global,
+    config::{
+        key::Key,
+        locator,
+        secret::{self, shamir, Secret},
+    },
+    print::Print,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Secret(#[from] secret::Error),
+    #[error(transparent)]
+    Shamir(#[from] shamir::Error),
+    #[error(transparent)]
+    SeedPhrase(#[from] sep5::Error),
+    #[error("identity '{0}' is not a seed phrase stored in the config directory, only those can be split")]
+    NotASeedPhrase(String),
+    #[error("failed to write share to {path:?}: {error}")]
+    Write {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+}
+
+/// e.g. `stellar keys split alice --threshold 3 --shares 5 --output-dir shares`
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Identity whose seed phrase to split
+    pub name: String,
+
+    /// Number of shares needed to recover the seed phrase
+    #[arg(long, short = 'm')]
+    pub threshold: u8,
+
+    /// Number of shares to create
+    #[arg(long, short = 'n')]
+    pub shares: u8,
+
+    /// Directory to write one file per share to, instead of printing the shares
+    #[arg(long)]
+    pub output_dir: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub config_locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let seed_phrase = match self.config_locator.read_identity(&self.name)? {
+            Key::Secret(secret) => match secret.decrypt()? {
+                Secret::SeedPhrase { seed_phrase } => SeedPhrase::from_seed_phrase(&seed_phrase)?,
+                _ => return Err(Error::NotASeedPhrase(self.name.clone())),
+            },
+            _ => return Err(Error::NotASeedPhrase(self.name.clone())),
+        };
+        let shares = shamir::split(&seed_phrase, self.threshold, self.shares)?;
+
+        if let Some(dir) = &self.output_dir {
+            std::fs::create_dir_all(dir).map_err(|error| Error::Write {
+                path: dir.clone(),
+                error,
+            })?;
+            for share in &shares {
+                let path = dir.join(format!(
+                    "{}-share-{}-of-{}.txt",
+                    self.name, share.index, self.shares
+                ));
+                std::fs::write(&path, format!("{share}\n")).map_err(|error| Error::Write {
+                    path: path.clone(),
+                    error,
+                })?;
+                print.checkln(format!("Share {} written to {path:?}", share.index));
+            }
+        } else {
+            for share in &shares {
+                println!("{share}");
+            }
+        }
+        print.infoln(format!(
+            "Any {} of the {} shares recover '{}' with `stellar keys recover`, store them in separate places",
+            self.threshold, self.shares, self.name
+        ));
+        Ok(())
+    }
+}
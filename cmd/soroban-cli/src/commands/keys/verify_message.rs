@@ -0,0 +1,70 @@
+use base64::Engine as _;
+
+use crate::{
+    commands::global,
+    config::{address, locator, UnresolvedMuxedAccount},
+    print::Print,
+    signer,
+    tx::signatures,
+    xdr,
+};
+
+use super::message;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Address(#[from] address::Error),
+    #[error(transparent)]
+    Message(#[from] message::Error),
+    #[error("invalid base64 signature: {0}")]
+    Signature(base64::DecodeError),
+    #[error("signature is not valid for {address}")]
+    Invalid { address: String },
+}
+
+/// e.g. `stellar keys verify-message GBXF... "I own this account" --signature fO5d...`
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Address that signed the message, can be a public key (G...), a muxed account (M...) or an identity
+    pub address: UnresolvedMuxedAccount,
+
+    #[command(flatten)]
+    pub message: message::Args,
+
+    /// Base64 signature output by `keys sign-message`
+    #[arg(long)]
+    pub signature: String,
+
+    /// If address is an identity using a seed phrase use this hd path, default is 0
+    #[arg(long)]
+    pub hd_path: Option<usize>,
+
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let public_key = match self
+            .address
+            .resolve_muxed_account(&self.locator, self.hd_path)
+            .await?
+        {
+            xdr::MuxedAccount::Ed25519(key) => key.0,
+            xdr::MuxedAccount::MuxedEd25519(muxed) => muxed.ed25519.0,
+        };
+        let address = stellar_strkey::ed25519::PublicKey(public_key).to_string();
+        let signature = base64::engine::general_purpose::STANDARD
+            .decode(self.signature.trim())
+            .map_err(Error::Signature)?;
+        let payload = signer::message_payload(&self.message.bytes()?);
+        if !signatures::verify_ed25519(&public_key, &payload, &signature) {
+            return Err(Error::Invalid { address });
+        }
+        print.checkln(format!("Signature is valid for {address}"));
+        Ok(())
+    }
+}
@@ -1,11 +1,22 @@
+use soroban_spec_tools::Spec;
+
 use crate::{
-    commands::{global, txn_result::TxnEnvelopeResult},
+    assembled::simulate_and_assemble_transaction,
+    commands::{
+        contract::arg_parsing::{self, output_to_string},
+        global,
+        txn_result::TxnEnvelopeResult,
+    },
     config::{
         self,
         address::{self, UnresolvedMuxedAccount},
-        data, network, secret,
+        data, locator, network, secret,
     },
+    get_spec, key,
+    log::extract_events,
+    print::Print,
     rpc::{self, Client, GetTransactionResponse},
+    signer::Signer,
     tx::builder::{self, asset, TxExt},
     xdr::{self, Limits, WriteXdr},
 };
@@ -18,6 +29,12 @@ pub struct Args {
     /// Build the transaction and only write the base64 xdr to stdout
     #[arg(long)]
     pub build_only: bool,
+    #[command(flatten)]
+    pub sequence: super::sequence::Args,
+    #[command(flatten)]
+    pub memo: super::memo::Args,
+    #[command(flatten)]
+    pub preconditions: super::preconditions::Args,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -50,27 +67,79 @@ pub enum Error {
     InvalidPoolId(String),
     #[error("invalid hex for {name}: {hex}")]
     InvalidHex { name: String, hex: String },
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Key(#[from] key::Error),
+    #[error(transparent)]
+    GetSpec(#[from] get_spec::Error),
+    #[error(transparent)]
+    ArgParsing(#[from] arg_parsing::Error),
+    #[error(transparent)]
+    Memo(#[from] super::memo::Error),
+    #[error(transparent)]
+    Preconditions(#[from] super::preconditions::Error),
+}
+
+/// An operation built from the arguments of a `tx new` command.
+pub enum Op {
+    Classic(xdr::OperationBody),
+    Soroban(SorobanOp),
+}
+
+/// A Soroban operation, with what its transaction needs besides the operation itself.
+pub struct SorobanOp {
+    pub body: xdr::OperationBody,
+    /// Ledger entries the operation reads and writes, simulation adds the ones it discovers
+    pub footprint: xdr::LedgerFootprint,
+    /// Keys of the addresses passed as invocation arguments, to sign their auth entries
+    pub auth_signers: Vec<Signer>,
+    /// Spec and name of the invoked function, to print its result
+    pub function: Option<(Spec, String)>,
+}
+
+impl SorobanOp {
+    fn transaction_data(&self) -> xdr::SorobanTransactionData {
+        xdr::SorobanTransactionData {
+            ext: xdr::SorobanTransactionDataExt::V0,
+            resources: xdr::SorobanResources {
+                footprint: self.footprint.clone(),
+                instructions: 0,
+                disk_read_bytes: 0,
+                write_bytes: 0,
+            },
+            resource_fee: 0,
+        }
+    }
 }
 
 impl Args {
     pub async fn tx(&self, body: impl Into<xdr::OperationBody>) -> Result<xdr::Transaction, Error> {
         let source_account = self.source_account().await?;
-        let seq_num = self
-            .config
-            .next_sequence_number(source_account.clone().account_id())
-            .await?;
+        let seq_num = match &self.sequence.sequence_number {
+            Some(seq_num) => seq_num.clone(),
+            None => {
+                self.config
+                    .next_sequence_number(source_account.clone().account_id())
+                    .await?
+            }
+        };
 
         // Once we have a way to add operations this will be updated to allow for a different source account
         let operation = xdr::Operation {
             source_account: None,
             body: body.into(),
         };
-        Ok(xdr::Transaction::new_tx(
+        let mut tx = xdr::Transaction::new_tx(
             source_account,
             self.config.get_inclusion_fee()?,
             seq_num,
             operation,
-        ))
+        )
+        .add_memo(self.memo.memo()?);
+        self.preconditions
+            .apply(&mut tx, super::preconditions::now())?;
+        Ok(tx)
     }
 
     pub fn client(&self) -> Result<Client, Error> {
@@ -104,11 +173,11 @@ impl Args {
         tx: xdr::Transaction,
         args: &global::Args,
     ) -> Result<TxnEnvelopeResult<GetTransactionResponse>, Error> {
-        let network = self.config.get_network()?;
-        let client = Client::new(&network.rpc_url)?;
         if self.build_only {
             return Ok(TxnEnvelopeResult::TxnEnvelope(Box::new(tx.into())));
         }
+        let network = self.config.get_network()?;
+        let client = Client::new(&network.rpc_url)?;
 
         let txn_resp = client
             .send_transaction_polling(&self.config.sign(tx, args.quiet).await?)
@@ -159,7 +228,95 @@ impl Args {
             source_account,
             body: op_body.into(),
         };
-        Ok(super::xdr::add_op(tx_env, op)?)
+        let tx_env = super::xdr::add_op(tx_env, op)?;
+        if !self.memo.is_set()
+            && !self.preconditions.is_set()
+            && self.sequence.sequence_number.is_none()
+        {
+            return Ok(tx_env);
+        }
+        let mut tx = super::xdr::unwrap_envelope_v1(tx_env)?;
+        if let Some(seq_num) = &self.sequence.sequence_number {
+            tx.seq_num = seq_num.clone();
+        }
+        if self.memo.is_set() {
+            tx.memo = self.memo.memo()?;
+        }
+        self.preconditions
+            .apply(&mut tx, super::preconditions::now())?;
+        Ok(tx.into())
+    }
+
+    /// Build a transaction holding a Soroban operation. Unless `--build-only` is given, simulate
+    /// it to set its footprint, resources and fee, sign its auth entries, then sign and send it,
+    /// printing the result of an invocation.
+    pub async fn handle_soroban_and_print(
+        &self,
+        op: SorobanOp,
+        global_args: &global::Args,
+    ) -> Result<(), Error> {
+        let mut tx = self.tx(op.body.clone()).await?;
+        tx.ext = xdr::TransactionExt::V1(op.transaction_data());
+        if self.build_only {
+            let tx_env: xdr::TransactionEnvelope = tx.into();
+            println!("{}", tx_env.to_xdr_base64(Limits::none())?);
+            return Ok(());
+        }
+
+        let print = Print::new(global_args.quiet);
+        let network = self.config.get_network()?;
+        let client = network.rpc_client()?;
+        let assembled = simulate_and_assemble_transaction(&client, &tx, None, None).await?;
+        let mut tx = assembled.transaction().clone();
+        if let Some(signed) = self
+            .config
+            .sign_soroban_authorizations(&tx, &op.auth_signers, &[])
+            .await?
+        {
+            tx = signed;
+        }
+        let res = client
+            .send_transaction_polling(&self.config.sign(tx, global_args.quiet).await?)
+            .await?;
+        if !global_args.no_cache {
+            data::write(res.clone().try_into()?, &network.rpc_uri()?)?;
+        }
+
+        let events = extract_events(&res.result_meta.clone().unwrap_or_default());
+        crate::log::event::all(&events);
+        crate::log::event::contract(&events, &print);
+        if let Some((spec, function)) = &op.function {
+            let output = output_to_string(spec, &res.return_value()?, function)?;
+            println!("{}", output.into_result().unwrap_or_default());
+        }
+        Ok(())
+    }
+
+    /// Append a Soroban operation to a transaction and add its footprint to the transaction's.
+    pub async fn add_soroban_op(
+        &self,
+        op: SorobanOp,
+        tx_env: xdr::TransactionEnvelope,
+        op_source: Option<&address::UnresolvedMuxedAccount>,
+        quiet: bool,
+    ) -> Result<xdr::TransactionEnvelope, Error> {
+        let tx_env = self.add_op(op.body.clone(), tx_env, op_source).await?;
+        let mut tx = super::xdr::unwrap_envelope_v1(tx_env)?;
+        if tx.operations.len() > 1 {
+            Print::new(quiet).warnln(
+                "A Soroban operation must be the only operation of its transaction, the network rejects this one until the other operations are removed",
+            );
+        }
+        tx.ext = match tx.ext {
+            xdr::TransactionExt::V1(mut data) => {
+                let footprint = &mut data.resources.footprint;
+                footprint.read_only = merge_keys(&footprint.read_only, &op.footprint.read_only)?;
+                footprint.read_write = merge_keys(&footprint.read_write, &op.footprint.read_write)?;
+                xdr::TransactionExt::V1(data)
+            }
+            xdr::TransactionExt::V0 => xdr::TransactionExt::V1(op.transaction_data()),
+        };
+        Ok(tx.into())
     }
 
     pub fn resolve_asset(&self, asset: &builder::Asset) -> Result<xdr::Asset, Error> {
@@ -177,3 +334,16 @@ impl Args {
         Ok(signer_key)
     }
 }
+
+fn merge_keys(
+    keys: &xdr::VecM<xdr::LedgerKey>,
+    more: &xdr::VecM<xdr::LedgerKey>,
+) -> Result<xdr::VecM<xdr::LedgerKey>, Error> {
+    let mut keys = keys.to_vec();
+    for key in more.iter() {
+        if !keys.contains(key) {
+            keys.push(key.clone());
+        }
+    }
+    Ok(keys.try_into()?)
+}
@@ -0,0 +1,273 @@
+use std::ffi::OsString;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    commands::global,
+    config::{locator, network},
+    utils::{fee_bump_transaction_hash, transaction_hash},
+    xdr::{
+        self, DecoratedSignature, FeeBumpTransaction, FeeBumpTransactionInnerTx, Transaction,
+        TransactionEnvelope, TransactionV1Envelope,
+    },
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    XdrArgs(#[from] super::xdr::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("the transactions differ, signatures for one are not valid for the other")]
+    Differ,
+}
+
+/// Compare two transaction envelopes section by section, to check that only the signatures
+/// changed. Fails if the transactions have different signing hashes
+/// e.g. `stellar tx diff sent.txt signed.txt` or `cat signed.txt | stellar tx diff sent.txt`
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Base-64 transaction envelope XDR, or file containing XDR, to compare against
+    #[arg()]
+    pub base: OsString,
+    /// Base-64 transaction envelope XDR, or file containing XDR, to compare, or stdin if empty
+    #[arg()]
+    pub tx_xdr: Option<OsString>,
+    #[command(flatten)]
+    pub network: network::Args,
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self, _global_args: &global::Args) -> Result<(), Error> {
+        let network = self.network.get(&self.locator)?;
+        let base = super::xdr::tx_envelope_from_input(&Some(self.base.clone()))?;
+        let other = super::xdr::tx_envelope_from_input(&self.tx_xdr)?;
+        let report = Report {
+            network_passphrase: network.network_passphrase,
+        };
+        let same = report.envelopes(&base, &other)?;
+        if !same {
+            return Err(Error::Differ);
+        }
+        Ok(())
+    }
+}
+
+struct Report {
+    network_passphrase: String,
+}
+
+impl Report {
+    /// Print the differences between two envelopes. Returns whether their signing hashes are the
+    /// same.
+    fn envelopes(&self, a: &TransactionEnvelope, b: &TransactionEnvelope) -> Result<bool, Error> {
+        let (a_fee_bump, a_inner) = parts(a)?;
+        let (b_fee_bump, b_inner) = parts(b)?;
+        let mut same = true;
+        match (a_fee_bump, b_fee_bump) {
+            (Some(a), Some(b)) => same &= self.fee_bumps(a, b)?,
+            (None, None) => {}
+            (a, _) => {
+                let (from, to) = if a.is_some() {
+                    ("fee bump", "transaction")
+                } else {
+                    ("transaction", "fee bump")
+                };
+                println!("Envelope type: {from} → {to}");
+                same = false;
+            }
+        }
+        same &= self.transactions(a_inner, b_inner)?;
+        Ok(same)
+    }
+
+    fn fee_bumps(&self, a: &FeeBumpTransaction, b: &FeeBumpTransaction) -> Result<bool, Error> {
+        let a_hash = fee_bump_transaction_hash(a, &self.network_passphrase)?;
+        let b_hash = fee_bump_transaction_hash(b, &self.network_passphrase)?;
+        print_hash("Fee bump signing hash", a_hash, b_hash);
+        section("Fee source", &a.fee_source, &b.fee_source)?;
+        section("Fee bump fee", &a.fee, &b.fee)?;
+        println!();
+        Ok(a_hash == b_hash)
+    }
+
+    fn transactions(
+        &self,
+        a: &TransactionV1Envelope,
+        b: &TransactionV1Envelope,
+    ) -> Result<bool, Error> {
+        let a_hash = transaction_hash(&a.tx, &self.network_passphrase)?;
+        let b_hash = transaction_hash(&b.tx, &self.network_passphrase)?;
+        print_hash("Signing hash", a_hash, b_hash);
+        let (a, a_signatures, b, b_signatures) = (&a.tx, &a.signatures, &b.tx, &b.signatures);
+        section("Source account", &a.source_account, &b.source_account)?;
+        section("Fee", &a.fee, &b.fee)?;
+        section("Sequence number", &a.seq_num, &b.seq_num)?;
+        section("Preconditions", &a.cond, &b.cond)?;
+        section("Memo", &a.memo, &b.memo)?;
+        operations(a, b)?;
+        section("Soroban data", &a.ext, &b.ext)?;
+        signatures(a_signatures, b_signatures);
+        Ok(a_hash == b_hash)
+    }
+}
+
+/// The fee bump transaction, if any, and the transaction of an envelope.
+fn parts(
+    tx_env: &TransactionEnvelope,
+) -> Result<(Option<&FeeBumpTransaction>, &TransactionV1Envelope), Error> {
+    match tx_env {
+        TransactionEnvelope::Tx(envelope) => Ok((None, envelope)),
+        TransactionEnvelope::TxFeeBump(envelope) => {
+            let FeeBumpTransactionInnerTx::Tx(inner) = &envelope.tx.inner_tx;
+            Ok((Some(&envelope.tx), inner))
+        }
+        TransactionEnvelope::TxV0(_) => Err(super::xdr::Error::V0NotSupported.into()),
+    }
+}
+
+fn print_hash(name: &str, a: [u8; 32], b: [u8; 32]) {
+    if a == b {
+        println!("{name}: same, {}", hex::encode(a));
+    } else {
+        println!("{name}: differs, {} → {}", hex::encode(a), hex::encode(b));
+    }
+}
+
+fn operations(a: &Transaction, b: &Transaction) -> Result<(), Error> {
+    for i in 0..a.operations.len().max(b.operations.len()) {
+        let name = format!("Operation {}", i + 1);
+        match (a.operations.get(i), b.operations.get(i)) {
+            (Some(a), Some(b)) => section(&name, a, b)?,
+            (Some(a), None) => println!("{name}: removed, {}", compact(&a.body)?),
+            (None, Some(b)) => println!("{name}: added, {}", compact(&b.body)?),
+            (None, None) => {}
+        }
+    }
+    Ok(())
+}
+
+fn signatures(a: &[DecoratedSignature], b: &[DecoratedSignature]) {
+    let added = b.iter().filter(|s| !a.contains(s)).collect::<Vec<_>>();
+    let removed = a.iter().filter(|s| !b.contains(s)).collect::<Vec<_>>();
+    if added.is_empty() && removed.is_empty() {
+        println!("Signatures: same, {}", a.len());
+        return;
+    }
+    println!("Signatures: {} → {}", a.len(), b.len());
+    for signature in added {
+        println!("  added, hint {}", hex::encode(signature.hint.0));
+    }
+    for signature in removed {
+        println!("  removed, hint {}", hex::encode(signature.hint.0));
+    }
+}
+
+/// Print whether a section of the transaction changed, with the path of each changed field.
+fn section<T: Serialize>(name: &str, a: &T, b: &T) -> Result<(), Error> {
+    let mut changes = Vec::new();
+    diff(
+        "",
+        &serde_json::to_value(a)?,
+        &serde_json::to_value(b)?,
+        &mut changes,
+    );
+    match changes.as_slice() {
+        [] => println!("{name}: same"),
+        [(path, change)] if path.is_empty() => println!("{name}: {change}"),
+        changes => {
+            println!("{name}: changed");
+            for (path, change) in changes {
+                println!("  {path}: {change}");
+            }
+        }
+    }
+    Ok(())
+}
+
+fn compact<T: Serialize>(value: &T) -> Result<String, Error> {
+    Ok(serde_json::to_string(value)?)
+}
+
+/// Collect the differences between two JSON values, as the path of each changed field with
+/// its change.
+fn diff(path: &str, a: &Value, b: &Value, changes: &mut Vec<(String, String)>) {
+    let field = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{path}.{key}")
+        }
+    };
+    match (a, b) {
+        (Value::Object(a_fields), Value::Object(b_fields)) => {
+            for (key, a_value) in a_fields {
+                match b_fields.get(key) {
+                    Some(b_value) => diff(&field(key), a_value, b_value, changes),
+                    None => changes.push((field(key), format!("removed, was {a_value}"))),
+                }
+            }
+            for (key, b_value) in b_fields {
+                if !a_fields.contains_key(key) {
+                    changes.push((field(key), format!("added, {b_value}")));
+                }
+            }
+        }
+        (Value::Array(a_items), Value::Array(b_items)) => {
+            for i in 0..a_items.len().max(b_items.len()) {
+                let item = format!("{path}[{i}]");
+                match (a_items.get(i), b_items.get(i)) {
+                    (Some(a_item), Some(b_item)) => diff(&item, a_item, b_item, changes),
+                    (Some(a_item), None) => changes.push((item, format!("removed, was {a_item}"))),
+                    (None, Some(b_item)) => changes.push((item, format!("added, {b_item}"))),
+                    (None, None) => {}
+                }
+            }
+        }
+        (a, b) if a != b => changes.push((path.to_string(), format!("{a} → {b}"))),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn changes(a: &Value, b: &Value) -> Vec<String> {
+        let mut changes = Vec::new();
+        diff("", a, b, &mut changes);
+        changes
+            .into_iter()
+            .map(|(path, change)| format!("{path}: {change}"))
+            .collect()
+    }
+
+    #[test]
+    fn diff_lists_changed_paths() {
+        assert_eq!(
+            changes(
+                &json!({"payment": {"amount": "10", "asset": "native"}, "path": [1, 2]}),
+                &json!({"payment": {"amount": "20", "asset": "native"}, "path": [1], "memo": 7}),
+            ),
+            [
+                "path[1]: removed, was 2",
+                "payment.amount: \"10\" → \"20\"",
+                "memo: added, 7",
+            ]
+        );
+        assert_eq!(changes(&json!(100), &json!(200)), [": 100 → 200"]);
+        assert_eq!(
+            changes(&json!({"a": [1]}), &json!({"a": [1]})),
+            Vec::<String>::new()
+        );
+    }
+}
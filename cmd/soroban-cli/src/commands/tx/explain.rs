@@ -0,0 +1,1014 @@
+use std::{collections::HashMap, ffi::OsString};
+
+use sha2::{Digest, Sha256};
+use soroban_spec_tools::Spec;
+
+use crate::{
+    commands::global,
+    config::{key::Key, locator, network, secret::Secret},
+    get_spec::{self, contract_spec, get_remote_contract_spec},
+    print::Print,
+    tx::ONE_XLM,
+    utils::{fee_bump_transaction_hash, rpc::get_remote_wasm_from_hash, transaction_hash},
+    xdr::{
+        self, AccountId, AlphaNum12, AlphaNum4, Asset, ChangeTrustAsset, ClaimPredicate, Claimant,
+        ClaimantV0, ContractExecutable, ContractId, ContractIdPreimage,
+        ContractIdPreimageFromAddress, CreateContractArgs, CreateContractArgsV2,
+        FeeBumpTransactionEnvelope, FeeBumpTransactionInnerTx, Hash, HostFunction,
+        InvokeContractArgs, LedgerKey, LiquidityPoolParameters, Memo, MuxedAccount, Operation,
+        OperationBody, Preconditions, Price, RevokeSponsorshipOp, ScAddress, ScSpecTypeDef, ScVal,
+        SorobanAuthorizationEntry, SorobanAuthorizedFunction, SorobanAuthorizedInvocation,
+        SorobanCredentials, Transaction, TransactionEnvelope, TransactionExt,
+        TransactionV1Envelope, TrustLineAsset,
+    },
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    XdrArgs(#[from] super::xdr::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Spec(#[from] get_spec::Error),
+}
+
+const ACCOUNT_FLAGS: [(u32, &str); 4] = [
+    (1, "auth required"),
+    (2, "auth revocable"),
+    (4, "auth immutable"),
+    (8, "auth clawback enabled"),
+];
+
+const TRUSTLINE_FLAGS: [(u32, &str); 3] = [
+    (1, "authorized"),
+    (2, "authorized to maintain liabilities"),
+    (4, "clawback enabled"),
+];
+
+/// Explain a transaction envelope in plain English, with an operation per line, for reviewing
+/// it before signing
+/// e.g. `stellar tx explain file.txt` or `cat file.txt | stellar tx explain`
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Base-64 transaction envelope XDR, or file containing XDR to decode, or stdin if empty
+    #[arg()]
+    pub tx_xdr: Option<OsString>,
+    #[command(flatten)]
+    pub network: network::Args,
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let network = self.network.get(&self.locator)?;
+        let tx_env = super::xdr::tx_envelope_from_input(&self.tx_xdr)?;
+        let mut explainer = Explainer {
+            network_passphrase: network.network_passphrase.clone(),
+            names: Names::load(&self.locator, &network.network_passphrase)?,
+            specs: HashMap::new(),
+        };
+        let print = Print::new(global_args.quiet);
+        let tx = match &tx_env {
+            TransactionEnvelope::Tx(envelope) => &envelope.tx,
+            TransactionEnvelope::TxFeeBump(envelope) => {
+                let FeeBumpTransactionInnerTx::Tx(inner) = &envelope.tx.inner_tx;
+                &inner.tx
+            }
+            TransactionEnvelope::TxV0(_) => return Err(super::xdr::Error::V0NotSupported.into()),
+        };
+        let (contracts, wasms) = soroban_code(tx);
+        for contract in contracts {
+            let ScAddress::Contract(ContractId(Hash(id))) = &contract else {
+                continue;
+            };
+            match get_remote_contract_spec(id, &self.locator, &self.network, Some(global_args), None)
+                .await
+            {
+                Ok(entries) => {
+                    explainer
+                        .specs
+                        .insert(contract.to_string(), Spec::new(&entries));
+                }
+                Err(e) => print.warnln(format!(
+                    "Cannot fetch the spec of contract {contract}, its arguments are not decoded: {e}"
+                )),
+            }
+        }
+        for hash in wasms {
+            match wasm_spec(&hash, &network, global_args).await {
+                Ok(spec) => {
+                    explainer.specs.insert(hash.to_string(), spec);
+                }
+                Err(e) => print.warnln(format!(
+                    "Cannot fetch the spec of wasm {hash}, its constructor arguments are not decoded: {e}"
+                )),
+            }
+        }
+        for line in explainer.envelope(&tx_env)? {
+            println!("{line}");
+        }
+        Ok(())
+    }
+}
+
+/// The spec of an uploaded contract wasm, from the cache or the network.
+async fn wasm_spec(
+    hash: &Hash,
+    network: &network::Network,
+    global_args: &global::Args,
+) -> Result<Spec, get_spec::Error> {
+    let hash_str = hash.to_string();
+    let entries = if let Ok(entries) = crate::config::data::read_spec(&hash_str) {
+        entries
+    } else {
+        let client = network.rpc_client()?;
+        let wasm = get_remote_wasm_from_hash(&client, hash).await?;
+        let entries = contract_spec::Spec::new(&wasm)?.spec;
+        if !global_args.no_cache {
+            crate::config::data::write_spec(&hash_str, &entries)?;
+        }
+        entries
+    };
+    Ok(Spec::new(&entries))
+}
+
+/// The contracts a transaction invokes, in its operations or authorization entries, and the
+/// wasms of the contracts it deploys with constructor arguments.
+fn soroban_code(tx: &Transaction) -> (Vec<ScAddress>, Vec<Hash>) {
+    let mut contracts = Vec::new();
+    let mut wasms = Vec::new();
+    for op in tx.operations.iter() {
+        let OperationBody::InvokeHostFunction(body) = &op.body else {
+            continue;
+        };
+        match &body.host_function {
+            HostFunction::InvokeContract(args) => {
+                push_new(&mut contracts, &args.contract_address);
+            }
+            HostFunction::CreateContractV2(CreateContractArgsV2 {
+                executable: ContractExecutable::Wasm(hash),
+                ..
+            }) => push_new(&mut wasms, hash),
+            _ => {}
+        }
+        for entry in body.auth.iter() {
+            invocation_code(&entry.root_invocation, &mut contracts, &mut wasms);
+        }
+    }
+    (contracts, wasms)
+}
+
+fn invocation_code(
+    invocation: &SorobanAuthorizedInvocation,
+    contracts: &mut Vec<ScAddress>,
+    wasms: &mut Vec<Hash>,
+) {
+    match &invocation.function {
+        SorobanAuthorizedFunction::ContractFn(args) => push_new(contracts, &args.contract_address),
+        SorobanAuthorizedFunction::CreateContractV2HostFn(CreateContractArgsV2 {
+            executable: ContractExecutable::Wasm(hash),
+            ..
+        }) => push_new(wasms, hash),
+        _ => {}
+    }
+    for sub in invocation.sub_invocations.iter() {
+        invocation_code(sub, contracts, wasms);
+    }
+}
+
+fn push_new<T: PartialEq + Clone>(items: &mut Vec<T>, item: &T) {
+    if !items.contains(item) {
+        items.push(item.clone());
+    }
+}
+
+/// Names of the identities and contract aliases, shown next to the addresses they stand for.
+#[derive(Default)]
+struct Names {
+    accounts: HashMap<String, String>,
+    contracts: HashMap<String, String>,
+}
+
+impl Names {
+    fn load(locator: &locator::Args, network_passphrase: &str) -> Result<Self, Error> {
+        let mut names = Names::default();
+        for name in locator.list_identities()? {
+            // Reading the public key of these needs a device or the OS keychain
+            let Ok(key) = locator.read_identity(&name) else {
+                continue;
+            };
+            if matches!(
+                key,
+                Key::Secret(Secret::Ledger | Secret::SecureStore { .. })
+            ) {
+                continue;
+            }
+            if let Ok(account) = key.muxed_account(None) {
+                names.accounts.entry(account.to_string()).or_insert(name);
+            }
+        }
+        for (alias, data) in locator.list_contract_aliases()? {
+            if let Some(id) = data.ids.get(network_passphrase) {
+                names.contracts.entry(id.clone()).or_insert(alias);
+            }
+        }
+        Ok(names)
+    }
+
+    fn named(&self, key: &str, address: String) -> String {
+        match self.accounts.get(key).or_else(|| self.contracts.get(key)) {
+            Some(name) => format!("{name} ({address})"),
+            None => address,
+        }
+    }
+
+    fn account(&self, account: &AccountId) -> String {
+        let address = account.to_string();
+        self.named(&address, address.clone())
+    }
+
+    fn muxed(&self, account: &MuxedAccount) -> String {
+        self.named(
+            &account.clone().account_id().to_string(),
+            account.to_string(),
+        )
+    }
+
+    fn address(&self, address: &ScAddress) -> String {
+        let key = match address {
+            ScAddress::MuxedAccount(muxed) => {
+                stellar_strkey::ed25519::PublicKey(muxed.ed25519.0).to_string()
+            }
+            address => address.to_string(),
+        };
+        self.named(&key, address.to_string())
+    }
+
+    fn asset(&self, asset: &Asset) -> String {
+        match asset {
+            Asset::Native => "XLM".to_string(),
+            Asset::CreditAlphanum4(AlphaNum4 { asset_code, issuer }) => {
+                format!("{asset_code}:{}", self.account(issuer))
+            }
+            Asset::CreditAlphanum12(AlphaNum12 { asset_code, issuer }) => {
+                format!("{asset_code}:{}", self.account(issuer))
+            }
+        }
+    }
+
+    fn trustline_asset(&self, asset: &TrustLineAsset) -> String {
+        match asset {
+            TrustLineAsset::Native => self.asset(&Asset::Native),
+            TrustLineAsset::CreditAlphanum4(a) => self.asset(&Asset::CreditAlphanum4(a.clone())),
+            TrustLineAsset::CreditAlphanum12(a) => self.asset(&Asset::CreditAlphanum12(a.clone())),
+            TrustLineAsset::PoolShare(pool_id) => format!("shares of liquidity pool {pool_id}"),
+        }
+    }
+
+    fn change_trust_asset(&self, asset: &ChangeTrustAsset) -> String {
+        match asset {
+            ChangeTrustAsset::Native => self.asset(&Asset::Native),
+            ChangeTrustAsset::CreditAlphanum4(a) => self.asset(&Asset::CreditAlphanum4(a.clone())),
+            ChangeTrustAsset::CreditAlphanum12(a) => {
+                self.asset(&Asset::CreditAlphanum12(a.clone()))
+            }
+            ChangeTrustAsset::PoolShare(LiquidityPoolParameters::LiquidityPoolConstantProduct(
+                pool,
+            )) => format!(
+                "shares of the {} / {} liquidity pool",
+                self.asset(&pool.asset_a),
+                self.asset(&pool.asset_b)
+            ),
+        }
+    }
+
+    fn claimant(&self, claimant: &Claimant) -> String {
+        let Claimant::ClaimantTypeV0(ClaimantV0 {
+            destination,
+            predicate: claim_predicate,
+        }) = claimant;
+        format!(
+            "{} {}",
+            self.account(destination),
+            predicate(claim_predicate)
+        )
+    }
+
+    fn ledger_key(&self, key: &LedgerKey) -> String {
+        match key {
+            LedgerKey::Account(k) => format!("account {}", self.account(&k.account_id)),
+            LedgerKey::Trustline(k) => format!(
+                "the trustline of {} to {}",
+                self.account(&k.account_id),
+                self.trustline_asset(&k.asset)
+            ),
+            LedgerKey::Offer(k) => {
+                format!("offer {} of {}", k.offer_id, self.account(&k.seller_id))
+            }
+            LedgerKey::Data(k) => format!(
+                "data entry {:?} of {}",
+                k.data_name.to_utf8_string_lossy(),
+                self.account(&k.account_id)
+            ),
+            LedgerKey::ClaimableBalance(k) => format!("claimable balance {}", k.balance_id),
+            LedgerKey::LiquidityPool(k) => format!("liquidity pool {}", k.liquidity_pool_id),
+            key => format!("a {} entry", key.name()),
+        }
+    }
+}
+
+struct Explainer {
+    network_passphrase: String,
+    names: Names,
+    /// Specs of the contracts, by contract address, and of uploaded wasms, by hash.
+    specs: HashMap<String, Spec>,
+}
+
+impl Explainer {
+    fn envelope(&self, tx_env: &TransactionEnvelope) -> Result<Vec<String>, Error> {
+        match tx_env {
+            TransactionEnvelope::Tx(envelope) => self.transaction(envelope),
+            TransactionEnvelope::TxFeeBump(envelope) => self.fee_bump(envelope),
+            TransactionEnvelope::TxV0(_) => Err(super::xdr::Error::V0NotSupported.into()),
+        }
+    }
+
+    fn fee_bump(&self, envelope: &FeeBumpTransactionEnvelope) -> Result<Vec<String>, Error> {
+        let hash = fee_bump_transaction_hash(&envelope.tx, &self.network_passphrase)?;
+        let FeeBumpTransactionInnerTx::Tx(inner) = &envelope.tx.inner_tx;
+        let mut lines = vec![
+            format!("Fee bump transaction {}", hex::encode(hash)),
+            format!("Fee source: {}", self.names.muxed(&envelope.tx.fee_source)),
+            format!("Fee: at most {} XLM", amount(envelope.tx.fee)),
+            format!("Signatures: {}", envelope.signatures.len()),
+            String::new(),
+        ];
+        lines.extend(self.transaction(inner)?);
+        Ok(lines)
+    }
+
+    fn transaction(&self, envelope: &TransactionV1Envelope) -> Result<Vec<String>, Error> {
+        let tx = &envelope.tx;
+        let hash = transaction_hash(tx, &self.network_passphrase)?;
+        let mut lines = vec![
+            format!("Transaction {}", hex::encode(hash)),
+            format!("Network: {}", self.network_passphrase),
+            format!("Source account: {}", self.names.muxed(&tx.source_account)),
+            format!("Sequence number: {}", tx.seq_num.0),
+        ];
+        let fee = match &tx.ext {
+            TransactionExt::V1(data) => format!(
+                "Fee: at most {} XLM, including a resource fee of {} XLM",
+                amount(i64::from(tx.fee)),
+                amount(data.resource_fee)
+            ),
+            TransactionExt::V0 => format!("Fee: at most {} XLM", amount(i64::from(tx.fee))),
+        };
+        lines.push(fee);
+        if let Some(memo) = memo(&tx.memo) {
+            lines.push(format!("Memo: {memo}"));
+        }
+        lines.extend(preconditions(&tx.cond));
+        lines.push(format!("Signatures: {}", envelope.signatures.len()));
+        lines.push(String::new());
+        lines.push("Operations:".to_string());
+        for (i, op) in tx.operations.iter().enumerate() {
+            let source = op.source_account.as_ref().unwrap_or(&tx.source_account);
+            lines.push(format!("{}. {}", i + 1, self.operation(tx, source, op)));
+            if let OperationBody::InvokeHostFunction(body) = &op.body {
+                for entry in body.auth.iter() {
+                    self.authorization(&mut lines, source, entry);
+                }
+            }
+        }
+        Ok(lines)
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn operation(&self, tx: &Transaction, source: &MuxedAccount, op: &Operation) -> String {
+        let names = &self.names;
+        let source = names.muxed(source);
+        match &op.body {
+            OperationBody::CreateAccount(op) => format!(
+                "{source} creates account {} with a starting balance of {} XLM",
+                names.account(&op.destination),
+                amount(op.starting_balance)
+            ),
+            OperationBody::Payment(op) => format!(
+                "{source} pays {} {} to {}",
+                amount(op.amount),
+                names.asset(&op.asset),
+                names.muxed(&op.destination)
+            ),
+            OperationBody::PathPaymentStrictReceive(op) => format!(
+                "{source} pays exactly {} {} to {}, sending at most {} {}{}",
+                amount(op.dest_amount),
+                names.asset(&op.dest_asset),
+                names.muxed(&op.destination),
+                amount(op.send_max),
+                names.asset(&op.send_asset),
+                self.path(&op.path)
+            ),
+            OperationBody::PathPaymentStrictSend(op) => format!(
+                "{source} sends exactly {} {} to {}, who receives at least {} {}{}",
+                amount(op.send_amount),
+                names.asset(&op.send_asset),
+                names.muxed(&op.destination),
+                amount(op.dest_min),
+                names.asset(&op.dest_asset),
+                self.path(&op.path)
+            ),
+            OperationBody::ManageSellOffer(op) => {
+                let (selling, buying) = (names.asset(&op.selling), names.asset(&op.buying));
+                offer(
+                    &source,
+                    op.offer_id,
+                    op.amount,
+                    &format!(
+                        "sell {} {selling} for {buying} at a price of {} {buying} per {selling}",
+                        amount(op.amount),
+                        price(&op.price)
+                    ),
+                )
+            }
+            OperationBody::ManageBuyOffer(op) => {
+                let (selling, buying) = (names.asset(&op.selling), names.asset(&op.buying));
+                offer(
+                    &source,
+                    op.offer_id,
+                    op.buy_amount,
+                    &format!(
+                        "buy {} {buying} for {selling} at a price of {} {selling} per {buying}",
+                        amount(op.buy_amount),
+                        price(&op.price)
+                    ),
+                )
+            }
+            OperationBody::CreatePassiveSellOffer(op) => {
+                let (selling, buying) = (names.asset(&op.selling), names.asset(&op.buying));
+                format!(
+                    "{source} offers passively to sell {} {selling} for {buying} at a price of {} {buying} per {selling}",
+                    amount(op.amount),
+                    price(&op.price)
+                )
+            }
+            OperationBody::SetOptions(op) => {
+                let mut changes = Vec::new();
+                if let Some(inflation_dest) = &op.inflation_dest {
+                    changes.push(format!(
+                        "sets its inflation destination to {}",
+                        names.account(inflation_dest)
+                    ));
+                }
+                if let Some(set_flags) = op.set_flags {
+                    changes.push(format!("sets flags {}", flags(set_flags, &ACCOUNT_FLAGS)));
+                }
+                if let Some(clear_flags) = op.clear_flags {
+                    changes.push(format!(
+                        "clears flags {}",
+                        flags(clear_flags, &ACCOUNT_FLAGS)
+                    ));
+                }
+                if let Some(weight) = op.master_weight {
+                    changes.push(format!("sets its master key weight to {weight}"));
+                }
+                for (name, threshold) in [
+                    ("low", op.low_threshold),
+                    ("medium", op.med_threshold),
+                    ("high", op.high_threshold),
+                ] {
+                    if let Some(threshold) = threshold {
+                        changes.push(format!("sets its {name} threshold to {threshold}"));
+                    }
+                }
+                if let Some(home_domain) = &op.home_domain {
+                    changes.push(format!(
+                        "sets its home domain to {:?}",
+                        home_domain.to_utf8_string_lossy()
+                    ));
+                }
+                if let Some(signer) = &op.signer {
+                    let key = signer.key.to_string();
+                    let key = names.named(&key, key.clone());
+                    changes.push(if signer.weight == 0 {
+                        format!("removes signer {key}")
+                    } else {
+                        format!("adds signer {key} with weight {}", signer.weight)
+                    });
+                }
+                if changes.is_empty() {
+                    format!("{source} sets no account options")
+                } else {
+                    format!("{source} {}", changes.join(", "))
+                }
+            }
+            OperationBody::ChangeTrust(op) => {
+                let asset = names.change_trust_asset(&op.line);
+                match op.limit {
+                    0 => format!("{source} removes its trustline to {asset}"),
+                    i64::MAX => format!("{source} trusts {asset} with no limit"),
+                    limit => format!("{source} trusts {asset} up to {}", amount(limit)),
+                }
+            }
+            OperationBody::AllowTrust(op) => {
+                let trustor = names.account(&op.trustor);
+                let code = &op.asset;
+                match op.authorize {
+                    0 => format!("{source} deauthorizes {trustor} to hold {code}"),
+                    2 => format!(
+                        "{source} authorizes {trustor} to maintain liabilities of {code}"
+                    ),
+                    _ => format!("{source} authorizes {trustor} to hold {code}"),
+                }
+            }
+            OperationBody::AccountMerge(destination) => format!(
+                "{source} merges its account into {}, transferring all its XLM",
+                names.muxed(destination)
+            ),
+            OperationBody::Inflation => format!("{source} runs inflation"),
+            OperationBody::ManageData(op) => {
+                let name = op.data_name.to_utf8_string_lossy();
+                match &op.data_value {
+                    Some(value) => format!(
+                        "{source} sets data entry {name:?} to {}",
+                        data_value(value.as_slice())
+                    ),
+                    None => format!("{source} deletes data entry {name:?}"),
+                }
+            }
+            OperationBody::BumpSequence(op) => {
+                format!("{source} bumps its sequence number to {}", op.bump_to.0)
+            }
+            OperationBody::CreateClaimableBalance(op) => format!(
+                "{source} creates a claimable balance of {} {}, claimable by {}",
+                amount(op.amount),
+                names.asset(&op.asset),
+                op.claimants
+                    .iter()
+                    .map(|claimant| names.claimant(claimant))
+                    .collect::<Vec<_>>()
+                    .join(", or by ")
+            ),
+            OperationBody::ClaimClaimableBalance(op) => {
+                format!("{source} claims claimable balance {}", op.balance_id)
+            }
+            OperationBody::BeginSponsoringFutureReserves(op) => format!(
+                "{source} begins sponsoring the reserves of {}",
+                names.account(&op.sponsored_id)
+            ),
+            OperationBody::EndSponsoringFutureReserves => {
+                format!("{source} ends the sponsorship of its reserves")
+            }
+            OperationBody::RevokeSponsorship(RevokeSponsorshipOp::LedgerEntry(key)) => format!(
+                "{source} revokes the sponsorship of {}",
+                names.ledger_key(key)
+            ),
+            OperationBody::RevokeSponsorship(RevokeSponsorshipOp::Signer(op)) => format!(
+                "{source} revokes the sponsorship of signer {} of {}",
+                op.signer_key,
+                names.account(&op.account_id)
+            ),
+            OperationBody::Clawback(op) => format!(
+                "{source} claws back {} {} from {}",
+                amount(op.amount),
+                names.asset(&op.asset),
+                names.muxed(&op.from)
+            ),
+            OperationBody::ClawbackClaimableBalance(op) => {
+                format!("{source} claws back claimable balance {}", op.balance_id)
+            }
+            OperationBody::SetTrustLineFlags(op) => {
+                let mut changes = Vec::new();
+                if op.set_flags != 0 {
+                    changes.push(format!("sets flags {}", flags(op.set_flags, &TRUSTLINE_FLAGS)));
+                }
+                if op.clear_flags != 0 {
+                    changes.push(format!(
+                        "clears flags {}",
+                        flags(op.clear_flags, &TRUSTLINE_FLAGS)
+                    ));
+                }
+                if changes.is_empty() {
+                    changes.push("changes no flags".to_string());
+                }
+                format!(
+                    "{source} {} of the trustline of {} to {}",
+                    changes.join(" and "),
+                    names.account(&op.trustor),
+                    names.asset(&op.asset)
+                )
+            }
+            OperationBody::LiquidityPoolDeposit(op) => format!(
+                "{source} deposits at most {} of asset A and {} of asset B in liquidity pool {}, at a price of A in B between {} and {}",
+                amount(op.max_amount_a),
+                amount(op.max_amount_b),
+                op.liquidity_pool_id,
+                price(&op.min_price),
+                price(&op.max_price)
+            ),
+            OperationBody::LiquidityPoolWithdraw(op) => format!(
+                "{source} withdraws {} shares from liquidity pool {}, receiving at least {} of asset A and {} of asset B",
+                amount(op.amount),
+                op.liquidity_pool_id,
+                amount(op.min_amount_a),
+                amount(op.min_amount_b)
+            ),
+            OperationBody::InvokeHostFunction(op) => match &op.host_function {
+                HostFunction::InvokeContract(args) => {
+                    format!("{source} invokes {}", self.call(args))
+                }
+                HostFunction::CreateContract(args) => {
+                    format!("{source} deploys {}", self.create_contract(args))
+                }
+                HostFunction::CreateContractV2(args) => {
+                    format!("{source} deploys {}", self.create_contract_v2(args))
+                }
+                HostFunction::UploadContractWasm(wasm) => format!(
+                    "{source} uploads contract wasm {} of {} bytes",
+                    hex::encode(Sha256::digest(wasm.as_slice())),
+                    wasm.len()
+                ),
+            },
+            OperationBody::ExtendFootprintTtl(op) => format!(
+                "{source} extends the TTL of {} ledger entries to at least {} ledgers",
+                footprint(tx).0,
+                op.extend_to
+            ),
+            OperationBody::RestoreFootprint(_) => format!(
+                "{source} restores {} archived ledger entries",
+                footprint(tx).1
+            ),
+        }
+    }
+
+    fn path(&self, path: &[Asset]) -> String {
+        if path.is_empty() {
+            return String::new();
+        }
+        let assets = path
+            .iter()
+            .map(|asset| self.names.asset(asset))
+            .collect::<Vec<_>>();
+        format!(", through {}", assets.join(", "))
+    }
+
+    /// A contract call as `function(name: value, …) on contract`, with the arguments decoded
+    /// through the contract spec when it could be fetched.
+    fn call(&self, args: &InvokeContractArgs) -> String {
+        let function = args.function_name.to_utf8_string_lossy();
+        let spec = self.specs.get(&args.contract_address.to_string());
+        format!(
+            "{function}({}) on {}",
+            self.args(spec, &function, &args.args),
+            self.names.address(&args.contract_address)
+        )
+    }
+
+    fn create_contract(&self, args: &CreateContractArgs) -> String {
+        self.deployment(&args.executable, &args.contract_id_preimage)
+    }
+
+    fn create_contract_v2(&self, args: &CreateContractArgsV2) -> String {
+        let deployment = self.deployment(&args.executable, &args.contract_id_preimage);
+        if args.constructor_args.is_empty() {
+            return deployment;
+        }
+        let spec = match &args.executable {
+            ContractExecutable::Wasm(hash) => self.specs.get(&hash.to_string()),
+            ContractExecutable::StellarAsset => None,
+        };
+        format!(
+            "{deployment}, calling its constructor with ({})",
+            self.args(spec, "__constructor", &args.constructor_args)
+        )
+    }
+
+    fn deployment(&self, executable: &ContractExecutable, preimage: &ContractIdPreimage) -> String {
+        match (executable, preimage) {
+            (_, ContractIdPreimage::Asset(asset)) => {
+                format!("the Stellar Asset Contract of {}", self.names.asset(asset))
+            }
+            (
+                executable,
+                ContractIdPreimage::Address(ContractIdPreimageFromAddress { address, salt }),
+            ) => {
+                let code = match executable {
+                    ContractExecutable::Wasm(hash) => format!("a contract of wasm {hash}"),
+                    ContractExecutable::StellarAsset => "a Stellar Asset Contract".to_string(),
+                };
+                format!(
+                    "{code} with deployer {} and salt {}",
+                    self.names.address(address),
+                    hex::encode(salt.0)
+                )
+            }
+        }
+    }
+
+    fn args(&self, spec: Option<&Spec>, function: &str, args: &[ScVal]) -> String {
+        let inputs = spec
+            .and_then(|spec| spec.find_function(function).ok())
+            .map(|f| f.inputs.as_slice())
+            .filter(|inputs| inputs.len() == args.len());
+        match inputs {
+            Some(inputs) => inputs
+                .iter()
+                .zip(args)
+                .map(|(input, val)| {
+                    format!(
+                        "{}: {}",
+                        input.name.to_utf8_string_lossy(),
+                        self.value(spec, val, Some(&input.type_))
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+            None => args
+                .iter()
+                .map(|val| self.value(None, val, None))
+                .collect::<Vec<_>>()
+                .join(", "),
+        }
+    }
+
+    fn value(&self, spec: Option<&Spec>, val: &ScVal, type_: Option<&ScSpecTypeDef>) -> String {
+        if let ScVal::Address(address) = val {
+            return self.names.address(address);
+        }
+        if let (Some(spec), Some(type_)) = (spec, type_) {
+            if let Ok(json) = spec.xdr_to_json(val, type_) {
+                return json.to_string();
+            }
+        }
+        soroban_spec_tools::to_string(val).unwrap_or_else(|_| format!("{val:?}"))
+    }
+
+    /// An authorization entry, as who authorizes it followed by the tree of invocations it
+    /// authorizes.
+    fn authorization(
+        &self,
+        lines: &mut Vec<String>,
+        source: &MuxedAccount,
+        entry: &SorobanAuthorizationEntry,
+    ) {
+        let by = match &entry.credentials {
+            SorobanCredentials::SourceAccount => {
+                format!("the source account {}", self.names.muxed(source))
+            }
+            SorobanCredentials::Address(credentials) => {
+                let signed = if credentials.signature == ScVal::Void {
+                    "not signed yet"
+                } else {
+                    "signed"
+                };
+                format!(
+                    "{}, nonce {}, until ledger {}, {signed}",
+                    self.names.address(&credentials.address),
+                    credentials.nonce,
+                    credentials.signature_expiration_ledger
+                )
+            }
+        };
+        lines.push(format!("   Authorized by {by}:"));
+        self.invocation(lines, &entry.root_invocation, 5);
+    }
+
+    fn invocation(
+        &self,
+        lines: &mut Vec<String>,
+        invocation: &SorobanAuthorizedInvocation,
+        indent: usize,
+    ) {
+        let function = match &invocation.function {
+            SorobanAuthorizedFunction::ContractFn(args) => self.call(args),
+            SorobanAuthorizedFunction::CreateContractHostFn(args) => {
+                format!("deploy {}", self.create_contract(args))
+            }
+            SorobanAuthorizedFunction::CreateContractV2HostFn(args) => {
+                format!("deploy {}", self.create_contract_v2(args))
+            }
+        };
+        lines.push(format!("{:indent$}{function}", ""));
+        for sub in invocation.sub_invocations.iter() {
+            self.invocation(lines, sub, indent + 2);
+        }
+    }
+}
+
+fn preconditions(cond: &Preconditions) -> Vec<String> {
+    let mut lines = Vec::new();
+    let (time_bounds, v2) = match cond {
+        Preconditions::None => return lines,
+        Preconditions::Time(time_bounds) => (Some(time_bounds), None),
+        Preconditions::V2(v2) => (v2.time_bounds.as_ref(), Some(v2)),
+    };
+    if let Some(time_bounds) = time_bounds {
+        if time_bounds.min_time.0 > 0 {
+            lines.push(format!("Valid after: {}", time(time_bounds.min_time.0)));
+        }
+        if time_bounds.max_time.0 > 0 {
+            lines.push(format!("Valid until: {}", time(time_bounds.max_time.0)));
+        }
+    }
+    let Some(v2) = v2 else {
+        return lines;
+    };
+    if let Some(ledger_bounds) = &v2.ledger_bounds {
+        if ledger_bounds.min_ledger > 0 {
+            lines.push(format!("Valid from ledger: {}", ledger_bounds.min_ledger));
+        }
+        if ledger_bounds.max_ledger > 0 {
+            lines.push(format!("Valid before ledger: {}", ledger_bounds.max_ledger));
+        }
+    }
+    if let Some(min_seq_num) = &v2.min_seq_num {
+        lines.push(format!("Minimum source sequence number: {}", min_seq_num.0));
+    }
+    if v2.min_seq_age.0 > 0 {
+        lines.push(format!(
+            "Minimum source sequence age: {} seconds",
+            v2.min_seq_age.0
+        ));
+    }
+    if v2.min_seq_ledger_gap > 0 {
+        lines.push(format!(
+            "Minimum source sequence ledger gap: {}",
+            v2.min_seq_ledger_gap
+        ));
+    }
+    for signer in v2.extra_signers.iter() {
+        lines.push(format!("Extra signer: {signer}"));
+    }
+    lines
+}
+
+fn offer(source: &str, offer_id: i64, amount: i64, terms: &str) -> String {
+    match (offer_id, amount) {
+        (0, _) => format!("{source} offers to {terms}"),
+        (offer_id, 0) => format!("{source} deletes offer {offer_id}"),
+        (offer_id, _) => format!("{source} updates offer {offer_id} to {terms}"),
+    }
+}
+
+/// The number of read-only and read-write ledger entries in the Soroban footprint.
+fn footprint(tx: &Transaction) -> (usize, usize) {
+    match &tx.ext {
+        TransactionExt::V1(data) => (
+            data.resources.footprint.read_only.len(),
+            data.resources.footprint.read_write.len(),
+        ),
+        TransactionExt::V0 => (0, 0),
+    }
+}
+
+fn memo(memo: &Memo) -> Option<String> {
+    Some(match memo {
+        Memo::None => return None,
+        Memo::Text(text) => format!("text {:?}", text.to_utf8_string_lossy()),
+        Memo::Id(id) => format!("id {id}"),
+        Memo::Hash(hash) => format!("hash {hash}"),
+        Memo::Return(hash) => format!("return {hash}"),
+    })
+}
+
+fn predicate(predicate: &ClaimPredicate) -> String {
+    match predicate {
+        ClaimPredicate::Unconditional => "at any time".to_string(),
+        ClaimPredicate::And(predicates) => format!(
+            "({})",
+            predicates
+                .iter()
+                .map(self::predicate)
+                .collect::<Vec<_>>()
+                .join(" and ")
+        ),
+        ClaimPredicate::Or(predicates) => format!(
+            "({})",
+            predicates
+                .iter()
+                .map(self::predicate)
+                .collect::<Vec<_>>()
+                .join(" or ")
+        ),
+        ClaimPredicate::Not(Some(predicate)) => format!("not {}", self::predicate(predicate)),
+        ClaimPredicate::Not(None) => "never".to_string(),
+        ClaimPredicate::BeforeAbsoluteTime(seconds) => {
+            format!(
+                "before {}",
+                time(u64::try_from(*seconds).unwrap_or_default())
+            )
+        }
+        ClaimPredicate::BeforeRelativeTime(seconds) => {
+            format!("within {seconds} seconds of the balance's creation")
+        }
+    }
+}
+
+/// Format an amount in stroops in whole units, e.g. `15_000_000` as `1.5`.
+fn amount(stroops: i64) -> String {
+    let sign = if stroops < 0 { "-" } else { "" };
+    let one = ONE_XLM.unsigned_abs();
+    let (whole, fraction) = (stroops.unsigned_abs() / one, stroops.unsigned_abs() % one);
+    if fraction == 0 {
+        return format!("{sign}{whole}");
+    }
+    let fraction = format!("{fraction:07}");
+    format!("{sign}{whole}.{}", fraction.trim_end_matches('0'))
+}
+
+fn price(price: &Price) -> String {
+    if price.d == 0 {
+        return format!("{}/{}", price.n, price.d);
+    }
+    let price = format!("{:.7}", f64::from(price.n) / f64::from(price.d));
+    price
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+fn time(seconds: u64) -> String {
+    i64::try_from(seconds)
+        .ok()
+        .and_then(|seconds| chrono::DateTime::from_timestamp(seconds, 0))
+        .map_or_else(|| seconds.to_string(), |time| time.to_rfc3339())
+}
+
+fn flags(value: u32, names: &[(u32, &str)]) -> String {
+    names
+        .iter()
+        .filter(|(flag, _)| value & flag != 0)
+        .map(|(_, name)| *name)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A data entry value as a string if it is readable text, or else hex-encoded.
+fn data_value(value: &[u8]) -> String {
+    match std::str::from_utf8(value) {
+        Ok(text) if !text.chars().any(char::is_control) => format!("{text:?}"),
+        _ => format!("0x{}", hex::encode(value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdr::{AssetCode4, PaymentOp, PublicKey, Uint256};
+
+    #[test]
+    fn amounts_are_in_whole_units() {
+        assert_eq!(amount(10_000_000), "1");
+        assert_eq!(amount(15_000_000), "1.5");
+        assert_eq!(amount(1), "0.0000001");
+        assert_eq!(amount(-25_000_000), "-2.5");
+        assert_eq!(price(&Price { n: 3, d: 2 }), "1.5");
+        assert_eq!(price(&Price { n: 1, d: 3 }), "0.3333333");
+    }
+
+    #[test]
+    fn payment_is_explained_with_names() {
+        let alice = AccountId(PublicKey::PublicKeyTypeEd25519(Uint256([1; 32])));
+        let bob = MuxedAccount::Ed25519(Uint256([2; 32]));
+        let mut names = Names::default();
+        names
+            .accounts
+            .insert(alice.to_string(), "alice".to_string());
+        let explainer = Explainer {
+            network_passphrase: String::new(),
+            names,
+            specs: HashMap::new(),
+        };
+        let op = Operation {
+            source_account: None,
+            body: OperationBody::Payment(PaymentOp {
+                destination: bob.clone(),
+                asset: Asset::CreditAlphanum4(AlphaNum4 {
+                    asset_code: AssetCode4(*b"USDC"),
+                    issuer: alice.clone(),
+                }),
+                amount: 12_500_000,
+            }),
+        };
+        let tx = Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256([1; 32])),
+            fee: 100,
+            seq_num: xdr::SequenceNumber(1),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: vec![op.clone()].try_into().unwrap(),
+            ext: TransactionExt::V0,
+        };
+        assert_eq!(
+            explainer.operation(&tx, &tx.source_account, &op),
+            format!("alice ({alice}) pays 1.25 USDC:alice ({alice}) to {bob}")
+        );
+    }
+}
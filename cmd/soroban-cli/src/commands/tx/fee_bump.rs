@@ -0,0 +1,275 @@
+use std::ffi::OsString;
+
+use crate::{
+    commands::global,
+    config::{address, locator, network, sign_with, UnresolvedMuxedAccount},
+    print::Print,
+    rpc,
+    xdr::{
+        self, FeeBumpTransaction, FeeBumpTransactionEnvelope, FeeBumpTransactionExt,
+        FeeBumpTransactionInnerTx, LedgerHeaderHistoryEntry, Limits, MuxedAccount, ReadXdr,
+        Transaction, TransactionEnvelope, TransactionExt, VecM, WriteXdr,
+    },
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    XdrArgs(#[from] super::xdr::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Address(#[from] address::Error),
+    #[error(transparent)]
+    SignWith(#[from] sign_with::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error("the network did not return its latest ledger {sequence}, pass `--base-fee`")]
+    MissingLatestLedger { sequence: u32 },
+    #[error("the transaction is already a fee bump")]
+    AlreadyFeeBump,
+    #[error("the inner transaction is not signed, sign it before wrapping it in a fee bump")]
+    UnsignedInner,
+    #[error("fee {fee} is below the minimum of {minimum} stroops for this fee bump")]
+    FeeTooLow { fee: i64, minimum: i64 },
+}
+
+/// e.g. `stellar tx fee-bump signed.xdr --fee-source sponsor | stellar tx send`
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Base-64 signed transaction envelope XDR, or file containing XDR, to wrap, or stdin if empty
+    #[arg()]
+    pub tx_xdr: Option<OsString>,
+    /// Account that pays the fee. Can be an identity (--fee-source alice), a public key (--fee-source GDKW...) or a muxed account (--fee-source MDA…). Unless `--build-only` is given, it also signs the fee bump when no `--sign-with-*` signer is given
+    #[arg(long)]
+    pub fee_source: UnresolvedMuxedAccount,
+    /// Maximum total fee the fee source pays, in stroops, including the Soroban resource fee of the inner transaction. Defaults to the minimum fee for the fee bump
+    #[arg(long)]
+    pub fee: Option<i64>,
+    /// Base fee per operation of the network, in stroops, used to compute the minimum fee. Defaults to the base fee of the latest ledger of the network
+    #[arg(long)]
+    pub base_fee: Option<i64>,
+    /// Build the fee bump and only write the base64 xdr to stdout, without signing it
+    #[arg(long)]
+    pub build_only: bool,
+    #[command(flatten)]
+    pub sign_with: sign_with::Args,
+    #[command(flatten)]
+    pub network: network::Args,
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let inner = super::xdr::tx_envelope_from_input(&self.tx_xdr)?;
+        let fee_source = self
+            .fee_source
+            .resolve_muxed_account(&self.locator, self.sign_with.hd_path)
+            .await?;
+        let base_fee = match self.base_fee {
+            Some(base_fee) => base_fee,
+            None => network_base_fee(&self.network.get(&self.locator)?.rpc_client()?).await?,
+        };
+        let mut tx_env = fee_bump(inner, fee_source, self.fee, base_fee)?;
+        if let TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope { tx, .. }) = &tx_env {
+            print.infoln(format!("Fee bump offers a fee of {} stroops", tx.fee));
+        }
+        if !self.build_only {
+            let network = self.network.get(&self.locator)?;
+            tx_env = self
+                .sign_with
+                .sign_tx_env(
+                    &tx_env,
+                    &self.locator,
+                    &network,
+                    global_args.quiet,
+                    Some(&self.fee_source),
+                )
+                .await?;
+        }
+        println!("{}", tx_env.to_xdr_base64(Limits::none())?);
+        Ok(())
+    }
+}
+
+/// The base fee per operation of the latest ledger of the network, in stroops.
+pub async fn network_base_fee(client: &rpc::Client) -> Result<i64, Error> {
+    let sequence = client.get_latest_ledger().await?.sequence;
+    let ledgers = client
+        .get_ledgers(rpc::LedgerStart::Ledger(sequence), Some(1), None)
+        .await?;
+    let Some(ledger) = ledgers.ledgers.first() else {
+        return Err(Error::MissingLatestLedger { sequence });
+    };
+    let entry = LedgerHeaderHistoryEntry::from_xdr_base64(&ledger.header_xdr, Limits::none())?;
+    Ok(i64::from(entry.header.base_fee))
+}
+
+/// Wrap a signed transaction in an unsigned fee bump paid by `fee_source`, offering `fee`, or
+/// the minimum fee at `base_fee` if none is given.
+pub fn fee_bump(
+    inner: TransactionEnvelope,
+    fee_source: MuxedAccount,
+    fee: Option<i64>,
+    base_fee: i64,
+) -> Result<TransactionEnvelope, Error> {
+    let inner = match inner {
+        TransactionEnvelope::Tx(inner) => inner,
+        TransactionEnvelope::TxFeeBump(_) => return Err(Error::AlreadyFeeBump),
+        TransactionEnvelope::TxV0(_) => return Err(super::xdr::Error::V0NotSupported.into()),
+    };
+    if inner.signatures.is_empty() {
+        return Err(Error::UnsignedInner);
+    }
+    let minimum = minimum_fee(&inner.tx, base_fee);
+    let fee = fee.unwrap_or(minimum);
+    if fee < minimum {
+        return Err(Error::FeeTooLow { fee, minimum });
+    }
+    Ok(TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope {
+        tx: FeeBumpTransaction {
+            fee_source,
+            fee,
+            inner_tx: FeeBumpTransactionInnerTx::Tx(inner),
+            ext: FeeBumpTransactionExt::V0,
+        },
+        signatures: VecM::default(),
+    }))
+}
+
+/// The smallest fee a fee bump of `inner` can offer. On top of the Soroban resource fee, the
+/// fee bump pays for one more operation than the inner transaction, at a rate of at least the
+/// base fee and at least the inner transaction's inclusion fee per operation.
+pub fn minimum_fee(inner: &Transaction, base_fee: i64) -> i64 {
+    let resource_fee = match &inner.ext {
+        TransactionExt::V0 => 0,
+        TransactionExt::V1(data) => data.resource_fee,
+    };
+    let ops = i64::try_from(inner.operations.len())
+        .unwrap_or(i64::MAX)
+        .max(1);
+    let inner_inclusion_fee = i64::from(inner.fee) - resource_fee;
+    // The inner rate is inner_inclusion_fee / ops, rounded up so the fee bump's rate isn't lower
+    let inclusion_fee = (inner_inclusion_fee * (ops + 1) + ops - 1) / ops;
+    resource_fee + inclusion_fee.max(base_fee * (ops + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdr::{
+        DecoratedSignature, Memo, Operation, OperationBody, Preconditions, SequenceNumber,
+        SorobanResources, SorobanTransactionData, SorobanTransactionDataExt, TransactionV1Envelope,
+        Uint256,
+    };
+
+    fn inner(fee: u32, ops: usize, resource_fee: i64) -> TransactionEnvelope {
+        let op = Operation {
+            source_account: None,
+            body: OperationBody::Inflation,
+        };
+        let ext = if resource_fee == 0 {
+            TransactionExt::V0
+        } else {
+            TransactionExt::V1(SorobanTransactionData {
+                ext: SorobanTransactionDataExt::V0,
+                resources: SorobanResources {
+                    footprint: xdr::LedgerFootprint {
+                        read_only: VecM::default(),
+                        read_write: VecM::default(),
+                    },
+                    instructions: 0,
+                    disk_read_bytes: 0,
+                    write_bytes: 0,
+                },
+                resource_fee,
+            })
+        };
+        TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: Transaction {
+                source_account: MuxedAccount::Ed25519(Uint256([1; 32])),
+                fee,
+                seq_num: SequenceNumber(1),
+                cond: Preconditions::None,
+                memo: Memo::None,
+                operations: vec![op; ops].try_into().unwrap(),
+                ext,
+            },
+            signatures: vec![DecoratedSignature {
+                hint: xdr::SignatureHint([0; 4]),
+                signature: xdr::Signature::default(),
+            }]
+            .try_into()
+            .unwrap(),
+        })
+    }
+
+    fn bump(inner: TransactionEnvelope, fee: Option<i64>) -> Result<i64, Error> {
+        match fee_bump(inner, MuxedAccount::Ed25519(Uint256([2; 32])), fee, 100)? {
+            TransactionEnvelope::TxFeeBump(env) => Ok(env.tx.fee),
+            _ => panic!("expected a fee bump"),
+        }
+    }
+
+    #[test]
+    fn minimum_fee_covers_an_extra_operation() {
+        assert_eq!(bump(inner(100, 1, 0), None).unwrap(), 200);
+        assert_eq!(bump(inner(300, 3, 0), None).unwrap(), 400);
+        assert_eq!(bump(inner(100, 1, 0), Some(1_000)).unwrap(), 1_000);
+        assert!(matches!(
+            bump(inner(100, 1, 0), Some(199)),
+            Err(Error::FeeTooLow {
+                fee: 199,
+                minimum: 200
+            })
+        ));
+    }
+
+    #[test]
+    fn minimum_fee_follows_the_base_fee() {
+        let TransactionEnvelope::Tx(env) = inner(100, 1, 0) else {
+            panic!("expected a v1 envelope");
+        };
+        assert_eq!(minimum_fee(&env.tx, 100), 200);
+        assert_eq!(minimum_fee(&env.tx, 500), 1_000);
+    }
+
+    #[test]
+    fn minimum_fee_keeps_the_inner_rate() {
+        // 1000 stroops for 2 operations is a rate of 500, so 3 operations need 1500
+        assert_eq!(bump(inner(1_000, 2, 0), None).unwrap(), 1_500);
+        // The rate is rounded up
+        assert_eq!(bump(inner(1_001, 2, 0), None).unwrap(), 1_502);
+    }
+
+    #[test]
+    fn minimum_fee_includes_the_resource_fee() {
+        assert_eq!(bump(inner(50_100, 1, 50_000), None).unwrap(), 50_200);
+        assert_eq!(bump(inner(51_000, 1, 50_000), None).unwrap(), 52_000);
+    }
+
+    #[test]
+    fn only_signed_v1_transactions_are_wrapped() {
+        let fee_bump = fee_bump(
+            inner(100, 1, 0),
+            MuxedAccount::Ed25519(Uint256([2; 32])),
+            None,
+            100,
+        )
+        .unwrap();
+        assert!(matches!(bump(fee_bump, None), Err(Error::AlreadyFeeBump)));
+        let TransactionEnvelope::Tx(mut unsigned) = inner(100, 1, 0) else {
+            panic!("expected a v1 envelope");
+        };
+        unsigned.signatures = VecM::default();
+        assert!(matches!(
+            bump(TransactionEnvelope::Tx(unsigned), None),
+            Err(Error::UnsignedInner)
+        ));
+    }
+}
@@ -1,7 +1,7 @@
 use hex;
 use std::ffi::OsString;
 
-use crate::{commands::global, config::network, utils::transaction_hash};
+use crate::{commands::global, config::network};
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -28,11 +28,14 @@ pub struct Cmd {
 
 impl Cmd {
     pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
-        let tx = super::xdr::unwrap_envelope_v1(super::xdr::tx_envelope_from_input(&self.tx_xdr)?)?;
+        let tx_env = super::xdr::tx_envelope_from_input(&self.tx_xdr)?;
         let network = &self.network.get(&global_args.locator)?;
         println!(
             "{}",
-            hex::encode(transaction_hash(&tx, &network.network_passphrase)?)
+            hex::encode(super::xdr::envelope_hash(
+                &tx_env,
+                &network.network_passphrase
+            )?)
         );
         Ok(())
     }
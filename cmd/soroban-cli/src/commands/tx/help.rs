@@ -24,3 +24,9 @@ pub const BEGIN_SPONSORING_FUTURE_RESERVES: &str =
     "Begin sponsoring future reserves for another account";
 pub const END_SPONSORING_FUTURE_RESERVES: &str = "End sponsoring future reserves";
 pub const REVOKE_SPONSORSHIP: &str = "Revoke sponsorship of a ledger entry or signer";
+pub const EXTEND_FOOTPRINT_TTL: &str =
+    "Extend the time to live of contract data, contract instances or Wasm code";
+pub const INVOKE_HOST_FUNCTION: &str =
+    "Invoke a contract function, with arguments parsed using the contract's spec";
+pub const RESTORE_FOOTPRINT: &str =
+    "Restore archived contract data, contract instances or Wasm code";
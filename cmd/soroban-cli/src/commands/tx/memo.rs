@@ -0,0 +1,104 @@
+use crate::xdr::{self, Hash, Memo};
+
+/// Longest text memo, in bytes.
+const MAX_TEXT_LEN: usize = 28;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("memo text is {len} bytes long, it can be at most {MAX_TEXT_LEN} bytes")]
+    TextTooLong { len: usize },
+    #[error("invalid memo {name} {hash:?}, expected 32 bytes, hex-encoded")]
+    InvalidHash { name: &'static str, hash: String },
+}
+
+/// Memo of a transaction. Only one of the memo options can be given.
+#[derive(Debug, clap::Args, Clone, Default)]
+#[group(skip)]
+pub struct Args {
+    /// Text memo of up to 28 bytes, such as the reference exchanges and custodians ask for to credit a deposit
+    #[arg(long, conflicts_with_all = ["memo_id", "memo_hash", "memo_return"])]
+    pub memo_text: Option<String>,
+    /// ID memo, an unsigned 64-bit integer
+    #[arg(long, conflicts_with_all = ["memo_hash", "memo_return"])]
+    pub memo_id: Option<u64>,
+    /// Hash memo, 32 bytes, hex-encoded
+    #[arg(long, conflicts_with = "memo_return")]
+    pub memo_hash: Option<String>,
+    /// Return memo, the hash, hex-encoded, of the transaction this one refunds
+    #[arg(long)]
+    pub memo_return: Option<String>,
+}
+
+impl Args {
+    /// Whether a memo was given.
+    pub fn is_set(&self) -> bool {
+        self.memo_text.is_some()
+            || self.memo_id.is_some()
+            || self.memo_hash.is_some()
+            || self.memo_return.is_some()
+    }
+
+    /// The given memo, or `Memo::None` if there is none.
+    pub fn memo(&self) -> Result<Memo, Error> {
+        if let Some(text) = &self.memo_text {
+            let text = xdr::StringM::<28>::try_from(text.as_bytes())
+                .map_err(|_| Error::TextTooLong { len: text.len() })?;
+            return Ok(Memo::Text(text));
+        }
+        if let Some(id) = self.memo_id {
+            return Ok(Memo::Id(id));
+        }
+        if let Some(hash) = &self.memo_hash {
+            return Ok(Memo::Hash(parse_hash("hash", hash)?));
+        }
+        if let Some(hash) = &self.memo_return {
+            return Ok(Memo::Return(parse_hash("return", hash)?));
+        }
+        Ok(Memo::None)
+    }
+}
+
+fn parse_hash(name: &'static str, hash: &str) -> Result<Hash, Error> {
+    hash.parse().map_err(|_| Error::InvalidHash {
+        name,
+        hash: hash.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memo() {
+        assert_eq!(Args::default().memo().unwrap(), Memo::None);
+        let args = Args {
+            memo_text: Some("a".repeat(MAX_TEXT_LEN)),
+            ..Args::default()
+        };
+        assert!(matches!(args.memo().unwrap(), Memo::Text(text) if text.len() == MAX_TEXT_LEN));
+        let args = Args {
+            memo_text: Some("a".repeat(MAX_TEXT_LEN + 1)),
+            ..Args::default()
+        };
+        assert!(matches!(args.memo(), Err(Error::TextTooLong { len: 29 })));
+        let args = Args {
+            memo_id: Some(u64::MAX),
+            ..Args::default()
+        };
+        assert_eq!(args.memo().unwrap(), Memo::Id(u64::MAX));
+        let args = Args {
+            memo_return: Some("ab".repeat(32)),
+            ..Args::default()
+        };
+        assert_eq!(args.memo().unwrap(), Memo::Return(Hash([0xab; 32])));
+        let args = Args {
+            memo_hash: Some("ab".repeat(31)),
+            ..Args::default()
+        };
+        assert!(matches!(
+            args.memo(),
+            Err(Error::InvalidHash { name: "hash", .. })
+        ));
+    }
+}
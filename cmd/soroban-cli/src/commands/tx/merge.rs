@@ -0,0 +1,151 @@
+use std::ffi::OsString;
+
+use crate::{
+    commands::global,
+    config::{locator, network},
+    print::Print,
+    tx::signatures,
+    utils::transaction_hash,
+    xdr::{
+        self, DecoratedSignature, Limits, Transaction, TransactionEnvelope, TransactionV1Envelope,
+        WriteXdr,
+    },
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    XdrArgs(#[from] super::xdr::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error("envelope {index} is for transaction {found}, expected {expected}")]
+    DifferentTransactions {
+        index: usize,
+        expected: String,
+        found: String,
+    },
+    #[error("envelope {index} has an invalid signature from {address}")]
+    InvalidSignature { index: usize, address: String },
+    #[error("envelope {index} has a signature with hint {hint} from an unknown key, pass the key with `--signer` or use `--allow-unknown`")]
+    UnknownSigner { index: usize, hint: String },
+    #[error("no transaction envelopes to merge")]
+    NoEnvelopes,
+    #[error("too many signatures, limited to 20 signatures in a transaction envelope")]
+    TooManySignatures,
+}
+
+/// e.g. `stellar tx merge alice.xdr bob.xdr | stellar tx send`
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Base-64 transaction envelope XDRs, or files containing XDR, of the same transaction
+    #[arg(required = true, num_args = 2..)]
+    pub tx_xdrs: Vec<OsString>,
+    /// Public key or identity to verify signatures against, can be passed multiple times. The source accounts of the transaction and its operations are always included
+    #[arg(long = "signer", value_name = "KEY")]
+    pub signers: Vec<String>,
+    /// Keep signatures whose key is unknown instead of failing, they are not verified
+    #[arg(long)]
+    pub allow_unknown: bool,
+    #[command(flatten)]
+    pub network: network::Args,
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let network = self.network.get(&self.locator)?;
+        let envelopes = self
+            .tx_xdrs
+            .iter()
+            .map(|input| super::xdr::tx_envelope_from_input(&Some(input.clone())))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut keys = Vec::new();
+        for key in &self.signers {
+            let account_id = self.locator.get_public_key(key, None)?.account_id();
+            keys.push(signatures::account_key(&account_id));
+        }
+        let merged = merge(
+            envelopes,
+            &keys,
+            &network.network_passphrase,
+            self.allow_unknown,
+            &print,
+        )?;
+        println!("{}", merged.to_xdr_base64(Limits::none())?);
+        Ok(())
+    }
+}
+
+/// Combine the signatures of envelopes of the same transaction, checking each signature against
+/// `keys` and the transaction's source accounts, and keeping one signature per hint.
+pub fn merge(
+    envelopes: Vec<TransactionEnvelope>,
+    keys: &[[u8; 32]],
+    network_passphrase: &str,
+    allow_unknown: bool,
+    print: &Print,
+) -> Result<TransactionEnvelope, Error> {
+    let mut expected: Option<(Transaction, [u8; 32])> = None;
+    let mut merged: Vec<DecoratedSignature> = Vec::new();
+    let mut keys = keys.to_vec();
+    for (index, envelope) in envelopes.into_iter().enumerate() {
+        let TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx,
+            signatures: sigs,
+        }) = envelope
+        else {
+            return Err(super::xdr::Error::OnlyTransactionV1Supported.into());
+        };
+        let hash = transaction_hash(&tx, network_passphrase)?;
+        match &expected {
+            None => {
+                for account_id in signatures::source_accounts(&tx) {
+                    keys.push(signatures::account_key(&account_id));
+                }
+                expected = Some((tx, hash));
+            }
+            Some((_, expected_hash)) if *expected_hash != hash => {
+                return Err(Error::DifferentTransactions {
+                    index,
+                    expected: hex::encode(expected_hash),
+                    found: hex::encode(hash),
+                });
+            }
+            Some(_) => {}
+        }
+        for signature in sigs.iter() {
+            if signatures::signer_of(&keys, &hash, signature).is_none() {
+                if let Some(key) = keys.iter().find(|k| signatures::hint(k) == signature.hint) {
+                    return Err(Error::InvalidSignature {
+                        index,
+                        address: stellar_strkey::ed25519::PublicKey(*key).to_string(),
+                    });
+                }
+                let hint = hex::encode(signature.hint.0);
+                if !allow_unknown {
+                    return Err(Error::UnknownSigner { index, hint });
+                }
+                print.warnln(format!(
+                    "Keeping unverified signature with hint {hint} from envelope {index}"
+                ));
+            }
+            if !merged.iter().any(|s| s.hint == signature.hint) {
+                merged.push(signature.clone());
+            }
+        }
+    }
+    let Some((tx, _)) = expected else {
+        return Err(Error::NoEnvelopes);
+    };
+    Ok(TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx,
+        signatures: merged.try_into().map_err(|_| Error::TooManySignatures)?,
+    }))
+}
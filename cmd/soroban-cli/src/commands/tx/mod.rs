@@ -1,19 +1,31 @@
 use super::global;
+use crate::{
+    print::Print,
+    xdr::{Limits, TransactionEnvelope, WriteXdr},
+};
 
 pub mod args;
 
 pub mod decode;
+pub mod diff;
 pub mod edit;
 pub mod encode;
+pub mod explain;
+pub mod fee_bump;
 pub mod fetch;
 pub mod hash;
 pub mod help;
+pub mod memo;
+pub mod merge;
 pub mod new;
 pub mod op;
+pub mod preconditions;
 pub mod send;
+pub mod sequence;
 pub mod sign;
 pub mod simulate;
 pub mod update;
+pub mod verify;
 pub mod xdr;
 
 pub use args::Args;
@@ -35,8 +47,18 @@ pub enum Cmd {
     /// $ stellar tx new manage-data --data-name hello --build-only | stellar tx edit
     ///
     Edit(edit::Cmd),
+    /// Compare two transaction envelopes section by section, to check that only the signatures
+    /// changed. Fails if the transactions have different signing hashes
+    Diff(diff::Cmd),
+    /// Explain a transaction envelope in plain English, with an operation per line, including
+    /// the contract calls it makes and the authorization entries it carries
+    Explain(explain::Cmd),
+    /// Wrap a signed transaction envelope in a fee bump transaction paid by another account
+    FeeBump(fee_bump::Cmd),
     /// Calculate the hash of a transaction envelope
     Hash(hash::Cmd),
+    /// Merge the signatures of several envelopes of the same transaction into one envelope
+    Merge(merge::Cmd),
     /// Create a new transaction
     #[command(subcommand)]
     New(new::Cmd),
@@ -49,6 +71,8 @@ pub enum Cmd {
     Sign(sign::Cmd),
     /// Simulate a transaction envelope from stdin
     Simulate(simulate::Cmd),
+    /// Check the signatures of a transaction envelope, including fee bump and Soroban authorization entry signatures
+    Verify(verify::Cmd),
     /// Fetch a transaction from the network by hash
     /// If no subcommand is passed in, the transaction envelope will be returned
     Fetch(fetch::Cmd),
@@ -58,13 +82,21 @@ pub enum Cmd {
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
+    #[error(transparent)]
+    FeeBump(#[from] fee_bump::Error),
     #[error(transparent)]
     Hash(#[from] hash::Error),
     #[error(transparent)]
+    Merge(#[from] merge::Error),
+    #[error(transparent)]
     New(#[from] new::Error),
     #[error(transparent)]
     Edit(#[from] edit::Error),
     #[error(transparent)]
+    Diff(#[from] diff::Error),
+    #[error(transparent)]
+    Explain(#[from] explain::Error),
+    #[error(transparent)]
     Op(#[from] op::Error),
     #[error(transparent)]
     Send(#[from] send::Error),
@@ -77,6 +109,8 @@ pub enum Error {
     #[error(transparent)]
     Update(#[from] update::Error),
     #[error(transparent)]
+    Verify(#[from] verify::Error),
+    #[error(transparent)]
     Fetch(#[from] fetch::Error),
     #[error(transparent)]
     Decode(#[from] decode::Error),
@@ -87,14 +121,19 @@ pub enum Error {
 impl Cmd {
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         match self {
+            Cmd::FeeBump(cmd) => cmd.run(global_args).await?,
             Cmd::Hash(cmd) => cmd.run(global_args)?,
+            Cmd::Merge(cmd) => cmd.run(global_args)?,
             Cmd::New(cmd) => cmd.run(global_args).await?,
             Cmd::Edit(cmd) => cmd.run(global_args)?,
+            Cmd::Diff(cmd) => cmd.run(global_args)?,
+            Cmd::Explain(cmd) => cmd.run(global_args).await?,
             Cmd::Operation(cmd) => cmd.run(global_args).await?,
             Cmd::Send(cmd) => cmd.run(global_args).await?,
             Cmd::Sign(cmd) => cmd.run(global_args).await?,
             Cmd::Simulate(cmd) => cmd.run(global_args).await?,
             Cmd::Update(cmd) => cmd.run(global_args).await?,
+            Cmd::Verify(cmd) => cmd.run(global_args)?,
             Cmd::Fetch(cmd) => cmd.run(global_args).await?,
             Cmd::Decode(cmd) => cmd.run()?,
             Cmd::Encode(cmd) => cmd.run()?,
@@ -102,3 +141,19 @@ impl Cmd {
         Ok(())
     }
 }
+
+/// Write an edited envelope to stdout. Editing the transaction invalidates the signatures it
+/// had, which are dropped, so warn about them.
+fn print_edited(
+    print: &Print,
+    signatures: usize,
+    tx_env: &TransactionEnvelope,
+) -> Result<(), crate::xdr::Error> {
+    if signatures > 0 {
+        print.warnln(format!(
+            "The transaction changed, its {signatures} signature(s) were removed and must be added again"
+        ));
+    }
+    println!("{}", tx_env.to_xdr_base64(Limits::none())?);
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use clap::Parser;
+
+use crate::{
+    commands::tx::{self, args::SorobanOp},
+    config, key, xdr,
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub tx: tx::Args,
+    #[clap(flatten)]
+    pub op: Args,
+}
+
+#[derive(Debug, clap::Args, Clone)]
+pub struct Args {
+    /// Number of ledgers to extend the entries
+    #[arg(long)]
+    pub ledgers_to_extend: u32,
+    #[command(flatten)]
+    pub key: key::Args,
+}
+
+impl Args {
+    /// The entries to extend go in the read-only footprint.
+    pub fn soroban_op(&self, config: &config::Args) -> Result<SorobanOp, tx::args::Error> {
+        let keys = self
+            .key
+            .parse_keys(&config.locator, &config.get_network()?)?;
+        Ok(SorobanOp {
+            body: self.into(),
+            footprint: xdr::LedgerFootprint {
+                read_only: keys.try_into()?,
+                read_write: xdr::VecM::default(),
+            },
+            auth_signers: Vec::new(),
+            function: None,
+        })
+    }
+}
+
+impl From<&Args> for xdr::OperationBody {
+    fn from(args: &Args) -> Self {
+        xdr::OperationBody::ExtendFootprintTtl(xdr::ExtendFootprintTtlOp {
+            ext: xdr::ExtensionPoint::V0,
+            extend_to: args.ledgers_to_extend,
+        })
+    }
+}
@@ -0,0 +1,66 @@
+use std::ffi::OsString;
+
+use clap::Parser;
+
+use crate::{
+    commands::{
+        contract::arg_parsing::build_host_function_parameters,
+        global,
+        tx::{self, args::SorobanOp},
+    },
+    config,
+    get_spec::get_remote_contract_spec,
+    xdr,
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub tx: tx::Args,
+    #[clap(flatten)]
+    pub op: Args,
+}
+
+#[derive(Debug, clap::Args, Clone)]
+pub struct Args {
+    /// Contract ID to invoke
+    #[arg(long = "id", env = "STELLAR_CONTRACT_ID")]
+    pub contract_id: config::UnresolvedContract,
+    /// Function name as subcommand, then arguments for that function as `--arg-name value`
+    #[arg(last = true, id = "CONTRACT_FN_AND_ARGS")]
+    pub slop: Vec<OsString>,
+}
+
+impl Args {
+    /// Parse the function and its arguments with the spec of the deployed contract.
+    pub async fn soroban_op(
+        &self,
+        config: &config::Args,
+        global_args: &global::Args,
+    ) -> Result<SorobanOp, tx::args::Error> {
+        let network = config.get_network()?;
+        let contract_id = self
+            .contract_id
+            .resolve_contract_id(&config.locator, &network.network_passphrase)?;
+        let spec_entries = get_remote_contract_spec(
+            &contract_id.0,
+            &config.locator,
+            &config.network,
+            Some(global_args),
+            Some(config),
+        )
+        .await?;
+        let (function, spec, invoke_args, auth_signers) =
+            build_host_function_parameters(&contract_id, &self.slop, &spec_entries, config).await?;
+        Ok(SorobanOp {
+            body: xdr::OperationBody::InvokeHostFunction(xdr::InvokeHostFunctionOp {
+                host_function: xdr::HostFunction::InvokeContract(invoke_args),
+                auth: xdr::VecM::default(),
+            }),
+            footprint: xdr::LedgerFootprint::default(),
+            auth_signers,
+            function: Some((spec, function)),
+        })
+    }
+}
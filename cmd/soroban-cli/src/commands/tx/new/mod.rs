@@ -1,7 +1,6 @@
 use clap::Parser;
-use soroban_sdk::xdr::OperationBody;
 
-use super::global;
+use super::{args::Op, global};
 
 pub mod account_merge;
 pub mod begin_sponsoring_future_reserves;
@@ -14,6 +13,8 @@ pub mod create_account;
 pub mod create_claimable_balance;
 pub mod create_passive_sell_offer;
 pub mod end_sponsoring_future_reserves;
+pub mod extend_footprint_ttl;
+pub mod invoke_host_function;
 pub mod liquidity_pool_deposit;
 pub mod liquidity_pool_withdraw;
 pub mod manage_buy_offer;
@@ -22,6 +23,7 @@ pub mod manage_sell_offer;
 pub mod path_payment_strict_receive;
 pub mod path_payment_strict_send;
 pub mod payment;
+pub mod restore_footprint;
 pub mod revoke_sponsorship;
 pub mod set_options;
 pub mod set_trustline_flags;
@@ -51,6 +53,10 @@ pub enum Cmd {
     CreatePassiveSellOffer(create_passive_sell_offer::Cmd),
     #[command(about = super::help::END_SPONSORING_FUTURE_RESERVES)]
     EndSponsoringFutureReserves(end_sponsoring_future_reserves::Cmd),
+    #[command(about = super::help::EXTEND_FOOTPRINT_TTL)]
+    ExtendFootprintTtl(extend_footprint_ttl::Cmd),
+    #[command(about = super::help::INVOKE_HOST_FUNCTION)]
+    InvokeHostFunction(invoke_host_function::Cmd),
     #[command(about = super::help::LIQUIDITY_POOL_DEPOSIT)]
     LiquidityPoolDeposit(liquidity_pool_deposit::Cmd),
     #[command(about = super::help::LIQUIDITY_POOL_WITHDRAW)]
@@ -67,6 +73,8 @@ pub enum Cmd {
     PathPaymentStrictReceive(path_payment_strict_receive::Cmd),
     #[command(about = super::help::PAYMENT)]
     Payment(payment::Cmd),
+    #[command(about = super::help::RESTORE_FOOTPRINT)]
+    RestoreFootprint(restore_footprint::Cmd),
     #[command(about = super::help::REVOKE_SPONSORSHIP)]
     RevokeSponsorship(revoke_sponsorship::Cmd),
     #[command(about = super::help::SET_OPTIONS)]
@@ -81,65 +89,77 @@ pub enum Error {
     Tx(#[from] super::args::Error),
 }
 
-impl TryFrom<&Cmd> for OperationBody {
-    type Error = super::args::Error;
-    fn try_from(cmd: &Cmd) -> Result<Self, Self::Error> {
-        Ok(match cmd {
-            Cmd::AccountMerge(cmd) => cmd.try_into()?,
-            Cmd::BeginSponsoringFutureReserves(cmd) => cmd.try_into()?,
-            Cmd::BumpSequence(cmd) => cmd.into(),
-            Cmd::ChangeTrust(cmd) => cmd.try_into()?,
-            Cmd::ClaimClaimableBalance(cmd) => cmd.try_into()?,
-            Cmd::Clawback(cmd) => cmd.try_into()?,
-            Cmd::ClawbackClaimableBalance(cmd) => cmd.try_into()?,
-            Cmd::CreateAccount(cmd) => cmd.try_into()?,
-            Cmd::CreateClaimableBalance(cmd) => cmd.try_into()?,
-            Cmd::CreatePassiveSellOffer(cmd) => cmd.try_into()?,
-            Cmd::EndSponsoringFutureReserves(cmd) => cmd.into(),
-            Cmd::LiquidityPoolDeposit(cmd) => cmd.try_into()?,
-            Cmd::LiquidityPoolWithdraw(cmd) => cmd.try_into()?,
-            Cmd::ManageBuyOffer(cmd) => cmd.try_into()?,
-            Cmd::ManageData(cmd) => cmd.into(),
-            Cmd::ManageSellOffer(cmd) => cmd.try_into()?,
-            Cmd::PathPaymentStrictSend(cmd) => cmd.try_into()?,
-            Cmd::PathPaymentStrictReceive(cmd) => cmd.try_into()?,
-            Cmd::Payment(cmd) => cmd.try_into()?,
-            Cmd::RevokeSponsorship(cmd) => cmd.try_into()?,
-            Cmd::SetOptions(cmd) => cmd.try_into()?,
-            Cmd::SetTrustlineFlags(cmd) => cmd.try_into()?,
-        })
-    }
-}
-
 impl Cmd {
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
-        let op = OperationBody::try_from(self)?;
+        let tx = self.tx();
+        match self.op(global_args).await? {
+            Op::Classic(op) => tx.handle_and_print(op, global_args).await?,
+            Op::Soroban(op) => tx.handle_soroban_and_print(op, global_args).await?,
+        }
+        Ok(())
+    }
+
+    fn tx(&self) -> &super::Args {
         match self {
-            Cmd::AccountMerge(cmd) => cmd.tx.handle_and_print(op, global_args).await,
-            Cmd::BeginSponsoringFutureReserves(cmd) => {
-                cmd.tx.handle_and_print(op, global_args).await
+            Cmd::AccountMerge(cmd) => &cmd.tx,
+            Cmd::BeginSponsoringFutureReserves(cmd) => &cmd.tx,
+            Cmd::BumpSequence(cmd) => &cmd.tx,
+            Cmd::ChangeTrust(cmd) => &cmd.tx,
+            Cmd::ClaimClaimableBalance(cmd) => &cmd.tx,
+            Cmd::Clawback(cmd) => &cmd.tx,
+            Cmd::ClawbackClaimableBalance(cmd) => &cmd.tx,
+            Cmd::CreateAccount(cmd) => &cmd.tx,
+            Cmd::CreateClaimableBalance(cmd) => &cmd.tx,
+            Cmd::CreatePassiveSellOffer(cmd) => &cmd.tx,
+            Cmd::EndSponsoringFutureReserves(cmd) => &cmd.tx,
+            Cmd::ExtendFootprintTtl(cmd) => &cmd.tx,
+            Cmd::InvokeHostFunction(cmd) => &cmd.tx,
+            Cmd::LiquidityPoolDeposit(cmd) => &cmd.tx,
+            Cmd::LiquidityPoolWithdraw(cmd) => &cmd.tx,
+            Cmd::ManageBuyOffer(cmd) => &cmd.tx,
+            Cmd::ManageData(cmd) => &cmd.tx,
+            Cmd::ManageSellOffer(cmd) => &cmd.tx,
+            Cmd::PathPaymentStrictSend(cmd) => &cmd.tx,
+            Cmd::PathPaymentStrictReceive(cmd) => &cmd.tx,
+            Cmd::Payment(cmd) => &cmd.tx,
+            Cmd::RestoreFootprint(cmd) => &cmd.tx,
+            Cmd::RevokeSponsorship(cmd) => &cmd.tx,
+            Cmd::SetOptions(cmd) => &cmd.tx,
+            Cmd::SetTrustlineFlags(cmd) => &cmd.tx,
+        }
+    }
+
+    /// Soroban operations need their footprint, and invocations the contract's spec, so
+    /// operations are built asynchronously.
+    async fn op(&self, global_args: &global::Args) -> Result<Op, Error> {
+        Ok(match self {
+            Cmd::AccountMerge(cmd) => Op::Classic(cmd.try_into()?),
+            Cmd::BeginSponsoringFutureReserves(cmd) => Op::Classic(cmd.try_into()?),
+            Cmd::BumpSequence(cmd) => Op::Classic(cmd.into()),
+            Cmd::ChangeTrust(cmd) => Op::Classic(cmd.try_into()?),
+            Cmd::ClaimClaimableBalance(cmd) => Op::Classic(cmd.try_into()?),
+            Cmd::Clawback(cmd) => Op::Classic(cmd.try_into()?),
+            Cmd::ClawbackClaimableBalance(cmd) => Op::Classic(cmd.try_into()?),
+            Cmd::CreateAccount(cmd) => Op::Classic(cmd.try_into()?),
+            Cmd::CreateClaimableBalance(cmd) => Op::Classic(cmd.try_into()?),
+            Cmd::CreatePassiveSellOffer(cmd) => Op::Classic(cmd.try_into()?),
+            Cmd::EndSponsoringFutureReserves(cmd) => Op::Classic(cmd.into()),
+            Cmd::ExtendFootprintTtl(cmd) => Op::Soroban(cmd.op.soroban_op(&cmd.tx.config)?),
+            Cmd::InvokeHostFunction(cmd) => {
+                Op::Soroban(cmd.op.soroban_op(&cmd.tx.config, global_args).await?)
             }
-            Cmd::BumpSequence(cmd) => cmd.tx.handle_and_print(op, global_args).await,
-            Cmd::ChangeTrust(cmd) => cmd.tx.handle_and_print(op, global_args).await,
-            Cmd::ClaimClaimableBalance(cmd) => cmd.tx.handle_and_print(op, global_args).await,
-            Cmd::Clawback(cmd) => cmd.tx.handle_and_print(op, global_args).await,
-            Cmd::ClawbackClaimableBalance(cmd) => cmd.tx.handle_and_print(op, global_args).await,
-            Cmd::CreateAccount(cmd) => cmd.tx.handle_and_print(op, global_args).await,
-            Cmd::CreateClaimableBalance(cmd) => cmd.tx.handle_and_print(op, global_args).await,
-            Cmd::CreatePassiveSellOffer(cmd) => cmd.tx.handle_and_print(op, global_args).await,
-            Cmd::EndSponsoringFutureReserves(cmd) => cmd.tx.handle_and_print(op, global_args).await,
-            Cmd::LiquidityPoolDeposit(cmd) => cmd.tx.handle_and_print(op, global_args).await,
-            Cmd::LiquidityPoolWithdraw(cmd) => cmd.tx.handle_and_print(op, global_args).await,
-            Cmd::ManageBuyOffer(cmd) => cmd.tx.handle_and_print(op, global_args).await,
-            Cmd::ManageData(cmd) => cmd.tx.handle_and_print(op, global_args).await,
-            Cmd::ManageSellOffer(cmd) => cmd.tx.handle_and_print(op, global_args).await,
-            Cmd::PathPaymentStrictSend(cmd) => cmd.tx.handle_and_print(op, global_args).await,
-            Cmd::PathPaymentStrictReceive(cmd) => cmd.tx.handle_and_print(op, global_args).await,
-            Cmd::Payment(cmd) => cmd.tx.handle_and_print(op, global_args).await,
-            Cmd::RevokeSponsorship(cmd) => cmd.tx.handle_and_print(op, global_args).await,
-            Cmd::SetOptions(cmd) => cmd.tx.handle_and_print(op, global_args).await,
-            Cmd::SetTrustlineFlags(cmd) => cmd.tx.handle_and_print(op, global_args).await,
-        }?;
-        Ok(())
+            Cmd::LiquidityPoolDeposit(cmd) => Op::Classic(cmd.try_into()?),
+            Cmd::LiquidityPoolWithdraw(cmd) => Op::Classic(cmd.try_into()?),
+            Cmd::ManageBuyOffer(cmd) => Op::Classic(cmd.try_into()?),
+            Cmd::ManageData(cmd) => Op::Classic(cmd.into()),
+            Cmd::ManageSellOffer(cmd) => Op::Classic(cmd.try_into()?),
+            Cmd::PathPaymentStrictSend(cmd) => Op::Classic(cmd.try_into()?),
+            Cmd::PathPaymentStrictReceive(cmd) => Op::Classic(cmd.try_into()?),
+            Cmd::Payment(cmd) => Op::Classic(cmd.try_into()?),
+            Cmd::RestoreFootprint(cmd) => Op::Soroban(cmd.op.soroban_op(&cmd.tx.config)?),
+            Cmd::RevokeSponsorship(cmd) => Op::Classic(cmd.try_into()?),
+            Cmd::SetOptions(cmd) => Op::Classic(cmd.try_into()?),
+            Cmd::SetTrustlineFlags(cmd) => Op::Classic(cmd.try_into()?),
+        })
     }
 }
@@ -0,0 +1,47 @@
+use clap::Parser;
+
+use crate::{
+    commands::tx::{self, args::SorobanOp},
+    config, key, xdr,
+};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub tx: tx::Args,
+    #[clap(flatten)]
+    pub op: Args,
+}
+
+#[derive(Debug, clap::Args, Clone)]
+pub struct Args {
+    #[command(flatten)]
+    pub key: key::Args,
+}
+
+impl Args {
+    /// The entries to restore go in the read-write footprint.
+    pub fn soroban_op(&self, config: &config::Args) -> Result<SorobanOp, tx::args::Error> {
+        let keys = self
+            .key
+            .parse_keys(&config.locator, &config.get_network()?)?;
+        Ok(SorobanOp {
+            body: self.into(),
+            footprint: xdr::LedgerFootprint {
+                read_only: xdr::VecM::default(),
+                read_write: keys.try_into()?,
+            },
+            auth_signers: Vec::new(),
+            function: None,
+        })
+    }
+}
+
+impl From<&Args> for xdr::OperationBody {
+    fn from(_: &Args) -> Self {
+        xdr::OperationBody::RestoreFootprint(xdr::RestoreFootprintOp {
+            ext: xdr::ExtensionPoint::V0,
+        })
+    }
+}
@@ -1,5 +1,5 @@
-use super::super::{global, help, xdr::tx_envelope_from_input};
-use crate::xdr::{OperationBody, WriteXdr};
+use super::super::{args::Op, global, help, xdr::tx_envelope_from_input};
+use crate::xdr::WriteXdr;
 
 pub(crate) use super::super::new;
 
@@ -15,6 +15,8 @@ mod create_account;
 mod create_claimable_balance;
 mod create_passive_sell_offer;
 mod end_sponsoring_future_reserves;
+mod extend_footprint_ttl;
+mod invoke_host_function;
 mod liquidity_pool_deposit;
 mod liquidity_pool_withdraw;
 mod manage_buy_offer;
@@ -23,6 +25,7 @@ mod manage_sell_offer;
 mod path_payment_strict_receive;
 mod path_payment_strict_send;
 mod payment;
+mod restore_footprint;
 mod revoke_sponsorship;
 mod set_options;
 mod set_trustline_flags;
@@ -52,6 +55,10 @@ pub enum Cmd {
     CreatePassiveSellOffer(create_passive_sell_offer::Cmd),
     #[command(about = help::END_SPONSORING_FUTURE_RESERVES)]
     EndSponsoringFutureReserves(end_sponsoring_future_reserves::Cmd),
+    #[command(about = help::EXTEND_FOOTPRINT_TTL)]
+    ExtendFootprintTtl(extend_footprint_ttl::Cmd),
+    #[command(about = help::INVOKE_HOST_FUNCTION)]
+    InvokeHostFunction(invoke_host_function::Cmd),
     #[command(about = help::LIQUIDITY_POOL_DEPOSIT)]
     LiquidityPoolDeposit(liquidity_pool_deposit::Cmd),
     #[command(about = help::LIQUIDITY_POOL_WITHDRAW)]
@@ -68,6 +75,8 @@ pub enum Cmd {
     PathPaymentStrictSend(path_payment_strict_send::Cmd),
     #[command(about = help::PAYMENT)]
     Payment(payment::Cmd),
+    #[command(about = help::RESTORE_FOOTPRINT)]
+    RestoreFootprint(restore_footprint::Cmd),
     #[command(about = help::REVOKE_SPONSORSHIP)]
     RevokeSponsorship(revoke_sponsorship::Cmd),
     #[command(about = help::SET_OPTIONS)]
@@ -88,169 +97,81 @@ pub enum Error {
     Tx(#[from] super::super::args::Error),
 }
 
-impl TryFrom<&Cmd> for OperationBody {
-    type Error = super::super::new::Error;
-    fn try_from(cmd: &Cmd) -> Result<Self, Self::Error> {
-        Ok(match &cmd {
-            Cmd::AccountMerge(account_merge::Cmd { op, .. }) => op.try_into()?,
-            Cmd::BeginSponsoringFutureReserves(begin_sponsoring_future_reserves::Cmd {
-                op,
-                ..
-            }) => op.try_into()?,
-            Cmd::BumpSequence(bump_sequence::Cmd { op, .. }) => op.into(),
-            Cmd::ChangeTrust(change_trust::Cmd { op, .. }) => op.try_into()?,
-            Cmd::ClaimClaimableBalance(claim_claimable_balance::Cmd { op, .. }) => op.try_into()?,
-            Cmd::Clawback(clawback::Cmd { op, .. }) => op.try_into()?,
-            Cmd::ClawbackClaimableBalance(clawback_claimable_balance::Cmd { op, .. }) => {
-                op.try_into()?
-            }
-            Cmd::CreateAccount(create_account::Cmd { op, .. }) => op.try_into()?,
-            Cmd::CreateClaimableBalance(create_claimable_balance::Cmd { op, .. }) => {
-                op.try_into()?
-            }
-            Cmd::CreatePassiveSellOffer(create_passive_sell_offer::Cmd { op, .. }) => {
-                op.try_into()?
-            }
-            Cmd::EndSponsoringFutureReserves(end_sponsoring_future_reserves::Cmd {
-                op, ..
-            }) => op.into(),
-            Cmd::LiquidityPoolDeposit(liquidity_pool_deposit::Cmd { op, .. }) => op.try_into()?,
-            Cmd::LiquidityPoolWithdraw(liquidity_pool_withdraw::Cmd { op, .. }) => op.try_into()?,
-            Cmd::ManageBuyOffer(manage_buy_offer::Cmd { op, .. }) => op.try_into()?,
-            Cmd::ManageData(manage_data::Cmd { op, .. }) => op.into(),
-            Cmd::ManageSellOffer(manage_sell_offer::Cmd { op, .. }) => op.try_into()?,
-            Cmd::PathPaymentStrictReceive(path_payment_strict_receive::Cmd { op, .. }) => {
-                op.try_into()?
-            }
-            Cmd::PathPaymentStrictSend(path_payment_strict_send::Cmd { op, .. }) => {
-                op.try_into()?
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let (args, tx) = self.args();
+        let tx_env = tx_envelope_from_input(&args.tx_xdr)?;
+        let res = match self.op(global_args).await? {
+            Op::Classic(op) => tx.add_op(op, tx_env, args.source()).await?,
+            Op::Soroban(op) => {
+                tx.add_soroban_op(op, tx_env, args.source(), global_args.quiet)
+                    .await?
             }
-            Cmd::Payment(payment::Cmd { op, .. }) => op.try_into()?,
-            Cmd::RevokeSponsorship(revoke_sponsorship::Cmd { op, .. }) => op.try_into()?,
-            Cmd::SetOptions(set_options::Cmd { op, .. }) => op.try_into()?,
-            Cmd::SetTrustlineFlags(set_trustline_flags::Cmd { op, .. }) => op.try_into()?,
-        })
+        };
+        println!("{}", res.to_xdr_base64(crate::xdr::Limits::none())?);
+        Ok(())
     }
-}
 
-impl Cmd {
-    #[allow(clippy::too_many_lines)]
-    pub async fn run(&self, _: &global::Args) -> Result<(), Error> {
-        let op = OperationBody::try_from(self)?;
-        let res = match self {
-            Cmd::AccountMerge(cmd) => cmd.op.tx.add_op(
-                op,
-                tx_envelope_from_input(&cmd.args.tx_xdr)?,
-                cmd.args.source(),
-            ),
-            Cmd::BeginSponsoringFutureReserves(cmd) => cmd.op.tx.add_op(
-                op,
-                tx_envelope_from_input(&cmd.args.tx_xdr)?,
-                cmd.args.source(),
-            ),
-            Cmd::BumpSequence(cmd) => cmd.op.tx.add_op(
-                op,
-                tx_envelope_from_input(&cmd.args.tx_xdr)?,
-                cmd.args.source(),
-            ),
-            Cmd::ChangeTrust(cmd) => cmd.op.tx.add_op(
-                op,
-                tx_envelope_from_input(&cmd.args.tx_xdr)?,
-                cmd.args.source(),
-            ),
-            Cmd::ClaimClaimableBalance(cmd) => cmd.op.tx.add_op(
-                op,
-                tx_envelope_from_input(&cmd.args.tx_xdr)?,
-                cmd.args.source(),
-            ),
-            Cmd::Clawback(cmd) => cmd.op.tx.add_op(
-                op,
-                tx_envelope_from_input(&cmd.args.tx_xdr)?,
-                cmd.args.source(),
-            ),
-            Cmd::ClawbackClaimableBalance(cmd) => cmd.op.tx.add_op(
-                op,
-                tx_envelope_from_input(&cmd.args.tx_xdr)?,
-                cmd.args.source(),
-            ),
-            Cmd::CreateAccount(cmd) => cmd.op.tx.add_op(
-                op,
-                tx_envelope_from_input(&cmd.args.tx_xdr)?,
-                cmd.args.source(),
-            ),
-            Cmd::CreateClaimableBalance(cmd) => cmd.op.tx.add_op(
-                op,
-                tx_envelope_from_input(&cmd.args.tx_xdr)?,
-                cmd.args.source(),
-            ),
-            Cmd::CreatePassiveSellOffer(cmd) => cmd.op.tx.add_op(
-                op,
-                tx_envelope_from_input(&cmd.args.tx_xdr)?,
-                cmd.args.source(),
-            ),
-            Cmd::EndSponsoringFutureReserves(cmd) => cmd.op.tx.add_op(
-                op,
-                tx_envelope_from_input(&cmd.args.tx_xdr)?,
-                cmd.args.source(),
-            ),
-            Cmd::LiquidityPoolDeposit(cmd) => cmd.op.tx.add_op(
-                op,
-                tx_envelope_from_input(&cmd.args.tx_xdr)?,
-                cmd.args.source(),
-            ),
-            Cmd::LiquidityPoolWithdraw(cmd) => cmd.op.tx.add_op(
-                op,
-                tx_envelope_from_input(&cmd.args.tx_xdr)?,
-                cmd.args.source(),
-            ),
-            Cmd::ManageBuyOffer(cmd) => cmd.op.tx.add_op(
-                op,
-                tx_envelope_from_input(&cmd.args.tx_xdr)?,
-                cmd.args.source(),
-            ),
-            Cmd::ManageData(cmd) => cmd.op.tx.add_op(
-                op,
-                tx_envelope_from_input(&cmd.args.tx_xdr)?,
-                cmd.args.source(),
-            ),
-            Cmd::ManageSellOffer(cmd) => cmd.op.tx.add_op(
-                op,
-                tx_envelope_from_input(&cmd.args.tx_xdr)?,
-                cmd.args.source(),
-            ),
-            Cmd::PathPaymentStrictReceive(cmd) => cmd.op.tx.add_op(
-                op,
-                tx_envelope_from_input(&cmd.args.tx_xdr)?,
-                cmd.args.source(),
-            ),
-            Cmd::PathPaymentStrictSend(cmd) => cmd.op.tx.add_op(
-                op,
-                tx_envelope_from_input(&cmd.args.tx_xdr)?,
-                cmd.args.source(),
-            ),
-            Cmd::Payment(cmd) => cmd.op.tx.add_op(
-                op,
-                tx_envelope_from_input(&cmd.args.tx_xdr)?,
-                cmd.args.source(),
-            ),
-            Cmd::RevokeSponsorship(cmd) => cmd.op.tx.add_op(
-                op,
-                tx_envelope_from_input(&cmd.args.tx_xdr)?,
-                cmd.args.source(),
-            ),
-            Cmd::SetOptions(cmd) => cmd.op.tx.add_op(
-                op,
-                tx_envelope_from_input(&cmd.args.tx_xdr)?,
-                cmd.args.source(),
-            ),
-            Cmd::SetTrustlineFlags(cmd) => cmd.op.tx.add_op(
-                op,
-                tx_envelope_from_input(&cmd.args.tx_xdr)?,
-                cmd.args.source(),
-            ),
+    fn args(&self) -> (&args::Args, &super::super::Args) {
+        match self {
+            Cmd::AccountMerge(cmd) => (&cmd.args, &cmd.op.tx),
+            Cmd::BeginSponsoringFutureReserves(cmd) => (&cmd.args, &cmd.op.tx),
+            Cmd::BumpSequence(cmd) => (&cmd.args, &cmd.op.tx),
+            Cmd::ChangeTrust(cmd) => (&cmd.args, &cmd.op.tx),
+            Cmd::ClaimClaimableBalance(cmd) => (&cmd.args, &cmd.op.tx),
+            Cmd::Clawback(cmd) => (&cmd.args, &cmd.op.tx),
+            Cmd::ClawbackClaimableBalance(cmd) => (&cmd.args, &cmd.op.tx),
+            Cmd::CreateAccount(cmd) => (&cmd.args, &cmd.op.tx),
+            Cmd::CreateClaimableBalance(cmd) => (&cmd.args, &cmd.op.tx),
+            Cmd::CreatePassiveSellOffer(cmd) => (&cmd.args, &cmd.op.tx),
+            Cmd::EndSponsoringFutureReserves(cmd) => (&cmd.args, &cmd.op.tx),
+            Cmd::ExtendFootprintTtl(cmd) => (&cmd.args, &cmd.op.tx),
+            Cmd::InvokeHostFunction(cmd) => (&cmd.args, &cmd.op.tx),
+            Cmd::LiquidityPoolDeposit(cmd) => (&cmd.args, &cmd.op.tx),
+            Cmd::LiquidityPoolWithdraw(cmd) => (&cmd.args, &cmd.op.tx),
+            Cmd::ManageBuyOffer(cmd) => (&cmd.args, &cmd.op.tx),
+            Cmd::ManageData(cmd) => (&cmd.args, &cmd.op.tx),
+            Cmd::ManageSellOffer(cmd) => (&cmd.args, &cmd.op.tx),
+            Cmd::PathPaymentStrictReceive(cmd) => (&cmd.args, &cmd.op.tx),
+            Cmd::PathPaymentStrictSend(cmd) => (&cmd.args, &cmd.op.tx),
+            Cmd::Payment(cmd) => (&cmd.args, &cmd.op.tx),
+            Cmd::RestoreFootprint(cmd) => (&cmd.args, &cmd.op.tx),
+            Cmd::RevokeSponsorship(cmd) => (&cmd.args, &cmd.op.tx),
+            Cmd::SetOptions(cmd) => (&cmd.args, &cmd.op.tx),
+            Cmd::SetTrustlineFlags(cmd) => (&cmd.args, &cmd.op.tx),
         }
-        .await?;
-        println!("{}", res.to_xdr_base64(crate::xdr::Limits::none())?);
-        Ok(())
+    }
+
+    /// Operations are built like with `tx new`, see `new::Cmd::op`.
+    async fn op(&self, global_args: &global::Args) -> Result<Op, Error> {
+        Ok(match self {
+            Cmd::AccountMerge(cmd) => Op::Classic((&cmd.op).try_into()?),
+            Cmd::BeginSponsoringFutureReserves(cmd) => Op::Classic((&cmd.op).try_into()?),
+            Cmd::BumpSequence(cmd) => Op::Classic((&cmd.op).into()),
+            Cmd::ChangeTrust(cmd) => Op::Classic((&cmd.op).try_into()?),
+            Cmd::ClaimClaimableBalance(cmd) => Op::Classic((&cmd.op).try_into()?),
+            Cmd::Clawback(cmd) => Op::Classic((&cmd.op).try_into()?),
+            Cmd::ClawbackClaimableBalance(cmd) => Op::Classic((&cmd.op).try_into()?),
+            Cmd::CreateAccount(cmd) => Op::Classic((&cmd.op).try_into()?),
+            Cmd::CreateClaimableBalance(cmd) => Op::Classic((&cmd.op).try_into()?),
+            Cmd::CreatePassiveSellOffer(cmd) => Op::Classic((&cmd.op).try_into()?),
+            Cmd::EndSponsoringFutureReserves(cmd) => Op::Classic((&cmd.op).into()),
+            Cmd::ExtendFootprintTtl(cmd) => Op::Soroban(cmd.op.op.soroban_op(&cmd.op.tx.config)?),
+            Cmd::InvokeHostFunction(cmd) => {
+                Op::Soroban(cmd.op.op.soroban_op(&cmd.op.tx.config, global_args).await?)
+            }
+            Cmd::LiquidityPoolDeposit(cmd) => Op::Classic((&cmd.op).try_into()?),
+            Cmd::LiquidityPoolWithdraw(cmd) => Op::Classic((&cmd.op).try_into()?),
+            Cmd::ManageBuyOffer(cmd) => Op::Classic((&cmd.op).try_into()?),
+            Cmd::ManageData(cmd) => Op::Classic((&cmd.op).into()),
+            Cmd::ManageSellOffer(cmd) => Op::Classic((&cmd.op).try_into()?),
+            Cmd::PathPaymentStrictReceive(cmd) => Op::Classic((&cmd.op).try_into()?),
+            Cmd::PathPaymentStrictSend(cmd) => Op::Classic((&cmd.op).try_into()?),
+            Cmd::Payment(cmd) => Op::Classic((&cmd.op).try_into()?),
+            Cmd::RestoreFootprint(cmd) => Op::Soroban(cmd.op.op.soroban_op(&cmd.op.tx.config)?),
+            Cmd::RevokeSponsorship(cmd) => Op::Classic((&cmd.op).try_into()?),
+            Cmd::SetOptions(cmd) => Op::Classic((&cmd.op).try_into()?),
+            Cmd::SetTrustlineFlags(cmd) => Op::Classic((&cmd.op).try_into()?),
+        })
     }
 }
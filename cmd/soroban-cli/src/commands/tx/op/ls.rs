@@ -0,0 +1,253 @@
+use std::ffi::OsString;
+
+use sha2::{Digest, Sha256};
+
+use crate::{
+    commands::{global, tx::xdr},
+    xdr::{
+        AlphaNum12, AlphaNum4, Asset, ChangeTrustAsset, ContractExecutable, ContractIdPreimage,
+        HostFunction, LiquidityPoolParameters, OperationBody, Price, RevokeSponsorshipOp,
+        TransactionEnvelope,
+    },
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    TxXdr(#[from] xdr::Error),
+}
+
+/// e.g. `stellar tx new payment --destination bob --amount 100 --build-only | stellar tx op ls`
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Base-64 transaction envelope XDR or file containing XDR to decode, or stdin if empty
+    #[arg()]
+    pub tx_xdr: Option<OsString>,
+}
+
+impl Cmd {
+    pub fn run(&self, _global_args: &global::Args) -> Result<(), Error> {
+        let tx_env = xdr::tx_envelope_from_input(&self.tx_xdr)?;
+        let tx = match tx_env {
+            TransactionEnvelope::TxFeeBump(env) => {
+                let crate::xdr::FeeBumpTransactionInnerTx::Tx(inner) = env.tx.inner_tx;
+                inner.tx
+            }
+            tx_env => xdr::unwrap_envelope_v1(tx_env)?,
+        };
+        for (index, op) in tx.operations.iter().enumerate() {
+            let mut parts = vec![format!("{index}: {}", op.body.name())];
+            parts.extend(
+                fields(&op.body)
+                    .into_iter()
+                    .map(|(key, value)| format!("{key}={value}")),
+            );
+            if let Some(source) = &op.source_account {
+                parts.push(format!("(source: {source})"));
+            }
+            println!("{}", parts.join(" "));
+        }
+        Ok(())
+    }
+}
+
+/// The key fields of an operation, amounts in stroops.
+#[allow(clippy::too_many_lines)]
+fn fields(body: &OperationBody) -> Vec<(&'static str, String)> {
+    match body {
+        OperationBody::CreateAccount(op) => vec![
+            ("destination", op.destination.to_string()),
+            ("starting_balance", op.starting_balance.to_string()),
+        ],
+        OperationBody::Payment(op) => vec![
+            ("destination", op.destination.to_string()),
+            ("asset", asset(&op.asset)),
+            ("amount", op.amount.to_string()),
+        ],
+        OperationBody::PathPaymentStrictReceive(op) => vec![
+            ("destination", op.destination.to_string()),
+            ("send_asset", asset(&op.send_asset)),
+            ("send_max", op.send_max.to_string()),
+            ("dest_asset", asset(&op.dest_asset)),
+            ("dest_amount", op.dest_amount.to_string()),
+        ],
+        OperationBody::PathPaymentStrictSend(op) => vec![
+            ("destination", op.destination.to_string()),
+            ("send_asset", asset(&op.send_asset)),
+            ("send_amount", op.send_amount.to_string()),
+            ("dest_asset", asset(&op.dest_asset)),
+            ("dest_min", op.dest_min.to_string()),
+        ],
+        OperationBody::ManageSellOffer(op) => vec![
+            ("selling", asset(&op.selling)),
+            ("buying", asset(&op.buying)),
+            ("amount", op.amount.to_string()),
+            ("price", price(&op.price)),
+            ("offer_id", op.offer_id.to_string()),
+        ],
+        OperationBody::ManageBuyOffer(op) => vec![
+            ("selling", asset(&op.selling)),
+            ("buying", asset(&op.buying)),
+            ("buy_amount", op.buy_amount.to_string()),
+            ("price", price(&op.price)),
+            ("offer_id", op.offer_id.to_string()),
+        ],
+        OperationBody::CreatePassiveSellOffer(op) => vec![
+            ("selling", asset(&op.selling)),
+            ("buying", asset(&op.buying)),
+            ("amount", op.amount.to_string()),
+            ("price", price(&op.price)),
+        ],
+        OperationBody::SetOptions(op) => {
+            let mut fields = Vec::new();
+            if let Some(inflation_dest) = &op.inflation_dest {
+                fields.push(("inflation_dest", inflation_dest.to_string()));
+            }
+            for (key, value) in [
+                ("clear_flags", op.clear_flags),
+                ("set_flags", op.set_flags),
+                ("master_weight", op.master_weight),
+                ("low_threshold", op.low_threshold),
+                ("med_threshold", op.med_threshold),
+                ("high_threshold", op.high_threshold),
+            ] {
+                if let Some(value) = value {
+                    fields.push((key, value.to_string()));
+                }
+            }
+            if let Some(home_domain) = &op.home_domain {
+                fields.push(("home_domain", home_domain.to_utf8_string_lossy()));
+            }
+            if let Some(signer) = &op.signer {
+                fields.push(("signer", format!("{}:{}", signer.key, signer.weight)));
+            }
+            fields
+        }
+        OperationBody::ChangeTrust(op) => vec![
+            ("line", change_trust_asset(&op.line)),
+            ("limit", op.limit.to_string()),
+        ],
+        OperationBody::AllowTrust(op) => vec![
+            ("trustor", op.trustor.to_string()),
+            ("asset", op.asset.to_string()),
+            ("authorize", op.authorize.to_string()),
+        ],
+        OperationBody::AccountMerge(destination) => {
+            vec![("destination", destination.to_string())]
+        }
+        OperationBody::ManageData(op) => vec![
+            ("name", format!("{:?}", op.data_name.to_utf8_string_lossy())),
+            (
+                "value",
+                op.data_value
+                    .as_ref()
+                    .map_or_else(|| "none".to_string(), |value| hex::encode(value.as_slice())),
+            ),
+        ],
+        OperationBody::BumpSequence(op) => vec![("bump_to", op.bump_to.0.to_string())],
+        OperationBody::CreateClaimableBalance(op) => vec![
+            ("asset", asset(&op.asset)),
+            ("amount", op.amount.to_string()),
+            ("claimants", op.claimants.len().to_string()),
+        ],
+        OperationBody::ClaimClaimableBalance(op) => {
+            vec![("balance_id", op.balance_id.to_string())]
+        }
+        OperationBody::BeginSponsoringFutureReserves(op) => {
+            vec![("sponsored", op.sponsored_id.to_string())]
+        }
+        OperationBody::RevokeSponsorship(RevokeSponsorshipOp::LedgerEntry(key)) => {
+            vec![("ledger_key", key.name().to_string())]
+        }
+        OperationBody::RevokeSponsorship(RevokeSponsorshipOp::Signer(op)) => vec![
+            ("account", op.account_id.to_string()),
+            ("signer", op.signer_key.to_string()),
+        ],
+        OperationBody::Clawback(op) => vec![
+            ("from", op.from.to_string()),
+            ("asset", asset(&op.asset)),
+            ("amount", op.amount.to_string()),
+        ],
+        OperationBody::ClawbackClaimableBalance(op) => {
+            vec![("balance_id", op.balance_id.to_string())]
+        }
+        OperationBody::SetTrustLineFlags(op) => vec![
+            ("trustor", op.trustor.to_string()),
+            ("asset", asset(&op.asset)),
+            ("clear_flags", op.clear_flags.to_string()),
+            ("set_flags", op.set_flags.to_string()),
+        ],
+        OperationBody::LiquidityPoolDeposit(op) => vec![
+            ("pool", op.liquidity_pool_id.to_string()),
+            ("max_amount_a", op.max_amount_a.to_string()),
+            ("max_amount_b", op.max_amount_b.to_string()),
+        ],
+        OperationBody::LiquidityPoolWithdraw(op) => vec![
+            ("pool", op.liquidity_pool_id.to_string()),
+            ("amount", op.amount.to_string()),
+        ],
+        OperationBody::InvokeHostFunction(op) => match &op.host_function {
+            HostFunction::InvokeContract(args) => vec![
+                ("contract", args.contract_address.to_string()),
+                ("function", args.function_name.to_utf8_string_lossy()),
+            ],
+            HostFunction::CreateContract(args) => {
+                create_contract(&args.executable, &args.contract_id_preimage)
+            }
+            HostFunction::CreateContractV2(args) => {
+                create_contract(&args.executable, &args.contract_id_preimage)
+            }
+            HostFunction::UploadContractWasm(wasm) => {
+                vec![("upload_wasm", hex::encode(Sha256::digest(wasm.as_slice())))]
+            }
+        },
+        OperationBody::ExtendFootprintTtl(op) => vec![("extend_to", op.extend_to.to_string())],
+        OperationBody::Inflation
+        | OperationBody::EndSponsoringFutureReserves
+        | OperationBody::RestoreFootprint(_) => Vec::new(),
+    }
+}
+
+fn create_contract(
+    executable: &ContractExecutable,
+    preimage: &ContractIdPreimage,
+) -> Vec<(&'static str, String)> {
+    match (executable, preimage) {
+        (_, ContractIdPreimage::Asset(a)) => vec![("create_asset_contract", asset(a))],
+        (ContractExecutable::Wasm(hash), ContractIdPreimage::Address(_)) => {
+            vec![("create_contract", hash.to_string())]
+        }
+        (ContractExecutable::StellarAsset, ContractIdPreimage::Address(_)) => {
+            vec![("create_contract", "stellar_asset".to_string())]
+        }
+    }
+}
+
+/// An asset as `native` or `CODE:ISSUER`.
+fn asset(asset: &Asset) -> String {
+    match asset {
+        Asset::Native => "native".to_string(),
+        Asset::CreditAlphanum4(AlphaNum4 { asset_code, issuer }) => {
+            format!("{asset_code}:{issuer}")
+        }
+        Asset::CreditAlphanum12(AlphaNum12 { asset_code, issuer }) => {
+            format!("{asset_code}:{issuer}")
+        }
+    }
+}
+
+fn change_trust_asset(line: &ChangeTrustAsset) -> String {
+    match line {
+        ChangeTrustAsset::Native => asset(&Asset::Native),
+        ChangeTrustAsset::CreditAlphanum4(a) => asset(&Asset::CreditAlphanum4(a.clone())),
+        ChangeTrustAsset::CreditAlphanum12(a) => asset(&Asset::CreditAlphanum12(a.clone())),
+        ChangeTrustAsset::PoolShare(LiquidityPoolParameters::LiquidityPoolConstantProduct(
+            pool,
+        )) => format!("pool:{}/{}", asset(&pool.asset_a), asset(&pool.asset_b)),
+    }
+}
+
+fn price(price: &Price) -> String {
+    format!("{}/{}", price.n, price.d)
+}
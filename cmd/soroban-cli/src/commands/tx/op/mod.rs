@@ -1,24 +1,48 @@
 use super::global;
 
 pub mod add;
+pub mod ls;
+pub mod move_op;
+pub mod remove;
+pub mod set_source;
 
 #[derive(Debug, clap::Subcommand)]
 pub enum Cmd {
     /// Add Operation to a transaction
     #[command(subcommand)]
     Add(add::Cmd),
+    /// List the operations of a transaction with their index and key fields
+    Ls(ls::Cmd),
+    /// Move an operation of a transaction to another index
+    Move(move_op::Cmd),
+    /// Remove an operation from a transaction
+    Remove(remove::Cmd),
+    /// Set or remove the source account of an operation in a transaction
+    SetSource(set_source::Cmd),
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error(transparent)]
     Add(#[from] add::Error),
+    #[error(transparent)]
+    Ls(#[from] ls::Error),
+    #[error(transparent)]
+    Move(#[from] move_op::Error),
+    #[error(transparent)]
+    Remove(#[from] remove::Error),
+    #[error(transparent)]
+    SetSource(#[from] set_source::Error),
 }
 
 impl Cmd {
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         match self {
             Cmd::Add(cmd) => cmd.run(global_args).await?,
+            Cmd::Ls(cmd) => cmd.run(global_args)?,
+            Cmd::Move(cmd) => cmd.run(global_args)?,
+            Cmd::Remove(cmd) => cmd.run(global_args)?,
+            Cmd::SetSource(cmd) => cmd.run(global_args).await?,
         }
         Ok(())
     }
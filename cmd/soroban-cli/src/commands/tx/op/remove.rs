@@ -0,0 +1,39 @@
+use std::ffi::OsString;
+
+use crate::{
+    commands::{
+        global,
+        tx::{print_edited, xdr},
+    },
+    print::Print,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    TxXdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Xdr(#[from] crate::xdr::Error),
+}
+
+/// e.g. `stellar tx op remove --index 1 tx.xdr`
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Index of the operation to remove, as listed by `tx op ls`
+    #[arg(long)]
+    pub index: usize,
+    /// Base-64 transaction envelope XDR or file containing XDR to decode, or stdin if empty
+    #[arg()]
+    pub tx_xdr: Option<OsString>,
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let tx_env = xdr::tx_envelope_from_input(&self.tx_xdr)?;
+        let signatures = xdr::envelope_signatures(&tx_env).len();
+        let tx_env = xdr::remove_op(tx_env, self.index)?;
+        print_edited(&Print::new(global_args.quiet), signatures, &tx_env)?;
+        Ok(())
+    }
+}
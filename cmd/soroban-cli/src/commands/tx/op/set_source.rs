@@ -0,0 +1,58 @@
+use std::ffi::OsString;
+
+use crate::{
+    commands::{
+        global,
+        tx::{print_edited, xdr},
+    },
+    config::{address, locator, UnresolvedMuxedAccount},
+    print::Print,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    TxXdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Xdr(#[from] crate::xdr::Error),
+    #[error(transparent)]
+    Address(#[from] address::Error),
+}
+
+/// e.g. `stellar tx op set-source --index 1 --operation-source-account alice tx.xdr`
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Index of the operation to update, as listed by `tx op ls`
+    #[arg(long)]
+    pub index: usize,
+    /// Source account of the operation. Can be an identity (alice), a public key (GDKW...) or a muxed account (MDA…). If omitted, the operation's source account is removed so it uses the transaction's source account
+    #[arg(long, visible_alias = "op-source")]
+    pub operation_source_account: Option<UnresolvedMuxedAccount>,
+    /// If the source account is an identity using a seed phrase, use this hd path, default is 0
+    #[arg(long)]
+    pub hd_path: Option<usize>,
+    /// Base-64 transaction envelope XDR or file containing XDR to decode, or stdin if empty
+    #[arg()]
+    pub tx_xdr: Option<OsString>,
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let source_account = match &self.operation_source_account {
+            Some(account) => Some(
+                account
+                    .resolve_muxed_account(&self.locator, self.hd_path)
+                    .await?,
+            ),
+            None => None,
+        };
+        let tx_env = xdr::tx_envelope_from_input(&self.tx_xdr)?;
+        let signatures = xdr::envelope_signatures(&tx_env).len();
+        let tx_env = xdr::set_op_source(tx_env, self.index, source_account)?;
+        print_edited(&Print::new(global_args.quiet), signatures, &tx_env)?;
+        Ok(())
+    }
+}
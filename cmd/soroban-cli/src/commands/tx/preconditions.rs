@@ -0,0 +1,367 @@
+use std::{str::FromStr, time::Duration};
+
+use crate::xdr::{
+    self, Duration as XdrDuration, LedgerBounds, Preconditions, PreconditionsV2, SequenceNumber,
+    SignerKey, TimeBounds, TimePoint, Transaction,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(
+        "invalid time {0:?}, use a unix timestamp, an RFC 3339 date such as 2025-06-01T12:00:00Z or a duration from now such as 10m"
+    )]
+    InvalidTime(String),
+    #[error("the transaction would never be valid, it is valid after {min_time} but only until {max_time}")]
+    TimeBounds { min_time: u64, max_time: u64 },
+    #[error(
+        "the transaction would never be valid, it expired at {max_time} and the time is now {now}"
+    )]
+    Expired { max_time: u64, now: u64 },
+    #[error("the transaction would never be valid, it is valid from ledger {min_ledger} but only until ledger {max_ledger}")]
+    LedgerBounds { min_ledger: u32, max_ledger: u32 },
+    #[error("the min sequence number {min_seq_num} must be lower than the sequence number of the transaction, {seq_num}")]
+    MinSeqNum { min_seq_num: i64, seq_num: i64 },
+    #[error("a transaction can have at most 2 extra signers, {0} were given")]
+    TooManyExtraSigners(usize),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+}
+
+/// Preconditions of a transaction, the conditions under which the network accepts it.
+#[derive(Debug, clap::Args, Clone, Default)]
+#[group(skip)]
+pub struct Args {
+    /// Time from which the transaction is valid: a unix timestamp, an RFC 3339 date such as `2025-06-01T12:00:00Z`, or a duration from now such as `10m`
+    #[arg(long)]
+    pub valid_after: Option<Time>,
+    /// Time until which the transaction is valid: a unix timestamp, an RFC 3339 date such as `2025-06-01T12:00:00Z`, or a duration from now such as `1h`
+    #[arg(long)]
+    pub valid_until: Option<Time>,
+    /// Ledger sequence from which the transaction is valid
+    #[arg(long)]
+    pub min_ledger: Option<u32>,
+    /// Ledger sequence until which the transaction is valid, the transaction is not valid in this ledger or any later one
+    #[arg(long)]
+    pub max_ledger: Option<u32>,
+    /// Smallest sequence number the source account can have for the transaction to be valid. Without it, the source account's sequence number must be the one right before the transaction's
+    #[arg(long, value_parser = clap::value_parser!(i64).range(0..))]
+    pub min_seq_num: Option<i64>,
+    /// Minimum time since the source account's sequence number last changed for the transaction to be valid, e.g. `1h` or `90s`
+    #[arg(long, value_parser = humantime::parse_duration)]
+    pub min_seq_age: Option<Duration>,
+    /// Minimum number of ledgers since the source account's sequence number last changed for the transaction to be valid
+    #[arg(long)]
+    pub min_seq_ledger_gap: Option<u32>,
+    /// Signer the transaction needs a signature of, on top of the signatures its accounts need. Can be a public key (G…), a pre-authorized transaction hash (T…), a hash-x (X…) or a signed payload (P…). Can be given twice
+    #[arg(long = "extra-signer")]
+    pub extra_signers: Vec<SignerKey>,
+}
+
+/// A point in time, absolute or relative to the time the transaction is built.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Time {
+    /// Seconds since the unix epoch
+    At(u64),
+    /// Duration from now
+    In(Duration),
+}
+
+impl Time {
+    /// The unix timestamp of this time, for a transaction built at `now`.
+    pub fn timestamp(self, now: u64) -> u64 {
+        match self {
+            Time::At(timestamp) => timestamp,
+            Time::In(duration) => now.saturating_add(duration.as_secs()),
+        }
+    }
+}
+
+impl FromStr for Time {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(timestamp) = s.parse() {
+            return Ok(Time::At(timestamp));
+        }
+        if let Ok(duration) = humantime::parse_duration(s) {
+            return Ok(Time::In(duration));
+        }
+        chrono::DateTime::parse_from_rfc3339(s)
+            .ok()
+            .and_then(|date| u64::try_from(date.timestamp()).ok())
+            .map(Time::At)
+            .ok_or_else(|| Error::InvalidTime(s.to_string()))
+    }
+}
+
+impl Args {
+    /// Whether any precondition was given.
+    pub fn is_set(&self) -> bool {
+        self.valid_after.is_some()
+            || self.valid_until.is_some()
+            || self.min_ledger.is_some()
+            || self.max_ledger.is_some()
+            || self.min_seq_num.is_some()
+            || self.min_seq_age.is_some()
+            || self.min_seq_ledger_gap.is_some()
+            || !self.extra_signers.is_empty()
+    }
+
+    /// Set the given preconditions on `tx`, keeping the ones it already has that aren't given,
+    /// with relative times counted from `now`, in seconds since the unix epoch.
+    pub fn apply(&self, tx: &mut Transaction, now: u64) -> Result<(), Error> {
+        let mut cond = v2(&tx.cond);
+        if self.valid_after.is_some() || self.valid_until.is_some() {
+            let bounds = cond.time_bounds.get_or_insert(TimeBounds {
+                min_time: TimePoint(0),
+                max_time: TimePoint(0),
+            });
+            if let Some(time) = self.valid_after {
+                bounds.min_time = TimePoint(time.timestamp(now));
+            }
+            if let Some(time) = self.valid_until {
+                bounds.max_time = TimePoint(time.timestamp(now));
+            }
+        }
+        if self.min_ledger.is_some() || self.max_ledger.is_some() {
+            let bounds = cond.ledger_bounds.get_or_insert(LedgerBounds {
+                min_ledger: 0,
+                max_ledger: 0,
+            });
+            if let Some(ledger) = self.min_ledger {
+                bounds.min_ledger = ledger;
+            }
+            if let Some(ledger) = self.max_ledger {
+                bounds.max_ledger = ledger;
+            }
+        }
+        if let Some(min_seq_num) = self.min_seq_num {
+            cond.min_seq_num = Some(SequenceNumber(min_seq_num));
+        }
+        if let Some(age) = self.min_seq_age {
+            cond.min_seq_age = XdrDuration(age.as_secs());
+        }
+        if let Some(gap) = self.min_seq_ledger_gap {
+            cond.min_seq_ledger_gap = gap;
+        }
+        if !self.extra_signers.is_empty() {
+            let mut signers = cond.extra_signers.to_vec();
+            for signer in &self.extra_signers {
+                if !signers.contains(signer) {
+                    signers.push(signer.clone());
+                }
+            }
+            let count = signers.len();
+            cond.extra_signers = signers
+                .try_into()
+                .map_err(|_| Error::TooManyExtraSigners(count))?;
+        }
+        validate(&cond, tx.seq_num.0, now)?;
+        tx.cond = simplify(cond);
+        Ok(())
+    }
+}
+
+/// Seconds since the unix epoch.
+pub fn now() -> u64 {
+    u64::try_from(chrono::Utc::now().timestamp()).unwrap_or_default()
+}
+
+fn v2(cond: &Preconditions) -> PreconditionsV2 {
+    match cond {
+        Preconditions::V2(cond) => cond.clone(),
+        Preconditions::Time(time_bounds) => PreconditionsV2 {
+            time_bounds: Some(time_bounds.clone()),
+            ..empty()
+        },
+        Preconditions::None => empty(),
+    }
+}
+
+fn empty() -> PreconditionsV2 {
+    PreconditionsV2 {
+        time_bounds: None,
+        ledger_bounds: None,
+        min_seq_num: None,
+        min_seq_age: XdrDuration(0),
+        min_seq_ledger_gap: 0,
+        extra_signers: xdr::VecM::default(),
+    }
+}
+
+/// The simplest form of `cond`, so transactions that only have time bounds keep the `Time`
+/// preconditions older tools understand.
+fn simplify(cond: PreconditionsV2) -> Preconditions {
+    let only_time_bounds = cond.ledger_bounds.is_none()
+        && cond.min_seq_num.is_none()
+        && cond.min_seq_age.0 == 0
+        && cond.min_seq_ledger_gap == 0
+        && cond.extra_signers.is_empty();
+    match (only_time_bounds, cond.time_bounds) {
+        (true, None) => Preconditions::None,
+        (true, Some(time_bounds)) => Preconditions::Time(time_bounds),
+        (false, time_bounds) => Preconditions::V2(PreconditionsV2 {
+            time_bounds,
+            ..cond
+        }),
+    }
+}
+
+fn validate(cond: &PreconditionsV2, seq_num: i64, now: u64) -> Result<(), Error> {
+    if let Some(TimeBounds { min_time, max_time }) = &cond.time_bounds {
+        let (min_time, max_time) = (min_time.0, max_time.0);
+        if max_time != 0 && min_time > max_time {
+            return Err(Error::TimeBounds { min_time, max_time });
+        }
+        if max_time != 0 && max_time < now {
+            return Err(Error::Expired { max_time, now });
+        }
+    }
+    if let Some(LedgerBounds {
+        min_ledger,
+        max_ledger,
+    }) = cond.ledger_bounds
+    {
+        if max_ledger != 0 && min_ledger >= max_ledger {
+            return Err(Error::LedgerBounds {
+                min_ledger,
+                max_ledger,
+            });
+        }
+    }
+    if let Some(SequenceNumber(min_seq_num)) = cond.min_seq_num {
+        if min_seq_num >= seq_num {
+            return Err(Error::MinSeqNum {
+                min_seq_num,
+                seq_num,
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdr::{Memo, MuxedAccount, TransactionExt, Uint256};
+
+    const NOW: u64 = 1_750_000_000;
+
+    fn tx() -> Transaction {
+        Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256([1; 32])),
+            fee: 100,
+            seq_num: SequenceNumber(10),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: xdr::VecM::default(),
+            ext: TransactionExt::V0,
+        }
+    }
+
+    #[test]
+    fn parse_time() {
+        assert_eq!("1750000000".parse::<Time>().unwrap(), Time::At(NOW));
+        assert_eq!(
+            "10m".parse::<Time>().unwrap(),
+            Time::In(Duration::from_secs(600))
+        );
+        assert_eq!(
+            "2025-06-15T15:06:40Z".parse::<Time>().unwrap(),
+            Time::At(NOW)
+        );
+        assert!(matches!(
+            "tomorrow".parse::<Time>(),
+            Err(Error::InvalidTime(_))
+        ));
+    }
+
+    #[test]
+    fn time_bounds_only_stay_time_preconditions() {
+        let mut tx = tx();
+        let args = Args {
+            valid_until: Some(Time::In(Duration::from_secs(60))),
+            ..Args::default()
+        };
+        args.apply(&mut tx, NOW).unwrap();
+        assert_eq!(
+            tx.cond,
+            Preconditions::Time(TimeBounds {
+                min_time: TimePoint(0),
+                max_time: TimePoint(NOW + 60),
+            })
+        );
+        Args::default().apply(&mut tx, NOW).unwrap();
+        assert!(matches!(tx.cond, Preconditions::Time(_)));
+    }
+
+    #[test]
+    fn other_preconditions_keep_existing_ones() {
+        let mut tx = tx();
+        let args = Args {
+            valid_after: Some(Time::At(NOW)),
+            ..Args::default()
+        };
+        args.apply(&mut tx, NOW).unwrap();
+        let args = Args {
+            min_ledger: Some(5),
+            min_seq_num: Some(3),
+            extra_signers: vec![SignerKey::Ed25519(Uint256([2; 32]))],
+            ..Args::default()
+        };
+        args.apply(&mut tx, NOW).unwrap();
+        let Preconditions::V2(cond) = tx.cond else {
+            panic!("expected v2 preconditions");
+        };
+        assert_eq!(cond.time_bounds.unwrap().min_time, TimePoint(NOW));
+        assert_eq!(cond.ledger_bounds.unwrap().min_ledger, 5);
+        assert_eq!(cond.min_seq_num, Some(SequenceNumber(3)));
+        assert_eq!(cond.extra_signers.len(), 1);
+    }
+
+    #[test]
+    fn impossible_preconditions_are_rejected() {
+        let apply = |args: Args| args.apply(&mut tx(), NOW);
+        assert!(matches!(
+            apply(Args {
+                valid_after: Some(Time::At(NOW + 10)),
+                valid_until: Some(Time::At(NOW + 5)),
+                ..Args::default()
+            }),
+            Err(Error::TimeBounds { .. })
+        ));
+        assert!(matches!(
+            apply(Args {
+                valid_until: Some(Time::At(NOW - 1)),
+                ..Args::default()
+            }),
+            Err(Error::Expired { .. })
+        ));
+        assert!(matches!(
+            apply(Args {
+                min_ledger: Some(20),
+                max_ledger: Some(20),
+                ..Args::default()
+            }),
+            Err(Error::LedgerBounds { .. })
+        ));
+        assert!(matches!(
+            apply(Args {
+                min_seq_num: Some(10),
+                ..Args::default()
+            }),
+            Err(Error::MinSeqNum { .. })
+        ));
+        assert!(matches!(
+            apply(Args {
+                extra_signers: vec![
+                    SignerKey::Ed25519(Uint256([1; 32])),
+                    SignerKey::Ed25519(Uint256([2; 32])),
+                    SignerKey::Ed25519(Uint256([3; 32])),
+                ],
+                ..Args::default()
+            }),
+            Err(Error::TooManyExtraSigners(3))
+        ));
+    }
+}
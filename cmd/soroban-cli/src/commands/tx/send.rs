@@ -1,15 +1,29 @@
 use crate::print::Print;
 use async_trait::async_trait;
-use soroban_rpc::GetTransactionResponse;
-use std::ffi::OsString;
+use jsonrpsee_core::{client::ClientT, params::ObjectParams};
+use soroban_rpc::{Client, GetTransactionResponse, SendTransactionResponse};
+use std::{
+    ffi::OsString,
+    str::FromStr,
+    time::{Duration, Instant},
+};
 
 use crate::{
     commands::{global, NetworkRunnable},
-    config::{self, locator, network},
+    config::{self, channels, data, locator, network, sign_with, UnresolvedMuxedAccount},
+    signer::{self, SignerKind},
+    tx::signatures,
+    xdr::{
+        Hash, Limits, ReadXdr, SequenceNumber, Transaction, TransactionEnvelope, TransactionResult,
+        TransactionResultResult, WriteXdr,
+    },
 };
 
 use stellar_xdr::curr;
 
+/// Longest wait between two submissions when the network asks to try again later.
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error(transparent)]
@@ -21,9 +35,39 @@ pub enum Error {
     #[error(transparent)]
     Rpc(#[from] crate::rpc::Error),
     #[error(transparent)]
+    JsonRpc(#[from] jsonrpsee_core::Error),
+    #[error(transparent)]
     SerdeJson(#[from] serde_json::Error),
     #[error("xdr processing error: {0}")]
     Xdr(#[from] curr::Error),
+    #[error(transparent)]
+    Channels(#[from] channels::Error),
+    #[error(transparent)]
+    SignWith(#[from] sign_with::Error),
+    #[error(transparent)]
+    Data(#[from] data::Error),
+    #[error(transparent)]
+    FeeBump(#[from] super::fee_bump::Error),
+    #[error(transparent)]
+    Address(#[from] config::address::Error),
+    #[error(transparent)]
+    Signer(#[from] signer::Error),
+    #[error("sending through a channel account changes the transaction, so it must be signed again, pass the signer of its source account with `--sign-with-key`, `--sign-with-lab` or `--sign-with-ledger`")]
+    ChannelNeedsSigner,
+    #[error("transaction {hash} was rejected: {result}")]
+    Rejected { hash: String, result: String },
+    #[error("the fee of transaction {hash} is too low while the network is surge pricing, pass `--max-fee` with `--fee-bump-source` or a `--sign-with-*` signer to resubmit it with a higher fee")]
+    InsufficientFee { hash: String },
+    #[error("transaction {hash} carries {count} signature(s) that would be lost by raising its fee, pass `--fee-bump-source` to raise it through a fee bump instead")]
+    ForeignSignatures { hash: String, count: usize },
+    #[error("the network is still surge pricing and the fee cannot be raised above the `--max-fee` of {max_fee} stroops")]
+    MaxFeeReached { max_fee: i64 },
+    #[error("the network asked to try again later {attempts} times, giving up")]
+    TooManyAttempts { attempts: u32 },
+    #[error("transaction {hash} was not final within the deadline of {deadline:?}")]
+    DeadlineExceeded { hash: String, deadline: Duration },
+    #[error("unexpected status {0:?} of a transaction submission")]
+    UnexpectedStatus(String),
 }
 
 #[derive(Debug, clap::Parser, Clone)]
@@ -34,10 +78,26 @@ pub struct Cmd {
     /// Base-64 transaction envelope XDR or file containing XDR to decode, or stdin if empty
     #[arg()]
     pub tx_xdr: Option<OsString>,
+    /// Give up if the transaction is not final within this time, submissions and polling included, e.g. `2m`
+    #[arg(long, default_value = "5m", value_parser = humantime::parse_duration)]
+    pub deadline: Duration,
+    /// How many times to submit the transaction when the network asks to try again later
+    #[arg(long, default_value_t = 5)]
+    pub max_attempts: u32,
+    /// Highest fee, in stroops, to offer when resubmitting the transaction because the network is surge pricing. The fee doubles on each resubmission, through a fee bump paid by `--fee-bump-source`, or by rebuilding the transaction and signing it with the `--sign-with-*` signer. Without it the fee is never raised
+    #[arg(long)]
+    pub max_fee: Option<i64>,
+    /// Account that pays the fee bumps raising the fee, when `--max-fee` is given. Can be an identity (--fee-bump-source alice) or a secret key (--fee-bump-source SC36…)
+    #[arg(long, requires = "max_fee")]
+    pub fee_bump_source: Option<UnresolvedMuxedAccount>,
     #[clap(flatten)]
     pub network: network::Args,
     #[clap(flatten)]
     pub locator: locator::Args,
+    #[clap(flatten)]
+    pub channels: channels::Args,
+    #[clap(flatten)]
+    pub sign_with: sign_with::Args,
 }
 
 impl Cmd {
@@ -63,14 +123,343 @@ impl NetworkRunnable for Cmd {
         } else {
             self.network.get(&self.locator)?
         };
-        let client = network.rpc_client()?;
-        let tx_env = super::xdr::tx_envelope_from_input(&self.tx_xdr)?;
+        let mut tx_env = super::xdr::tx_envelope_from_input(&self.tx_xdr)?;
+        let quiet = globals.is_some_and(|g| g.quiet);
+        let no_cache = globals.is_some_and(|g| g.no_cache);
+
+        if self.channels.is_set() && !self.sign_with.is_set() {
+            return Err(Error::ChannelNeedsSigner);
+        }
+        let channel = self.channels.lease(&self.locator).await?;
+        if let Some(channel) = &channel {
+            Print::new(quiet).infoln(format!("Using channel account {}", channel.name));
+            tx_env = self
+                .through_channel(tx_env, channel, &network, quiet)
+                .await?;
+        }
+
+        let submission = Submission {
+            cmd: self,
+            client: network.rpc_client()?,
+            network: &network,
+            channel: channel.as_ref(),
+            print: Print::new(quiet),
+            quiet,
+            no_cache,
+            deadline: Instant::now() + self.deadline,
+        };
+        submission.run(tx_env).await
+    }
+}
+
+impl Cmd {
+    /// Make the channel account the source of the transaction and sign it again, with the signer
+    /// of its previous source account and with the channel account.
+    async fn through_channel(
+        &self,
+        tx_env: TransactionEnvelope,
+        channel: &channels::Lease,
+        network: &network::Network,
+        quiet: bool,
+    ) -> Result<TransactionEnvelope, Error> {
+        let mut tx = super::xdr::unwrap_envelope_v1(tx_env)?;
+        let account = network
+            .rpc_client()?
+            .get_account(&channel.account.to_string())
+            .await?;
+        channel.apply(&mut tx, SequenceNumber(account.seq_num.0 + 1))?;
+        self.sign(tx, Some(channel), network, quiet).await
+    }
+
+    /// Sign a transaction with the `--sign-with-*` signer, then with the channel account if any.
+    async fn sign(
+        &self,
+        tx: Transaction,
+        channel: Option<&channels::Lease>,
+        network: &network::Network,
+        quiet: bool,
+    ) -> Result<TransactionEnvelope, Error> {
+        let tx_env = self
+            .sign_with
+            .sign_tx_env(&tx.into(), &self.locator, network, quiet, None)
+            .await?;
+        Ok(match channel {
+            Some(channel) => channel.sign(&tx_env, network, quiet).await?,
+            None => tx_env,
+        })
+    }
+}
+
+/// The state of one `tx send`: submits the transaction until the network accepts it, raising its
+/// fee if allowed, then polls its status until it is final.
+struct Submission<'a> {
+    cmd: &'a Cmd,
+    client: Client,
+    network: &'a network::Network,
+    channel: Option<&'a channels::Lease>,
+    print: Print,
+    quiet: bool,
+    no_cache: bool,
+    deadline: Instant,
+}
+
+impl Submission<'_> {
+    async fn run(&self, mut tx_env: TransactionEnvelope) -> Result<GetTransactionResponse, Error> {
+        let mut backoff = Duration::from_secs(1);
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let tx_hash = super::xdr::envelope_hash(&tx_env, &self.network.network_passphrase)?;
+            let hash = hex::encode(tx_hash);
+            self.print
+                .log_transaction_hash(&tx_hash, self.network, attempt == 1);
+            let response = self.submit(&tx_env).await?;
+            if !self.no_cache {
+                data::write(
+                    data::Action::Submit {
+                        attempt,
+                        response: response.clone(),
+                    },
+                    &self.network.rpc_uri()?,
+                )?;
+            }
+            match response.status.as_str() {
+                "PENDING" | "DUPLICATE" => return self.poll(&hash).await,
+                "TRY_AGAIN_LATER" => {
+                    if attempt >= self.cmd.max_attempts {
+                        return Err(Error::TooManyAttempts { attempts: attempt });
+                    }
+                    self.print.warnln(format!(
+                        "The network asked to try again later, attempt {attempt} of {}",
+                        self.cmd.max_attempts
+                    ));
+                    if self.cmd.max_fee.is_some() {
+                        match self.raise_fee(tx_env.clone()).await {
+                            Ok(raised) => tx_env = raised,
+                            // The fee cannot be raised, resubmit the same transaction
+                            Err(
+                                Error::InsufficientFee { .. }
+                                | Error::MaxFeeReached { .. }
+                                | Error::ForeignSignatures { .. },
+                            ) => {}
+                            Err(e) => return Err(e),
+                        }
+                    }
+                    self.wait(backoff, &hash).await?;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                }
+                "ERROR" => {
+                    let result = error_result(&response)?;
+                    if !is_insufficient_fee(&result) {
+                        return Err(Error::Rejected {
+                            hash,
+                            result: result.name().to_string(),
+                        });
+                    }
+                    if self.cmd.max_fee.is_none() {
+                        return Err(Error::InsufficientFee { hash });
+                    }
+                    tx_env = self.raise_fee(tx_env).await?;
+                    self.wait(backoff, &hash).await?;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                }
+                status => return Err(Error::UnexpectedStatus(status.to_string())),
+            }
+            if Instant::now() >= self.deadline {
+                return Err(self.deadline_exceeded(&hash));
+            }
+        }
+    }
+
+    async fn submit(&self, tx_env: &TransactionEnvelope) -> Result<SendTransactionResponse, Error> {
+        let mut params = ObjectParams::new();
+        params.insert("transaction", tx_env.to_xdr_base64(Limits::none())?)?;
+        Ok(self
+            .client
+            .client()
+            .request("sendTransaction", params)
+            .await?)
+    }
+
+    async fn poll(&self, hash: &str) -> Result<GetTransactionResponse, Error> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        let response = match self
+            .client
+            .get_transaction_polling(&Hash::from_str(hash)?, Some(remaining))
+            .await
+        {
+            Err(crate::rpc::Error::TransactionSubmissionTimeout) => {
+                return Err(self.deadline_exceeded(hash))
+            }
+            response => response?,
+        };
+        if !self.no_cache {
+            data::write(response.clone().try_into()?, &self.network.rpc_uri()?)?;
+        }
+        Ok(response)
+    }
 
-        if let Ok(txn) = super::xdr::unwrap_envelope_v1(tx_env.clone()) {
-            let print = Print::new(globals.is_some_and(|g| g.quiet));
-            print.log_transaction(&txn, &network, true)?;
+    async fn wait(&self, duration: Duration, hash: &str) -> Result<(), Error> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining < duration {
+            return Err(self.deadline_exceeded(hash));
+        }
+        tokio::time::sleep(duration).await;
+        Ok(())
+    }
+
+    fn deadline_exceeded(&self, hash: &str) -> Error {
+        Error::DeadlineExceeded {
+            hash: hash.to_string(),
+            deadline: self.cmd.deadline,
+        }
+    }
+
+    /// Double the fee offered for the transaction, up to `--max-fee`, through a fee bump if a fee
+    /// bump source was given, or else by rebuilding and signing the transaction again. Fails if the
+    /// fee cannot be raised: no fee bump source nor signer, `--max-fee` reached, or signatures that
+    /// the rebuilt transaction cannot carry.
+    async fn raise_fee(&self, tx_env: TransactionEnvelope) -> Result<TransactionEnvelope, Error> {
+        let max_fee = self.cmd.max_fee.unwrap_or_default();
+        if let Some(fee_source) = &self.cmd.fee_bump_source {
+            let fee = current_fee(&tx_env);
+            let inner = match tx_env {
+                TransactionEnvelope::TxFeeBump(env) => {
+                    let curr::FeeBumpTransactionInnerTx::Tx(inner) = env.tx.inner_tx;
+                    TransactionEnvelope::Tx(inner)
+                }
+                tx_env => tx_env,
+            };
+            let TransactionEnvelope::Tx(inner_env) = &inner else {
+                return Err(super::xdr::Error::V0NotSupported.into());
+            };
+            let base_fee = super::fee_bump::network_base_fee(&self.client).await?;
+            let minimum = super::fee_bump::minimum_fee(&inner_env.tx, base_fee);
+            let fee = next_fee(fee, minimum, max_fee)?;
+            let fee_source_account = fee_source
+                .resolve_muxed_account(&self.cmd.locator, None)
+                .await?;
+            let fee_bump =
+                super::fee_bump::fee_bump(inner, fee_source_account, Some(fee), base_fee)?;
+            self.print
+                .infoln(format!("Resubmitting in a fee bump offering {fee} stroops"));
+            return Ok(sign_with::Args::default()
+                .sign_tx_env(
+                    &fee_bump,
+                    &self.cmd.locator,
+                    self.network,
+                    self.quiet,
+                    Some(fee_source),
+                )
+                .await?);
+        }
+        if !self.cmd.sign_with.is_set() {
+            return Err(Error::InsufficientFee {
+                hash: hex::encode(super::xdr::envelope_hash(
+                    &tx_env,
+                    &self.network.network_passphrase,
+                )?),
+            });
+        }
+        self.check_resignable(&tx_env).await?;
+        let mut tx = super::xdr::unwrap_envelope_v1(tx_env)?;
+        let fee = next_fee(i64::from(tx.fee), 0, max_fee)?;
+        tx.fee = u32::try_from(fee).unwrap_or(u32::MAX);
+        self.print
+            .infoln(format!("Resubmitting with a fee of {} stroops", tx.fee));
+        self.cmd
+            .sign(tx, self.channel, self.network, self.quiet)
+            .await
+    }
+
+    /// Rebuilding a transaction drops its signatures, and only those of the `--sign-with-*` signer
+    /// and of the channel account are made again: refuse if there are others.
+    async fn check_resignable(&self, tx_env: &TransactionEnvelope) -> Result<(), Error> {
+        let existing = super::xdr::envelope_signatures(tx_env);
+        if existing.is_empty() {
+            return Ok(());
+        }
+        let mut keys = Vec::new();
+        let signer = self
+            .cmd
+            .sign_with
+            .signer(&self.cmd.locator, self.quiet, None)
+            .await?;
+        if !matches!(signer.kind, SignerKind::Lab) {
+            keys.push(signer.get_public_key().await?);
+        }
+        if let Some(channel) = self.channel {
+            keys.push(signatures::account_key(
+                &channel.account.clone().account_id(),
+            ));
         }
+        let tx_hash = super::xdr::envelope_hash(tx_env, &self.network.network_passphrase)?;
+        let count = existing
+            .iter()
+            .filter(|s| signatures::signer_of(&keys, &tx_hash, s).is_none())
+            .count();
+        if count > 0 {
+            return Err(Error::ForeignSignatures {
+                hash: hex::encode(tx_hash),
+                count,
+            });
+        }
+        Ok(())
+    }
+}
+
+fn current_fee(tx_env: &TransactionEnvelope) -> i64 {
+    match tx_env {
+        TransactionEnvelope::TxV0(env) => i64::from(env.tx.fee),
+        TransactionEnvelope::Tx(env) => i64::from(env.tx.fee),
+        TransactionEnvelope::TxFeeBump(env) => env.tx.fee,
+    }
+}
+
+/// The fee to offer after `fee`: double it, at least `minimum`, at most `max_fee`.
+fn next_fee(fee: i64, minimum: i64, max_fee: i64) -> Result<i64, Error> {
+    let next = fee.saturating_mul(2).max(minimum).min(max_fee);
+    if next <= fee || next < minimum {
+        return Err(Error::MaxFeeReached { max_fee });
+    }
+    Ok(next)
+}
+
+fn error_result(response: &SendTransactionResponse) -> Result<TransactionResultResult, Error> {
+    let xdr = response
+        .error_result_xdr
+        .as_deref()
+        .ok_or(crate::rpc::Error::MissingError)?;
+    Ok(TransactionResult::from_xdr_base64(xdr, Limits::none())?.result)
+}
+
+fn is_insufficient_fee(result: &TransactionResultResult) -> bool {
+    match result {
+        TransactionResultResult::TxInsufficientFee => true,
+        TransactionResultResult::TxFeeBumpInnerFailed(inner) => matches!(
+            inner.result.result,
+            curr::InnerTransactionResultResult::TxInsufficientFee
+        ),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        Ok(client.send_transaction_polling(&tx_env).await?)
+    #[test]
+    fn fee_doubles_up_to_max_fee() {
+        assert_eq!(next_fee(100, 0, 1_000).unwrap(), 200);
+        assert_eq!(next_fee(100, 300, 1_000).unwrap(), 300);
+        assert_eq!(next_fee(600, 0, 1_000).unwrap(), 1_000);
+        assert!(matches!(
+            next_fee(1_000, 0, 1_000),
+            Err(Error::MaxFeeReached { max_fee: 1_000 })
+        ));
+        assert!(matches!(
+            next_fee(100, 2_000, 1_000),
+            Err(Error::MaxFeeReached { .. })
+        ));
     }
 }
@@ -0,0 +1,55 @@
+use crate::xdr::SequenceNumber;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("invalid sequence number {0:?}, expected a number, or the current sequence number of the source account plus an offset, e.g. `1234+1`")]
+    Invalid(String),
+    #[error("sequence number {0:?} is out of range")]
+    OutOfRange(String),
+}
+
+/// Sequence number of a transaction, given instead of fetched from the network.
+#[derive(Debug, clap::Args, Clone, Default)]
+#[group(skip)]
+pub struct Args {
+    /// Sequence number of the transaction, instead of the next sequence number of the source account fetched from the network. Either the number itself, or the current sequence number of the source account plus an offset, e.g. `1234+1` for its next transaction. With `--build-only` the transaction is built without contacting the network, for signing on an offline machine
+    #[arg(long, value_name = "SEQ", value_parser = parse)]
+    pub sequence_number: Option<SequenceNumber>,
+}
+
+fn parse(value: &str) -> Result<SequenceNumber, Error> {
+    let number = |s: &str| {
+        s.trim()
+            .parse::<i64>()
+            .map_err(|_| Error::Invalid(value.to_string()))
+    };
+    let seq_num = match value.split_once('+') {
+        Some((current, offset)) => number(current)?
+            .checked_add(number(offset)?)
+            .ok_or_else(|| Error::OutOfRange(value.to_string()))?,
+        None => number(value)?,
+    };
+    if seq_num < 0 {
+        return Err(Error::OutOfRange(value.to_string()));
+    }
+    Ok(SequenceNumber(seq_num))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sequence_number() {
+        assert_eq!(parse("1234").unwrap(), SequenceNumber(1234));
+        assert_eq!(parse("1234+1").unwrap(), SequenceNumber(1235));
+        assert_eq!(parse("1234 + 3").unwrap(), SequenceNumber(1237));
+        assert!(matches!(parse("abc"), Err(Error::Invalid(_))));
+        assert!(matches!(parse("1+x"), Err(Error::Invalid(_))));
+        assert!(matches!(parse("-5"), Err(Error::OutOfRange(_))));
+        assert!(matches!(
+            parse(&format!("{}+1", i64::MAX)),
+            Err(Error::OutOfRange(_))
+        ));
+    }
+}
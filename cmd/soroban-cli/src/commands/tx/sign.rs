@@ -1,7 +1,13 @@
 use crate::{
     commands::global,
-    config::{locator, network, sign_with},
-    xdr::{self, Limits, WriteXdr},
+    config::{locator, network, network::Network, secret, sign_with},
+    print::Print,
+    signer::{self, Signer, SignerKind},
+    tx::signatures::{self, AccountSigners, MissingWeight, ThresholdLevel},
+    xdr::{
+        self, FeeBumpTransactionEnvelope, Limits, Preconditions, SignerKey, TransactionEnvelope,
+        TransactionV1Envelope, Uint256, WriteXdr,
+    },
 };
 use std::ffi::OsString;
 
@@ -17,6 +23,14 @@ pub enum Error {
     SignWith(#[from] sign_with::Error),
     #[error(transparent)]
     Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Signer(#[from] signer::Error),
+    #[error(transparent)]
+    Secret(#[from] secret::Error),
+    #[error(transparent)]
+    Rpc(#[from] soroban_rpc::Error),
+    #[error("{address} is not a signer of any source account of the transaction")]
+    NotASigner { address: String },
 }
 
 #[derive(Debug, clap::Parser, Clone)]
@@ -27,6 +41,12 @@ pub struct Cmd {
     pub tx_xdr: Option<OsString>,
     #[command(flatten)]
     pub sign_with: sign_with::Args,
+    /// Additional key to sign with, can be passed multiple times. Can be an identity (--signer alice), a secret key (--signer SC36…), or a seed phrase (--signer "kite urban…")
+    #[arg(long = "signer", value_name = "KEY")]
+    pub signers: Vec<String>,
+    /// Sign without fetching the signers and thresholds of the transaction's source accounts, e.g. when offline. By default, keys that are not signers of any source account (or extra signers of the transaction) are refused, and operations that still lack enough signature weight are reported
+    #[arg(long)]
+    pub no_check: bool,
     #[command(flatten)]
     pub network: network::Args,
     #[command(flatten)]
@@ -34,20 +54,154 @@ pub struct Cmd {
 }
 
 impl Cmd {
-    #[allow(clippy::unused_async)]
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
-        let tx_env = super::xdr::tx_envelope_from_input(&self.tx_xdr)?;
-        let tx_env_signed = self
-            .sign_with
-            .sign_tx_env(
-                &tx_env,
-                &self.locator,
-                &self.network.get(&self.locator)?,
-                global_args.quiet,
-                None,
-            )
-            .await?;
-        println!("{}", tx_env_signed.to_xdr_base64(Limits::none())?);
+        let print = Print::new(global_args.quiet);
+        let network = self.network.get(&self.locator)?;
+        let mut tx_env = super::xdr::tx_envelope_from_input(&self.tx_xdr)?;
+        let accounts = if self.no_check {
+            Vec::new()
+        } else {
+            source_account_signers(&tx_env, &network).await?
+        };
+        let extra_signers = extra_signers(&tx_env);
+
+        for signer in self.signers(global_args.quiet).await? {
+            if matches!(signer.kind, SignerKind::Lab) {
+                tx_env = signer.sign_tx_env(&tx_env, &network).await?;
+                continue;
+            }
+            let key = signer.get_public_key().await?;
+            let address = stellar_strkey::ed25519::PublicKey(key).to_string();
+            if signed_by(&tx_env, &network, [&key])?.contains(&key) {
+                print.warnln(format!(
+                    "Transaction is already signed by {address}, skipping"
+                ));
+                continue;
+            }
+            if !self.no_check
+                && !extra_signers.contains(&key)
+                && !accounts.iter().any(|a| a.is_signer(&key))
+            {
+                return Err(Error::NotASigner { address });
+            }
+            tx_env = signer.sign_tx_env(&tx_env, &network).await?;
+        }
+
+        if !self.no_check {
+            report_missing_weight(&tx_env, &network, &accounts, &print)?;
+        }
+        println!("{}", tx_env.to_xdr_base64(Limits::none())?);
         Ok(())
     }
+
+    /// The `--sign-with-*` signer, followed by each `--signer`. The `--sign-with-*` signer is
+    /// only required when no `--signer` is given.
+    async fn signers(&self, quiet: bool) -> Result<Vec<Signer>, Error> {
+        let mut signers = Vec::new();
+        if self.sign_with.is_set() || self.signers.is_empty() {
+            signers.push(self.sign_with.signer(&self.locator, quiet, None).await?);
+        }
+        for key in &self.signers {
+            let secret = self.locator.get_secret_key(key)?;
+            signers.push(
+                secret
+                    .signer(self.sign_with.hd_path, Print::new(quiet))
+                    .await?,
+            );
+        }
+        Ok(signers)
+    }
+}
+
+/// The signers of the accounts that must sign the envelope: the source accounts of the
+/// transaction and its operations, or the fee source of a fee bump.
+async fn source_account_signers(
+    tx_env: &TransactionEnvelope,
+    network: &Network,
+) -> Result<Vec<AccountSigners>, Error> {
+    let account_ids = match tx_env {
+        TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope { tx, .. }) => {
+            vec![tx.fee_source.clone().account_id()]
+        }
+        _ => signatures::source_accounts(&super::xdr::unwrap_envelope_v1(tx_env.clone())?),
+    };
+    let client = network.rpc_client()?;
+    let mut accounts = Vec::new();
+    for account_id in account_ids {
+        let entry = client.get_account(&account_id.to_string()).await?;
+        accounts.push(AccountSigners::from(&entry));
+    }
+    Ok(accounts)
+}
+
+/// The ed25519 extra signers required by the preconditions of the transaction.
+fn extra_signers(tx_env: &TransactionEnvelope) -> Vec<[u8; 32]> {
+    let cond = match tx_env {
+        TransactionEnvelope::Tx(TransactionV1Envelope { tx, .. }) => &tx.cond,
+        TransactionEnvelope::TxFeeBump(_) | TransactionEnvelope::TxV0(_) => return Vec::new(),
+    };
+    let Preconditions::V2(cond) = cond else {
+        return Vec::new();
+    };
+    cond.extra_signers
+        .iter()
+        .filter_map(|signer| match signer {
+            SignerKey::Ed25519(Uint256(key)) => Some(*key),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Keys among `candidates` that have signed the envelope.
+fn signed_by<'a>(
+    tx_env: &TransactionEnvelope,
+    network: &Network,
+    candidates: impl IntoIterator<Item = &'a [u8; 32]> + Clone,
+) -> Result<Vec<[u8; 32]>, Error> {
+    let tx_hash = super::xdr::envelope_hash(tx_env, &network.network_passphrase)?;
+    Ok(super::xdr::envelope_signatures(tx_env)
+        .iter()
+        .filter_map(|s| signatures::signer_of(candidates.clone(), &tx_hash, s).copied())
+        .collect())
+}
+
+fn report_missing_weight(
+    tx_env: &TransactionEnvelope,
+    network: &Network,
+    accounts: &[AccountSigners],
+    print: &Print,
+) -> Result<(), Error> {
+    let keys = accounts
+        .iter()
+        .flat_map(|a| a.signers.iter().map(|(key, _)| key));
+    let signed = signed_by(tx_env, network, keys)?;
+    let missing = if let TransactionEnvelope::TxFeeBump(_) = tx_env {
+        // A fee bump only needs the low threshold of its fee source
+        accounts
+            .iter()
+            .filter_map(|account| {
+                let level = ThresholdLevel::Low;
+                let needed = account.needed(level);
+                let signed = account.signed_weight(&signed);
+                (signed < needed).then(|| MissingWeight {
+                    operation: None,
+                    name: "fee bump",
+                    account_id: account.account_id.clone(),
+                    level,
+                    needed,
+                    signed,
+                })
+            })
+            .collect()
+    } else {
+        let tx = super::xdr::unwrap_envelope_v1(tx_env.clone())?;
+        signatures::missing_weight(&tx, accounts, &signed)
+    };
+    if missing.is_empty() {
+        print.checkln("Transaction has enough signature weight for all operations");
+    }
+    for m in missing {
+        print.warnln(format!("Missing signatures: {m}"));
+    }
+    Ok(())
 }
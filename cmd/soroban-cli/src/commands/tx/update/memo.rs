@@ -0,0 +1,43 @@
+use std::ffi::OsString;
+
+use crate::{
+    commands::{
+        global,
+        tx::{memo, print_edited, xdr},
+    },
+    print::Print,
+    xdr::TransactionEnvelope,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    TxXdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Xdr(#[from] crate::xdr::Error),
+    #[error(transparent)]
+    Memo(#[from] memo::Error),
+}
+
+/// e.g. `stellar tx update memo --memo-id 1234 tx.xdr`
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub memo: memo::Args,
+    /// Base-64 transaction envelope XDR or file containing XDR to decode, or stdin if empty
+    #[arg()]
+    pub tx_xdr: Option<OsString>,
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let tx_env = xdr::tx_envelope_from_input(&self.tx_xdr)?;
+        let signatures = xdr::envelope_signatures(&tx_env).len();
+        let mut tx = xdr::unwrap_envelope_v1(tx_env)?;
+        tx.memo = self.memo.memo()?;
+        let tx_env: TransactionEnvelope = tx.into();
+        print_edited(&Print::new(global_args.quiet), signatures, &tx_env)?;
+        Ok(())
+    }
+}
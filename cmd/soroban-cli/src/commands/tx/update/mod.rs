@@ -1,9 +1,15 @@
 use super::global;
 
+pub mod memo;
+pub mod preconditions;
 pub mod sequence_number;
 
 #[derive(Debug, clap::Subcommand)]
 pub enum Cmd {
+    /// Set the memo of a transaction, or remove it if no memo is given
+    Memo(memo::Cmd),
+    /// Set the time bounds, ledger bounds, min sequence number conditions or extra signers of a transaction
+    Preconditions(preconditions::Cmd),
     /// Edit the sequence number on a transaction
     #[command(subcommand, visible_alias = "seq-num")]
     SequenceNumber(sequence_number::Cmd),
@@ -11,6 +17,10 @@ pub enum Cmd {
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
+    #[error(transparent)]
+    Memo(#[from] memo::Error),
+    #[error(transparent)]
+    Preconditions(#[from] preconditions::Error),
     #[error(transparent)]
     SequenceNumber(#[from] sequence_number::Error),
 }
@@ -18,6 +28,8 @@ pub enum Error {
 impl Cmd {
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         match self {
+            Cmd::Memo(cmd) => cmd.run(global_args)?,
+            Cmd::Preconditions(cmd) => cmd.run(global_args)?,
             Cmd::SequenceNumber(cmd) => cmd.run(global_args).await?,
         }
         Ok(())
@@ -0,0 +1,49 @@
+use std::ffi::OsString;
+
+use crate::{
+    commands::{
+        global,
+        tx::{preconditions, print_edited, xdr},
+    },
+    print::Print,
+    xdr::{Preconditions, TransactionEnvelope},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    TxXdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Xdr(#[from] crate::xdr::Error),
+    #[error(transparent)]
+    Preconditions(#[from] preconditions::Error),
+}
+
+/// e.g. `stellar tx update preconditions --valid-until 1h tx.xdr`
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub preconditions: preconditions::Args,
+    /// Remove the preconditions the transaction already has before setting the given ones
+    #[arg(long)]
+    pub clear: bool,
+    /// Base-64 transaction envelope XDR or file containing XDR to decode, or stdin if empty
+    #[arg()]
+    pub tx_xdr: Option<OsString>,
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let tx_env = xdr::tx_envelope_from_input(&self.tx_xdr)?;
+        let signatures = xdr::envelope_signatures(&tx_env).len();
+        let mut tx = xdr::unwrap_envelope_v1(tx_env)?;
+        if self.clear {
+            tx.cond = Preconditions::None;
+        }
+        self.preconditions.apply(&mut tx, preconditions::now())?;
+        let tx_env: TransactionEnvelope = tx.into();
+        print_edited(&Print::new(global_args.quiet), signatures, &tx_env)?;
+        Ok(())
+    }
+}
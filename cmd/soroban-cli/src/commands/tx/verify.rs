@@ -0,0 +1,309 @@
+use std::ffi::OsString;
+
+use sha2::{Digest, Sha256};
+
+use crate::{
+    commands::global,
+    config::{locator, network},
+    signer::{self, authorization_payload},
+    tx::signatures,
+    utils::{fee_bump_transaction_hash, transaction_hash},
+    xdr::{
+        self, DecoratedSignature, FeeBumpTransactionEnvelope, FeeBumpTransactionInnerTx, Hash,
+        MuxedEd25519Account, OperationBody, ScAddress, ScVal, SorobanAddressCredentials,
+        SorobanAuthorizationEntry, SorobanCredentials, Transaction, TransactionEnvelope,
+        TransactionV1Envelope, Uint256,
+    },
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    XdrArgs(#[from] super::xdr::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Signer(#[from] signer::Error),
+    #[error("transaction v0 envelopes are not supported")]
+    V0EnvelopeNotSupported,
+    #[error("found {count} invalid signature(s)")]
+    InvalidSignatures { count: usize },
+}
+
+/// Check every signature of a transaction envelope, including the inner transaction of a fee bump
+/// and the signatures of Soroban authorization entries. Fails if any signature is invalid.
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Base-64 transaction envelope XDR, or file containing XDR to decode, or stdin if empty
+    #[arg()]
+    pub tx_xdr: Option<OsString>,
+    /// Public key or identity that may have signed, can be passed multiple times. The source accounts of the transaction and its operations, and the addresses of authorization entries, are always included
+    #[arg(long = "signer", value_name = "KEY")]
+    pub signers: Vec<String>,
+    #[command(flatten)]
+    pub network: network::Args,
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self, _global_args: &global::Args) -> Result<(), Error> {
+        let network = self.network.get(&self.locator)?;
+        let tx_env = super::xdr::tx_envelope_from_input(&self.tx_xdr)?;
+        let mut keys = Vec::new();
+        for key in &self.signers {
+            let account_id = self.locator.get_public_key(key, None)?.account_id();
+            keys.push(signatures::account_key(&account_id));
+        }
+        let mut report = Report {
+            network_passphrase: network.network_passphrase,
+            keys,
+            invalid: 0,
+        };
+        match &tx_env {
+            TransactionEnvelope::Tx(envelope) => report.transaction(envelope)?,
+            TransactionEnvelope::TxFeeBump(envelope) => report.fee_bump(envelope)?,
+            TransactionEnvelope::TxV0(_) => return Err(Error::V0EnvelopeNotSupported),
+        }
+        if report.invalid > 0 {
+            return Err(Error::InvalidSignatures {
+                count: report.invalid,
+            });
+        }
+        Ok(())
+    }
+}
+
+struct Report {
+    network_passphrase: String,
+    keys: Vec<[u8; 32]>,
+    invalid: usize,
+}
+
+impl Report {
+    fn fee_bump(&mut self, envelope: &FeeBumpTransactionEnvelope) -> Result<(), Error> {
+        let hash = fee_bump_transaction_hash(&envelope.tx, &self.network_passphrase)?;
+        let fee_source = signatures::account_key(&envelope.tx.fee_source.clone().account_id());
+        println!("Fee bump transaction {}", hex::encode(hash));
+        self.keys.push(fee_source);
+        self.signatures(&envelope.signatures, &hash, &fee_source, "fee source");
+        let FeeBumpTransactionInnerTx::Tx(inner) = &envelope.tx.inner_tx;
+        println!();
+        self.transaction(inner)
+    }
+
+    fn transaction(&mut self, envelope: &TransactionV1Envelope) -> Result<(), Error> {
+        let TransactionV1Envelope {
+            tx,
+            signatures: sigs,
+        } = envelope;
+        let hash = transaction_hash(tx, &self.network_passphrase)?;
+        println!("Transaction {}", hex::encode(hash));
+        for account_id in signatures::source_accounts(tx) {
+            self.keys.push(signatures::account_key(&account_id));
+        }
+        let source = signatures::account_key(&tx.source_account.clone().account_id());
+        self.signatures(sigs, &hash, &source, "source account");
+        self.authorizations(tx)
+    }
+
+    fn signatures(
+        &mut self,
+        sigs: &[DecoratedSignature],
+        payload: &[u8; 32],
+        source: &[u8; 32],
+        source_name: &str,
+    ) {
+        let mut signed_by_source = false;
+        for (i, signature) in sigs.iter().enumerate() {
+            let hint = hex::encode(signature.hint.0);
+            if let Some(key) = signatures::signer_of(&self.keys, payload, signature) {
+                signed_by_source |= key == source;
+                println!("Signature {i}: valid, signed by {}", address(key));
+            } else if let Some(key) = self
+                .keys
+                .iter()
+                .find(|k| signatures::hint(k) == signature.hint)
+            {
+                self.invalid += 1;
+                println!(
+                    "Signature {i}: invalid, hint {hint} matches {}",
+                    address(key)
+                );
+            } else {
+                println!("Signature {i}: unknown signer, hint {hint}");
+            }
+        }
+        let signed = if signed_by_source { "yes" } else { "no" };
+        println!("Signed by {source_name} {}: {signed}", address(source));
+    }
+
+    fn authorizations(&mut self, tx: &Transaction) -> Result<(), Error> {
+        let network_id = Hash(Sha256::digest(self.network_passphrase.as_bytes()).into());
+        for (op_index, op) in tx.operations.iter().enumerate() {
+            let OperationBody::InvokeHostFunction(body) = &op.body else {
+                continue;
+            };
+            for (i, entry) in body.auth.iter().enumerate() {
+                let SorobanAuthorizationEntry {
+                    credentials: SorobanCredentials::Address(credentials),
+                    root_invocation,
+                } = entry
+                else {
+                    continue;
+                };
+                let SorobanAddressCredentials {
+                    address: auth_address,
+                    nonce,
+                    signature_expiration_ledger,
+                    signature,
+                } = credentials;
+                print!(
+                    "Authorization entry {i} of operation {op_index} for {auth_address}, expiring at ledger {signature_expiration_ledger}: "
+                );
+                let account = match auth_address {
+                    ScAddress::Account(account_id) => signatures::account_key(account_id),
+                    ScAddress::MuxedAccount(MuxedEd25519Account {
+                        ed25519: Uint256(key),
+                        ..
+                    }) => *key,
+                    _ => {
+                        println!("signature not verified, only account signatures are supported");
+                        continue;
+                    }
+                };
+                let payload = authorization_payload(
+                    root_invocation,
+                    *nonce,
+                    *signature_expiration_ledger,
+                    &network_id,
+                )?;
+                self.account_authorization(&account, &payload, signature);
+            }
+        }
+        Ok(())
+    }
+
+    fn account_authorization(&mut self, account: &[u8; 32], payload: &[u8; 32], signature: &ScVal) {
+        let Some(pairs) = account_auth_signatures(signature) else {
+            self.invalid += 1;
+            println!("invalid, malformed signature");
+            return;
+        };
+        if pairs.is_empty() {
+            println!("not signed");
+            return;
+        }
+        println!();
+        for (i, (public_key, sig)) in pairs.iter().enumerate() {
+            let validity = if signatures::verify_ed25519(public_key, payload, sig) {
+                "valid"
+            } else {
+                self.invalid += 1;
+                "invalid"
+            };
+            let by = if public_key == account {
+                "the account".to_string()
+            } else {
+                address(public_key)
+            };
+            println!("  Signature {i}: {validity}, signed by {by}");
+        }
+    }
+}
+
+fn address(key: &[u8; 32]) -> String {
+    stellar_strkey::ed25519::PublicKey(*key).to_string()
+}
+
+/// The `(public_key, signature)` pairs of an account's authorization signature, `None` if it
+/// isn't in the format accounts use.
+fn account_auth_signatures(val: &ScVal) -> Option<Vec<([u8; 32], Vec<u8>)>> {
+    let items = match val {
+        ScVal::Void => return Some(Vec::new()),
+        ScVal::Vec(Some(items)) => items,
+        _ => return None,
+    };
+    items
+        .iter()
+        .map(|item| {
+            let ScVal::Map(Some(map)) = item else {
+                return None;
+            };
+            let field = |name: &str| {
+                map.iter()
+                    .find(
+                        |e| matches!(&e.key, ScVal::Symbol(s) if s.0.as_slice() == name.as_bytes()),
+                    )
+                    .map(|e| &e.val)
+            };
+            let (Some(ScVal::Bytes(public_key)), Some(ScVal::Bytes(signature))) =
+                (field("public_key"), field("signature"))
+            else {
+                return None;
+            };
+            Some((public_key.as_slice().try_into().ok()?, signature.to_vec()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdr::{ScMap, ScMapEntry, ScSymbol};
+    use ed25519_dalek::{Signer as _, SigningKey};
+
+    fn entry(key: &str, val: ScVal) -> ScMapEntry {
+        ScMapEntry {
+            key: ScVal::Symbol(ScSymbol(key.try_into().unwrap())),
+            val,
+        }
+    }
+
+    #[test]
+    fn account_auth_signature_is_verified() {
+        let key = SigningKey::from_bytes(&[1; 32]);
+        let public_key = key.verifying_key().to_bytes();
+        let payload = [9; 32];
+        let signature = ScVal::Vec(Some(
+            vec![ScVal::Map(Some(ScMap(
+                vec![
+                    entry(
+                        "public_key",
+                        ScVal::Bytes(public_key.to_vec().try_into().unwrap()),
+                    ),
+                    entry(
+                        "signature",
+                        ScVal::Bytes(key.sign(&payload).to_bytes().to_vec().try_into().unwrap()),
+                    ),
+                ]
+                .try_into()
+                .unwrap(),
+            )))]
+            .try_into()
+            .unwrap(),
+        ));
+
+        let pairs = account_auth_signatures(&signature).unwrap();
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].0, public_key);
+        assert!(signatures::verify_ed25519(
+            &pairs[0].0,
+            &payload,
+            &pairs[0].1
+        ));
+        assert!(!signatures::verify_ed25519(
+            &pairs[0].0,
+            &[8; 32],
+            &pairs[0].1
+        ));
+
+        assert_eq!(account_auth_signatures(&ScVal::Void), Some(Vec::new()));
+        assert_eq!(account_auth_signatures(&ScVal::U32(1)), None);
+    }
+}
@@ -1,5 +1,9 @@
-use crate::xdr::{
-    Limits, Operation, ReadXdr, Transaction, TransactionEnvelope, TransactionV1Envelope,
+use crate::{
+    utils::{fee_bump_transaction_hash, transaction_hash},
+    xdr::{
+        DecoratedSignature, FeeBumpTransactionEnvelope, Limits, MuxedAccount, Operation, ReadXdr,
+        Transaction, TransactionEnvelope, TransactionV1Envelope,
+    },
 };
 use std::ffi::OsString;
 use std::fs::File;
@@ -20,6 +24,12 @@ pub enum Error {
     TooManyOperations,
     #[error("no transaction provided")]
     NoStdin,
+    #[error("transaction v0 envelopes are not supported")]
+    V0NotSupported,
+    #[error("no operation at index {index}, the transaction has {count} operations")]
+    OperationIndexOutOfRange { index: usize, count: usize },
+    #[error("cannot remove the only operation of a transaction")]
+    LastOperation,
 }
 
 pub fn tx_envelope_from_input(input: &Option<OsString>) -> Result<TransactionEnvelope, Error> {
@@ -76,6 +86,32 @@ pub fn unwrap_envelope_v1(tx_env: TransactionEnvelope) -> Result<Transaction, Er
     Ok(tx)
 }
 
+/// The hash signed by the envelope's signatures. For a fee bump this is the hash of the fee bump
+/// transaction, which is also the hash the network reports, not the hash of the inner transaction.
+pub fn envelope_hash(
+    tx_env: &TransactionEnvelope,
+    network_passphrase: &str,
+) -> Result<[u8; 32], Error> {
+    match tx_env {
+        TransactionEnvelope::Tx(TransactionV1Envelope { tx, .. }) => {
+            Ok(transaction_hash(tx, network_passphrase)?)
+        }
+        TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope { tx, .. }) => {
+            Ok(fee_bump_transaction_hash(tx, network_passphrase)?)
+        }
+        TransactionEnvelope::TxV0(_) => Err(Error::V0NotSupported),
+    }
+}
+
+/// The outer signatures of the envelope, those of the fee source for a fee bump.
+pub fn envelope_signatures(tx_env: &TransactionEnvelope) -> &[DecoratedSignature] {
+    match tx_env {
+        TransactionEnvelope::TxV0(env) => &env.signatures,
+        TransactionEnvelope::Tx(env) => &env.signatures,
+        TransactionEnvelope::TxFeeBump(env) => &env.signatures,
+    }
+}
+
 pub fn add_op(tx_env: TransactionEnvelope, op: Operation) -> Result<TransactionEnvelope, Error> {
     let mut tx = unwrap_envelope_v1(tx_env)?;
     let mut ops = tx.operations.to_vec();
@@ -83,3 +119,127 @@ pub fn add_op(tx_env: TransactionEnvelope, op: Operation) -> Result<TransactionE
     tx.operations = ops.try_into().map_err(|_| Error::TooManyOperations)?;
     Ok(tx.into())
 }
+
+pub fn remove_op(tx_env: TransactionEnvelope, index: usize) -> Result<TransactionEnvelope, Error> {
+    let mut tx = unwrap_envelope_v1(tx_env)?;
+    let mut ops = tx.operations.to_vec();
+    check_op_index(&ops, index)?;
+    if ops.len() == 1 {
+        return Err(Error::LastOperation);
+    }
+    ops.remove(index);
+    tx.operations = ops.try_into().map_err(|_| Error::TooManyOperations)?;
+    Ok(tx.into())
+}
+
+/// Move the operation at `from` so that it ends up at index `to`, shifting the ones in between.
+pub fn move_op(
+    tx_env: TransactionEnvelope,
+    from: usize,
+    to: usize,
+) -> Result<TransactionEnvelope, Error> {
+    let mut tx = unwrap_envelope_v1(tx_env)?;
+    let mut ops = tx.operations.to_vec();
+    check_op_index(&ops, from)?;
+    check_op_index(&ops, to)?;
+    let op = ops.remove(from);
+    ops.insert(to, op);
+    tx.operations = ops.try_into().map_err(|_| Error::TooManyOperations)?;
+    Ok(tx.into())
+}
+
+/// Set the source account of the operation at `index`, or remove it so the operation uses the
+/// transaction's source account.
+pub fn set_op_source(
+    tx_env: TransactionEnvelope,
+    index: usize,
+    source_account: Option<MuxedAccount>,
+) -> Result<TransactionEnvelope, Error> {
+    let mut tx = unwrap_envelope_v1(tx_env)?;
+    let mut ops = tx.operations.to_vec();
+    check_op_index(&ops, index)?;
+    ops[index].source_account = source_account;
+    tx.operations = ops.try_into().map_err(|_| Error::TooManyOperations)?;
+    Ok(tx.into())
+}
+
+fn check_op_index(ops: &[Operation], index: usize) -> Result<(), Error> {
+    if index >= ops.len() {
+        return Err(Error::OperationIndexOutOfRange {
+            index,
+            count: ops.len(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdr::{
+        BumpSequenceOp, Memo, OperationBody, Preconditions, SequenceNumber, TransactionExt, Uint256,
+    };
+
+    fn tx_env(ops: &[i64]) -> TransactionEnvelope {
+        Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256([1; 32])),
+            fee: 100,
+            seq_num: SequenceNumber(1),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: ops
+                .iter()
+                .map(|bump_to| Operation {
+                    source_account: None,
+                    body: OperationBody::BumpSequence(BumpSequenceOp {
+                        bump_to: SequenceNumber(*bump_to),
+                    }),
+                })
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+            ext: TransactionExt::V0,
+        }
+        .into()
+    }
+
+    fn bumps(tx_env: TransactionEnvelope) -> Vec<i64> {
+        unwrap_envelope_v1(tx_env)
+            .unwrap()
+            .operations
+            .iter()
+            .map(|op| match &op.body {
+                OperationBody::BumpSequence(op) => op.bump_to.0,
+                _ => panic!("expected a bump sequence operation"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn remove_and_move_operations() {
+        assert_eq!(bumps(remove_op(tx_env(&[0, 1, 2]), 1).unwrap()), [0, 2]);
+        assert_eq!(bumps(move_op(tx_env(&[0, 1, 2]), 0, 2).unwrap()), [1, 2, 0]);
+        assert_eq!(bumps(move_op(tx_env(&[0, 1, 2]), 2, 0).unwrap()), [2, 0, 1]);
+        assert!(matches!(
+            remove_op(tx_env(&[0, 1]), 2),
+            Err(Error::OperationIndexOutOfRange { index: 2, count: 2 })
+        ));
+        assert!(matches!(
+            remove_op(tx_env(&[0]), 0),
+            Err(Error::LastOperation)
+        ));
+    }
+
+    #[test]
+    fn set_and_clear_operation_source() {
+        let source = MuxedAccount::Ed25519(Uint256([2; 32]));
+        let tx = set_op_source(tx_env(&[0, 1]), 1, Some(source.clone())).unwrap();
+        let ops = unwrap_envelope_v1(tx.clone()).unwrap().operations;
+        assert_eq!(ops[0].source_account, None);
+        assert_eq!(ops[1].source_account, Some(source));
+        let ops = unwrap_envelope_v1(set_op_source(tx, 1, None).unwrap())
+            .unwrap()
+            .operations;
+        assert_eq!(ops[1].source_account, None);
+    }
+}
@@ -7,6 +7,17 @@ use super::locator;
 #[derive(Serialize, Deserialize, Default)]
 pub struct Data {
     pub ids: HashMap<String, String>,
+    /// Init functions of a manifest called on the contract, by network passphrase
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub init: HashMap<String, Init>,
+}
+
+/// Init functions called on a contract deployed with `contract deploy --manifest`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Init {
+    pub contract_id: String,
+    /// Number of init functions of the manifest called, in order
+    pub calls: usize,
 }
 
 /// Address can be either a contract address, C.. or eventually an alias of a contract address.
@@ -0,0 +1,209 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::{
+    print::Print,
+    signer,
+    xdr::{self, MuxedAccount, SequenceNumber, Transaction, TransactionEnvelope, Uint256},
+};
+
+use super::{data, locator, network::Network, secret::Secret};
+
+/// A lock older than this is left over by a command that didn't release it, such as one that was
+/// killed, and is taken over. Commands only hold a lease while they build, sign and send one
+/// transaction.
+const STALE_AFTER: Duration = Duration::from_secs(10 * 60);
+
+/// How long to wait before trying the channel accounts again when they are all leased.
+const RETRY_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Secret(#[from] super::secret::Error),
+    #[error(transparent)]
+    Signer(#[from] signer::Error),
+    #[error(transparent)]
+    Data(#[from] data::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error("cannot lock channel account {path:?}: {error}")]
+    Lock { path: PathBuf, error: io::Error },
+    #[error("no channel account became free within {timeout:?}, all {count} are leased by other commands")]
+    NoFreeChannel { count: usize, timeout: Duration },
+}
+
+/// Channel accounts are used as the source account of transactions in place of the account they
+/// act for, so that concurrent commands don't compete for that account's sequence number.
+#[derive(Debug, clap::Args, Clone, Default)]
+#[group(skip)]
+pub struct Args {
+    /// Identity or secret key of a channel account to use as the transaction source, with the source account becoming the source of the operations. The channel account pays the fee and signs the transaction too. Give several, comma-separated or by repeating the option, to let concurrent commands share them, each command leasing a free one through a lock file in the data directory
+    #[arg(long = "channel", env = "STELLAR_CHANNELS", value_delimiter = ',')]
+    pub channels: Vec<String>,
+    /// How long to wait for a channel account to be free when all are leased, e.g. `30s`
+    #[arg(long, default_value = "60s", value_parser = humantime::parse_duration)]
+    pub channel_timeout: Duration,
+}
+
+/// A channel account leased by this process, released when dropped.
+#[derive(Debug)]
+pub struct Lease {
+    pub name: String,
+    pub account: MuxedAccount,
+    secret: Secret,
+    lock: PathBuf,
+}
+
+impl Args {
+    pub fn is_set(&self) -> bool {
+        !self.channels.is_empty()
+    }
+
+    /// Lease one of the channel accounts, waiting for one to be free if all are leased.
+    /// Returns `None` if no channel account was given.
+    pub async fn lease(&self, locator: &locator::Args) -> Result<Option<Lease>, Error> {
+        if !self.is_set() {
+            return Ok(None);
+        }
+        let mut channels = Vec::with_capacity(self.channels.len());
+        for name in &self.channels {
+            let secret = locator.get_secret_key(name)?;
+            let public_key = secret.public_key(None)?;
+            channels.push((name, secret, MuxedAccount::Ed25519(Uint256(public_key.0))));
+        }
+        // Start from a different channel in each process so concurrent commands don't all race
+        // for the first one.
+        let start = std::process::id() as usize % channels.len();
+        let started = Instant::now();
+        loop {
+            for (name, secret, account) in channels.iter().cycle().skip(start).take(channels.len())
+            {
+                if let Some(lock) = try_lock(account)? {
+                    return Ok(Some(Lease {
+                        name: (*name).clone(),
+                        account: account.clone(),
+                        secret: secret.clone(),
+                        lock,
+                    }));
+                }
+            }
+            if started.elapsed() >= self.channel_timeout {
+                return Err(Error::NoFreeChannel {
+                    count: channels.len(),
+                    timeout: self.channel_timeout,
+                });
+            }
+            tokio::time::sleep(RETRY_INTERVAL).await;
+        }
+    }
+}
+
+impl Lease {
+    /// Make the channel account the source of `tx`, with sequence number `seq_num`. Operations
+    /// without a source account get the previous source account of the transaction.
+    pub fn apply(&self, tx: &mut Transaction, seq_num: SequenceNumber) -> Result<(), Error> {
+        let source = std::mem::replace(&mut tx.source_account, self.account.clone());
+        let mut operations = tx.operations.to_vec();
+        for op in &mut operations {
+            op.source_account.get_or_insert_with(|| source.clone());
+        }
+        tx.operations = operations.try_into()?;
+        tx.seq_num = seq_num;
+        Ok(())
+    }
+
+    /// Add the signature of the channel account to the envelope.
+    pub async fn sign(
+        &self,
+        tx_env: &TransactionEnvelope,
+        network: &Network,
+        quiet: bool,
+    ) -> Result<TransactionEnvelope, Error> {
+        let signer = self.secret.signer(None, Print::new(quiet)).await?;
+        Ok(signer.sign_tx_env(tx_env, network).await?)
+    }
+}
+
+impl Drop for Lease {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.lock);
+    }
+}
+
+/// Create the lock file of `account`, taking over a stale one. Returns `None` if another command
+/// holds the lock.
+fn try_lock(account: &MuxedAccount) -> Result<Option<PathBuf>, Error> {
+    let path = data::channels_dir()?.join(format!("{}.lock", account.clone().account_id()));
+    for _ in 0..2 {
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut file) => {
+                writeln!(file, "{}", std::process::id()).map_err(|error| Error::Lock {
+                    path: path.clone(),
+                    error,
+                })?;
+                return Ok(Some(path));
+            }
+            Err(error) if error.kind() == io::ErrorKind::AlreadyExists => {
+                if !is_stale(&path) {
+                    return Ok(None);
+                }
+                tracing::debug!("taking over stale channel lock {path:?}");
+                let _ = fs::remove_file(&path);
+            }
+            Err(error) => return Err(Error::Lock { path, error }),
+        }
+    }
+    Ok(None)
+}
+
+fn is_stale(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age > STALE_AFTER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdr::{Memo, Operation, OperationBody, Preconditions, TransactionExt};
+
+    #[test]
+    fn channel_becomes_the_transaction_source() {
+        let source = MuxedAccount::Ed25519(Uint256([1; 32]));
+        let other = MuxedAccount::Ed25519(Uint256([2; 32]));
+        let channel = MuxedAccount::Ed25519(Uint256([3; 32]));
+        let op = |source_account| Operation {
+            source_account,
+            body: OperationBody::Inflation,
+        };
+        let mut tx = Transaction {
+            source_account: source.clone(),
+            fee: 100,
+            seq_num: SequenceNumber(5),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: vec![op(None), op(Some(other.clone()))].try_into().unwrap(),
+            ext: TransactionExt::V0,
+        };
+        let lease = Lease {
+            name: "channel".to_string(),
+            account: channel.clone(),
+            secret: Secret::from_seed(None).unwrap(),
+            lock: PathBuf::new(),
+        };
+        lease.apply(&mut tx, SequenceNumber(42)).unwrap();
+        assert_eq!(tx.source_account, channel);
+        assert_eq!(tx.seq_num, SequenceNumber(42));
+        assert_eq!(tx.operations[0].source_account, Some(source));
+        assert_eq!(tx.operations[1].source_account, Some(other));
+    }
+}
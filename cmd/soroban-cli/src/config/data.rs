@@ -1,4 +1,7 @@
-use crate::rpc::{GetTransactionResponse, GetTransactionResponseRaw, SimulateTransactionResponse};
+use crate::rpc::{
+    GetTransactionResponse, GetTransactionResponseRaw, SendTransactionResponse,
+    SimulateTransactionResponse,
+};
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
@@ -57,6 +60,12 @@ pub fn bucket_dir() -> Result<std::path::PathBuf, Error> {
     Ok(dir)
 }
 
+pub fn channels_dir() -> Result<std::path::PathBuf, Error> {
+    let dir = data_local_dir()?.join("channels");
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
 pub fn write(action: Action, rpc_url: &Url) -> Result<ulid::Ulid, Error> {
     let data = Data {
         action,
@@ -130,6 +139,7 @@ impl std::fmt::Display for DatedAction {
                 .as_ref()
                 .map_or_else(|| "SUCCESS".to_string(), |_| "ERROR".to_string()),
             Action::Send { response } => response.status.clone(),
+            Action::Submit { response, .. } => response.status.clone(),
         };
         write!(f, "{id} {} {status} {datetime} {uri} ", a.type_str(),)
     }
@@ -157,6 +167,11 @@ pub enum Action {
     Send {
         response: GetTransactionResponseRaw,
     },
+    /// One submission of a transaction, which the network may accept, reject or ask to retry.
+    Submit {
+        attempt: u32,
+        response: SendTransactionResponse,
+    },
 }
 
 impl Action {
@@ -164,6 +179,7 @@ impl Action {
         match self {
             Action::Simulate { .. } => "Simulate",
             Action::Send { .. } => "Send    ",
+            Action::Submit { .. } => "Submit  ",
         }
         .to_string()
     }
@@ -144,6 +144,7 @@ impl TryFrom<Key> for Secret {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::config::secret::encrypted::Encrypted;
 
     fn round_trip(key: &Key) {
         let serialized = toml::to_string(&key).unwrap();
@@ -180,4 +181,13 @@ mod test {
         let key = Key::Secret(secret);
         round_trip(&key);
     }
+    #[test]
+    fn secret_encrypted() {
+        let secret_key = stellar_strkey::ed25519::PrivateKey([0; 32]).to_string();
+        let public_key = stellar_strkey::ed25519::PublicKey([0; 32]).to_string();
+        let encrypted =
+            Encrypted::encrypt_with_cost(&secret_key, &public_key, "passphrase", 4).unwrap();
+        let key = Key::Secret(Secret::Encrypted { encrypted });
+        round_trip(&key);
+    }
 }
@@ -424,12 +424,41 @@ impl Args {
             .open(path)?;
 
         data.ids.remove::<str>(network_passphrase);
+        data.init.remove::<str>(network_passphrase);
 
         let content = serde_json::to_string(&data)?;
 
         Ok(to_file.write_all(content.as_bytes())?)
     }
 
+    /// Record the init functions of a manifest called on the contract with an alias.
+    pub fn save_contract_init(
+        &self,
+        network_passphrase: &str,
+        alias: &str,
+        init: alias::Init,
+    ) -> Result<(), Error> {
+        let path = self.alias_path(alias)?;
+        let content = fs::read_to_string(&path).unwrap_or_default();
+        let mut data: alias::Data = serde_json::from_str(&content).unwrap_or_default();
+
+        data.init.insert(network_passphrase.into(), init);
+
+        let content = serde_json::to_string(&data)?;
+
+        Ok(fs::write(path, content)?)
+    }
+
+    pub fn get_contract_init(
+        &self,
+        alias: &str,
+        network_passphrase: &str,
+    ) -> Result<Option<alias::Init>, Error> {
+        Ok(self
+            .load_contract_from_alias(alias)?
+            .and_then(|mut data| data.init.remove(network_passphrase)))
+    }
+
     pub fn get_contract_id(
         &self,
         alias: &str,
@@ -460,6 +489,30 @@ impl Args {
         Ok(contract)
     }
 
+    /// Every contract alias saved in the config directory, with its contract ids by network
+    /// passphrase.
+    pub fn list_contract_aliases(&self) -> Result<Vec<(String, alias::Data)>, Error> {
+        let dir = self.config_dir()?.join("contract-ids");
+        if !dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut aliases = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension() != Some(OsStr::new("json")) {
+                continue;
+            }
+            let Some(alias) = path.file_stem() else {
+                continue;
+            };
+            let content = fs::read_to_string(&path)?;
+            let data: alias::Data = serde_json::from_str(&content)?;
+            aliases.push((alias.to_string_lossy().into_owned(), data));
+        }
+        aliases.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Ok(aliases)
+    }
+
     pub fn global_config_path(&self) -> Result<PathBuf, Error> {
         if let Some(config_dir) = &self.config_dir {
             return Ok(config_dir.clone());
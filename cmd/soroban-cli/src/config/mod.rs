@@ -6,7 +6,7 @@ use std::{
 
 use crate::{
     print::Print,
-    signer::{self, Signer},
+    signer::{self, custom_account::CustomAccount, Signer},
     utils::deprecate_message,
     xdr::{self, SequenceNumber, Transaction, TransactionEnvelope, TransactionV1Envelope, VecM},
     Pwd,
@@ -15,6 +15,7 @@ use network::Network;
 
 pub mod address;
 pub mod alias;
+pub mod channels;
 pub mod data;
 pub mod key;
 pub mod locator;
@@ -120,6 +121,7 @@ impl Args {
         &self,
         tx: &Transaction,
         signers: &[Signer],
+        custom_accounts: &[CustomAccount],
     ) -> Result<Option<Transaction>, Error> {
         let network = self.get_network()?;
         let source_signer = self.source_signer().await?;
@@ -130,15 +132,21 @@ impl Args {
             tx,
             &source_signer,
             signers,
+            custom_accounts,
             seq_num,
             &network.network_passphrase,
-        )?)
+        )
+        .await?)
     }
 
     pub fn get_network(&self) -> Result<Network, Error> {
         Ok(self.network.get(&self.locator)?)
     }
 
+    pub fn get_network_passphrase(&self) -> Result<String, Error> {
+        Ok(self.network.get_passphrase(&self.locator)?)
+    }
+
     /// Get the inclusion fee if available from args, otherwise fall back to fee,
     /// and finally return 100 if nothing is set.
     ///
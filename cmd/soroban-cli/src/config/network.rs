@@ -117,6 +117,15 @@ impl Args {
             }),
         }
     }
+
+    /// The network passphrase, which unlike the network doesn't need an RPC URL when given
+    /// explicitly, to build transactions offline.
+    pub fn get_passphrase(&self, locator: &locator::Args) -> Result<String, Error> {
+        match &self.network_passphrase {
+            Some(network_passphrase) => Ok(network_passphrase.clone()),
+            None => Ok(self.get(locator)?.network_passphrase),
+        }
+    }
 }
 
 #[derive(Debug, clap::Args, Serialize, Deserialize, Clone)]
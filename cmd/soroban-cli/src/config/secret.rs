@@ -12,6 +12,10 @@ use crate::{
 
 use super::key::Key;
 
+pub mod encrypted;
+pub mod shamir;
+use encrypted::Encrypted;
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error(transparent)]
@@ -32,10 +36,15 @@ pub enum Error {
     SecureStoreDoesNotRevealSecretKey,
     #[error(transparent)]
     Ledger(#[from] signer::ledger::Error),
+    #[error(transparent)]
+    Encrypted(#[from] encrypted::Error),
+    #[error("only secret keys and seed phrases can be encrypted")]
+    CannotEncrypt,
 }
 
 #[derive(Debug, clap::Args, Clone)]
 #[group(skip)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Args {
     /// ⚠️ Deprecated, use `--secure-store`. Enter secret (S) key when prompted
     #[arg(long)]
@@ -52,6 +61,10 @@ pub struct Args {
     /// This only supports seed phrases for now.
     #[arg(long)]
     pub secure_store: bool,
+
+    /// Encrypt the key with a passphrase before saving it in the config directory. The passphrase is read from `STELLAR_IDENTITY_PASSPHRASE`, or prompted for.
+    #[arg(long, conflicts_with = "secure_store")]
+    pub encrypt: bool,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -61,6 +74,7 @@ pub enum Secret {
     SeedPhrase { seed_phrase: String },
     Ledger,
     SecureStore { entry_name: String },
+    Encrypted { encrypted: Encrypted },
 }
 
 impl FromStr for Secret {
@@ -123,12 +137,15 @@ impl Secret {
             Secret::SecureStore { .. } => {
                 return Err(Error::SecureStoreDoesNotRevealSecretKey);
             }
+            Secret::Encrypted { .. } => self.decrypt()?.private_key(index)?,
         })
     }
 
     pub fn public_key(&self, index: Option<usize>) -> Result<PublicKey, Error> {
         if let Secret::SecureStore { entry_name } = self {
             Ok(secure_store::get_public_key(entry_name, index)?)
+        } else if let (Secret::Encrypted { encrypted }, None | Some(0)) = (self, index) {
+            Ok(PublicKey::from_string(&encrypted.public_key)?)
         } else {
             let key = self.key_pair(index)?;
             Ok(stellar_strkey::ed25519::PublicKey::from_payload(
@@ -139,7 +156,7 @@ impl Secret {
 
     pub async fn signer(&self, hd_path: Option<usize>, print: Print) -> Result<Signer, Error> {
         let kind = match self {
-            Secret::SecretKey { .. } | Secret::SeedPhrase { .. } => {
+            Secret::SecretKey { .. } | Secret::SeedPhrase { .. } | Secret::Encrypted { .. } => {
                 let key = self.key_pair(hd_path)?;
                 SignerKind::Local(LocalKey { key })
             }
@@ -165,6 +182,41 @@ impl Secret {
     pub fn from_seed(seed: Option<&str>) -> Result<Self, Error> {
         Ok(seed_phrase_from_seed(seed)?.into())
     }
+
+    /// Encrypt a secret key or seed phrase with a passphrase, see [`Encrypted`].
+    pub fn encrypt(&self, passphrase: &str) -> Result<Self, Error> {
+        let plaintext = match self {
+            Secret::SecretKey { secret_key } => secret_key,
+            Secret::SeedPhrase { seed_phrase } => seed_phrase,
+            _ => return Err(Error::CannotEncrypt),
+        };
+        let public_key = self.public_key(None)?.to_string();
+        Ok(Secret::Encrypted {
+            encrypted: Encrypted::encrypt(plaintext, &public_key, passphrase)?,
+        })
+    }
+
+    /// Decrypt an encrypted secret, reading the passphrase from `STELLAR_IDENTITY_PASSPHRASE`
+    /// or prompting for it. Other secrets are returned as is.
+    pub fn decrypt(&self) -> Result<Self, Error> {
+        let Secret::Encrypted { encrypted } = self else {
+            return Ok(self.clone());
+        };
+        let passphrase =
+            encrypted::read_passphrase(&format!("Passphrase for {}", encrypted.public_key), false)?;
+        self.decrypt_with(&passphrase)
+    }
+
+    pub fn decrypt_with(&self, passphrase: &str) -> Result<Self, Error> {
+        let Secret::Encrypted { encrypted } = self else {
+            return Ok(self.clone());
+        };
+        let plaintext = encrypted.decrypt(passphrase)?;
+        match plaintext.parse()? {
+            secret @ (Secret::SecretKey { .. } | Secret::SeedPhrase { .. }) => Ok(secret),
+            _ => Err(Error::InvalidSecretOrSeedPhrase),
+        }
+    }
 }
 
 pub fn seed_phrase_from_seed(seed: Option<&str>) -> Result<SeedPhrase, Error> {
@@ -220,6 +272,25 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_encrypted_secret() {
+        let secret = Secret::from_str(TEST_SEED_PHRASE).unwrap();
+        let encrypted = Secret::Encrypted {
+            encrypted: Encrypted::encrypt_with_cost(TEST_SEED_PHRASE, TEST_PUBLIC_KEY, "pass", 4)
+                .unwrap(),
+        };
+        assert_eq!(
+            encrypted.public_key(None).unwrap().to_string(),
+            TEST_PUBLIC_KEY
+        );
+        assert_eq!(encrypted.decrypt_with("pass").unwrap(), secret);
+        assert!(encrypted.decrypt_with("wrong").is_err());
+        assert!(matches!(
+            Secret::Ledger.encrypt("pass"),
+            Err(Error::CannotEncrypt)
+        ));
+    }
+
     #[test]
     fn test_secret_from_invalid_string() {
         let secret = Secret::from_str("invalid");
@@ -0,0 +1,249 @@
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    XChaCha20Poly1305, XNonce,
+};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+/// Environment variable read for the passphrase before prompting for it.
+pub const PASSPHRASE_ENV: &str = "STELLAR_IDENTITY_PASSPHRASE";
+
+const VERSION: u32 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("unsupported encrypted identity version {0}")]
+    UnsupportedVersion(u32),
+    #[error("invalid key derivation parameters")]
+    InvalidKdfParams,
+    #[error("wrong passphrase, or the encrypted identity is corrupted")]
+    Decryption,
+    #[error("failed to encrypt identity")]
+    Encryption,
+    #[error(transparent)]
+    Hex(#[from] hex::FromHexError),
+    #[error("passphrase input error")]
+    PassphraseRead,
+    #[error("passphrases do not match")]
+    PassphraseMismatch,
+    #[error("passphrase cannot be empty")]
+    EmptyPassphrase,
+}
+
+/// Parameters of the scrypt key derivation, stored with the ciphertext so they can be raised
+/// later without breaking existing identities.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Scrypt {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+    pub salt: String,
+}
+
+impl Scrypt {
+    fn new(log_n: u8) -> Self {
+        let mut salt = [0u8; SALT_LEN];
+        rand::thread_rng().fill_bytes(&mut salt);
+        Scrypt {
+            log_n,
+            r: 8,
+            p: 1,
+            salt: hex::encode(salt),
+        }
+    }
+
+    fn derive_key(&self, passphrase: &str) -> Result<Zeroizing<[u8; 32]>, Error> {
+        let params = scrypt::Params::new(self.log_n, self.r, self.p, 32)
+            .map_err(|_| Error::InvalidKdfParams)?;
+        let mut key = Zeroizing::new([0u8; 32]);
+        scrypt::scrypt(
+            passphrase.as_bytes(),
+            &hex::decode(&self.salt)?,
+            &params,
+            key.as_mut(),
+        )
+        .map_err(|_| Error::InvalidKdfParams)?;
+        Ok(key)
+    }
+}
+
+/// Data encrypted with XChaCha20-Poly1305, using a key derived from a passphrase with scrypt.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Sealed {
+    pub scrypt: Scrypt,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+impl Sealed {
+    /// `aad` is authenticated along with the ciphertext but not encrypted, and must be passed
+    /// again to open it.
+    pub fn seal(plaintext: &[u8], aad: &[u8], passphrase: &str, log_n: u8) -> Result<Self, Error> {
+        let scrypt = Scrypt::new(log_n);
+        let key = scrypt.derive_key(passphrase)?;
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let ciphertext = XChaCha20Poly1305::new(key.as_ref().into())
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext,
+                    aad,
+                },
+            )
+            .map_err(|_| Error::Encryption)?;
+        Ok(Sealed {
+            scrypt,
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    pub fn open(&self, aad: &[u8], passphrase: &str) -> Result<Zeroizing<Vec<u8>>, Error> {
+        let key = self.scrypt.derive_key(passphrase)?;
+        let nonce = hex::decode(&self.nonce)?;
+        if nonce.len() != NONCE_LEN {
+            return Err(Error::Decryption);
+        }
+        let plaintext = XChaCha20Poly1305::new(key.as_ref().into())
+            .decrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: &hex::decode(&self.ciphertext)?,
+                    aad,
+                },
+            )
+            .map_err(|_| Error::Decryption)?;
+        Ok(Zeroizing::new(plaintext))
+    }
+}
+
+/// A secret key or seed phrase sealed with a passphrase.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Encrypted {
+    pub version: u32,
+    /// Address of the default hd path, so it can be looked up without the passphrase. It is
+    /// authenticated as part of the ciphertext.
+    pub public_key: String,
+    #[serde(flatten)]
+    pub sealed: Sealed,
+}
+
+impl Encrypted {
+    /// Recommended scrypt cost, about 32MB of memory.
+    pub const LOG_N: u8 = 15;
+
+    pub fn encrypt(plaintext: &str, public_key: &str, passphrase: &str) -> Result<Self, Error> {
+        Self::encrypt_with_cost(plaintext, public_key, passphrase, Self::LOG_N)
+    }
+
+    pub fn encrypt_with_cost(
+        plaintext: &str,
+        public_key: &str,
+        passphrase: &str,
+        log_n: u8,
+    ) -> Result<Self, Error> {
+        Ok(Encrypted {
+            version: VERSION,
+            public_key: public_key.to_string(),
+            sealed: Sealed::seal(
+                plaintext.as_bytes(),
+                public_key.as_bytes(),
+                passphrase,
+                log_n,
+            )?,
+        })
+    }
+
+    pub fn decrypt(&self, passphrase: &str) -> Result<Zeroizing<String>, Error> {
+        if self.version != VERSION {
+            return Err(Error::UnsupportedVersion(self.version));
+        }
+        let plaintext = self.sealed.open(self.public_key.as_bytes(), passphrase)?;
+        Ok(Zeroizing::new(
+            String::from_utf8(plaintext.to_vec()).map_err(|_| Error::Decryption)?,
+        ))
+    }
+}
+
+/// Read the passphrase from `STELLAR_IDENTITY_PASSPHRASE`, or prompt for it. When `confirm` is
+/// set the prompt asks for the passphrase twice.
+pub fn read_passphrase(prompt: &str, confirm: bool) -> Result<Zeroizing<String>, Error> {
+    read_passphrase_from_env(PASSPHRASE_ENV, prompt, confirm)
+}
+
+/// Like [`read_passphrase`], reading the passphrase from the `env` environment variable.
+pub fn read_passphrase_from_env(
+    env: &str,
+    prompt: &str,
+    confirm: bool,
+) -> Result<Zeroizing<String>, Error> {
+    if let Ok(passphrase) = std::env::var(env) {
+        return non_empty(Zeroizing::new(passphrase));
+    }
+    let passphrase = Zeroizing::new(
+        rpassword::prompt_password(format!("{prompt}: ")).map_err(|_| Error::PassphraseRead)?,
+    );
+    if confirm {
+        let again = Zeroizing::new(
+            rpassword::prompt_password("Confirm passphrase: ")
+                .map_err(|_| Error::PassphraseRead)?,
+        );
+        if again != passphrase {
+            return Err(Error::PassphraseMismatch);
+        }
+    }
+    non_empty(passphrase)
+}
+
+fn non_empty(passphrase: Zeroizing<String>) -> Result<Zeroizing<String>, Error> {
+    if passphrase.is_empty() {
+        Err(Error::EmptyPassphrase)
+    } else {
+        Ok(passphrase)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUBLIC_KEY: &str = "GAREAZZQWHOCBJS236KIE3AWYBVFLSBK7E5UW3ICI3TCRWQKT5LNLCEZ";
+    const SECRET_KEY: &str = "SBF5HLRREHMS36XZNTUSKZ6FTXDZGNXOHF4EXKUL5UCWZLPBX3NGJ4BH";
+
+    fn encrypted() -> Encrypted {
+        Encrypted::encrypt_with_cost(SECRET_KEY, PUBLIC_KEY, "hunter2", 4).unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let encrypted = encrypted();
+        assert!(!encrypted
+            .sealed
+            .ciphertext
+            .contains(&hex::encode(SECRET_KEY)));
+        assert_eq!(encrypted.decrypt("hunter2").unwrap().as_str(), SECRET_KEY);
+    }
+
+    #[test]
+    fn wrong_passphrase() {
+        assert!(matches!(
+            encrypted().decrypt("hunter3"),
+            Err(Error::Decryption)
+        ));
+    }
+
+    #[test]
+    fn public_key_is_authenticated() {
+        let mut encrypted = encrypted();
+        encrypted.public_key =
+            "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF".to_string();
+        assert!(matches!(
+            encrypted.decrypt("hunter2"),
+            Err(Error::Decryption)
+        ));
+    }
+}
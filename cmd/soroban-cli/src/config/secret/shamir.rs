@@ -0,0 +1,303 @@
+use std::{fmt::Display, str::FromStr};
+
+use bip39::{Language, Mnemonic};
+use rand::{Rng, RngCore};
+use sep5::SeedPhrase;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+const CHECKSUM_DOMAIN: &[u8] = b"stellar shamir share v1";
+/// Words holding the split id, threshold and share index, before the share value.
+const HEADER_WORDS: usize = 3;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("threshold must be between 2 and the number of shares, got {threshold} of {shares}")]
+    InvalidThreshold { threshold: u8, shares: u8 },
+    #[error("'{0}' is not a word of the BIP-39 English word list")]
+    UnknownWord(String),
+    #[error("share has {0} words, expected at least {min}", min = HEADER_WORDS + 13)]
+    TooShort(usize),
+    #[error("share is malformed")]
+    Malformed,
+    #[error("share checksum does not match, check the words for typos")]
+    InvalidChecksum,
+    #[error("shares come from different splits")]
+    DifferentSplits,
+    #[error("share {0} was given more than once")]
+    DuplicateShare(u8),
+    #[error("{needed} shares are needed to recover the seed phrase, got {got}")]
+    NotEnoughShares { needed: u8, got: usize },
+    #[error(transparent)]
+    SeedPhrase(#[from] sep5::Error),
+}
+
+/// One share of a seed phrase split with Shamir's secret sharing over GF(2^8).
+///
+/// A share is written as words of the BIP-39 English list: the id of the split, the threshold
+/// and the index of the share, then the share value as a BIP-39 mnemonic, then a checksum word
+/// over all of it. Both the mnemonic checksum and the final word catch mistyped words.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Share {
+    /// Random identifier shared by all shares of a split
+    pub id: u16,
+    pub threshold: u8,
+    pub index: u8,
+    pub value: Zeroizing<Vec<u8>>,
+}
+
+impl Share {
+    fn checksum(&self) -> u16 {
+        let [id_hi, id_lo] = self.id.to_be_bytes();
+        let hash = Sha256::new()
+            .chain_update(CHECKSUM_DOMAIN)
+            .chain_update([id_hi, id_lo, self.threshold, self.index])
+            .chain_update(&self.value)
+            .finalize();
+        u16::from_be_bytes([hash[0], hash[1]]) >> 5
+    }
+}
+
+fn word(bits: u16) -> &'static str {
+    Language::English.wordlist().get_word(bits.into())
+}
+
+fn bits(word: &str) -> Result<u16, Error> {
+    Language::English
+        .wordmap()
+        .get_bits(word)
+        .map(u16::from)
+        .map_err(|_| Error::UnknownWord(word.to_string()))
+}
+
+impl Display for Share {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value =
+            Mnemonic::from_entropy(&self.value, Language::English).map_err(|_| std::fmt::Error)?;
+        write!(
+            f,
+            "{} {} {} {} {}",
+            word(self.id),
+            word(self.threshold.into()),
+            word(self.index.into()),
+            value.phrase(),
+            word(self.checksum())
+        )
+    }
+}
+
+impl FromStr for Share {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words = s.split_whitespace().collect::<Vec<_>>();
+        if words.len() < HEADER_WORDS + 13 {
+            return Err(Error::TooShort(words.len()));
+        }
+        let (value, checksum) = words[HEADER_WORDS..].split_at(words.len() - HEADER_WORDS - 1);
+        if let Some(unknown) = words.iter().find(|w| bits(w).is_err()) {
+            return Err(Error::UnknownWord((*unknown).to_string()));
+        }
+        if ![12, 15, 18, 21, 24].contains(&value.len()) {
+            return Err(Error::Malformed);
+        }
+        let value = Mnemonic::from_phrase(&value.join(" "), Language::English)
+            .map_err(|_| Error::InvalidChecksum)?;
+        let share = Share {
+            id: bits(words[0])?,
+            threshold: bits(words[1])?.try_into().map_err(|_| Error::Malformed)?,
+            index: bits(words[2])?.try_into().map_err(|_| Error::Malformed)?,
+            value: Zeroizing::new(value.entropy().to_vec()),
+        };
+        if share.index == 0 || share.threshold == 0 {
+            return Err(Error::Malformed);
+        }
+        if share.checksum() != bits(checksum[0])? {
+            return Err(Error::InvalidChecksum);
+        }
+        Ok(share)
+    }
+}
+
+/// Split the entropy of a seed phrase so that any `threshold` of the `shares` can recover it.
+pub fn split(seed_phrase: &SeedPhrase, threshold: u8, shares: u8) -> Result<Vec<Share>, Error> {
+    if threshold < 2 || threshold > shares {
+        return Err(Error::InvalidThreshold { threshold, shares });
+    }
+    let mut rng = rand::thread_rng();
+    let id = rng.gen_range(0..2048);
+    let secret = seed_phrase.seed_phrase.entropy();
+    let mut result = (1..=shares)
+        .map(|index| Share {
+            id,
+            threshold,
+            index,
+            value: Zeroizing::new(Vec::with_capacity(secret.len())),
+        })
+        .collect::<Vec<_>>();
+    let mut coefficients = Zeroizing::new(vec![0u8; usize::from(threshold)]);
+    for byte in secret {
+        coefficients[0] = *byte;
+        rng.fill_bytes(&mut coefficients[1..]);
+        for share in &mut result {
+            // Horner's rule, from the highest coefficient down
+            let y = coefficients
+                .iter()
+                .rev()
+                .fold(0, |acc, c| gf_mul(acc, share.index) ^ c);
+            share.value.push(y);
+        }
+    }
+    Ok(result)
+}
+
+/// Recover the seed phrase from at least `threshold` shares of the same split.
+pub fn combine(shares: &[Share]) -> Result<SeedPhrase, Error> {
+    let Some(first) = shares.first() else {
+        return Err(Error::NotEnoughShares { needed: 2, got: 0 });
+    };
+    let mut indexes = Vec::with_capacity(shares.len());
+    for share in shares {
+        if share.id != first.id
+            || share.threshold != first.threshold
+            || share.value.len() != first.value.len()
+        {
+            return Err(Error::DifferentSplits);
+        }
+        if indexes.contains(&share.index) {
+            return Err(Error::DuplicateShare(share.index));
+        }
+        indexes.push(share.index);
+    }
+    if shares.len() < usize::from(first.threshold) {
+        return Err(Error::NotEnoughShares {
+            needed: first.threshold,
+            got: shares.len(),
+        });
+    }
+    let shares = &shares[..usize::from(first.threshold)];
+    let secret = Zeroizing::new(
+        (0..first.value.len())
+            .map(|i| {
+                // Lagrange interpolation at x = 0, where subtraction is xor
+                shares.iter().fold(0, |acc, share| {
+                    let basis = shares
+                        .iter()
+                        .filter(|other| other.index != share.index)
+                        .fold(1, |basis, other| {
+                            gf_mul(
+                                basis,
+                                gf_mul(other.index, gf_inv(other.index ^ share.index)),
+                            )
+                        });
+                    acc ^ gf_mul(share.value[i], basis)
+                })
+            })
+            .collect::<Vec<u8>>(),
+    );
+    Ok(SeedPhrase::from_entropy(&secret)?)
+}
+
+/// Multiplication in GF(2^8) with the AES polynomial.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80 != 0;
+        a <<= 1;
+        if carry {
+            a ^= 0x1b;
+        }
+        b >>= 1;
+    }
+    product
+}
+
+/// Multiplicative inverse in GF(2^8), as a^254.
+fn gf_inv(a: u8) -> u8 {
+    let mut result = 1;
+    for _ in 0..254 {
+        result = gf_mul(result, a);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEED_PHRASE: &str =
+        "depth decade power loud smile spatial sign movie judge february rate broccoli";
+
+    fn seed_phrase() -> SeedPhrase {
+        SeedPhrase::from_seed_phrase(SEED_PHRASE).unwrap()
+    }
+
+    #[test]
+    fn any_threshold_of_shares_recovers() {
+        let shares = split(&seed_phrase(), 3, 5).unwrap();
+        assert_eq!(shares.len(), 5);
+        for picked in [[0, 1, 2], [4, 2, 0], [1, 3, 4]] {
+            let picked = picked.map(|i| shares[i].clone());
+            assert_eq!(combine(&picked).unwrap().phrase(), SEED_PHRASE);
+        }
+        assert!(matches!(
+            combine(&shares[..2]),
+            Err(Error::NotEnoughShares { needed: 3, got: 2 })
+        ));
+    }
+
+    #[test]
+    fn words_round_trip() {
+        let seed_phrase = SeedPhrase::random(sep5::MnemonicType::Words24).unwrap();
+        let shares = split(&seed_phrase, 2, 3).unwrap();
+        let parsed = shares
+            .iter()
+            .map(|share| share.to_string().parse::<Share>().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(parsed, shares);
+        assert_eq!(shares[0].to_string().split_whitespace().count(), 28);
+        assert_eq!(
+            combine(&parsed[1..]).unwrap().phrase(),
+            seed_phrase.phrase()
+        );
+    }
+
+    #[test]
+    fn mistyped_share_is_detected() {
+        let share = Share {
+            id: 42,
+            threshold: 2,
+            index: 1,
+            value: Zeroizing::new(vec![7; 16]),
+        }
+        .to_string();
+        let mut words = share.split_whitespace().collect::<Vec<_>>();
+        assert_eq!(words.len(), 16);
+        // A valid word in the wrong place is only caught by the checksums
+        words[1] = word(3);
+        assert!(matches!(
+            words.join(" ").parse::<Share>(),
+            Err(Error::InvalidChecksum)
+        ));
+        words[1] = word(2);
+        words[5] = "notaword";
+        assert!(matches!(
+            words.join(" ").parse::<Share>(),
+            Err(Error::UnknownWord(w)) if w == "notaword"
+        ));
+    }
+
+    #[test]
+    fn shares_from_different_splits() {
+        let mut shares = split(&seed_phrase(), 2, 2).unwrap();
+        shares[1].id ^= 1;
+        assert!(matches!(combine(&shares), Err(Error::DifferentSplits)));
+        assert!(matches!(
+            split(&seed_phrase(), 1, 2),
+            Err(Error::InvalidThreshold { .. })
+        ));
+    }
+}
@@ -69,8 +69,23 @@ impl Args {
         quiet: bool,
         default_signer_account: Option<&UnresolvedMuxedAccount>,
     ) -> Result<TransactionEnvelope, Error> {
+        let signer = self.signer(locator, quiet, default_signer_account).await?;
+        Ok(signer.sign_tx_env(tx, network).await?)
+    }
+
+    /// Whether a signer was picked explicitly, rather than falling back to a default account.
+    pub fn is_set(&self) -> bool {
+        self.sign_with_key.is_some() || self.sign_with_lab || self.sign_with_ledger
+    }
+
+    pub async fn signer(
+        &self,
+        locator: &locator::Args,
+        quiet: bool,
+        default_signer_account: Option<&UnresolvedMuxedAccount>,
+    ) -> Result<Signer, Error> {
         let print = Print::new(quiet);
-        let signer = if self.sign_with_lab {
+        Ok(if self.sign_with_lab {
             Signer {
                 kind: SignerKind::Lab,
                 print,
@@ -99,7 +114,6 @@ impl Args {
 
             let secret = locator.get_secret_key(key_or_name)?;
             secret.signer(self.hd_path, print).await?
-        };
-        Ok(signer.sign_tx_env(tx, network).await?)
+        })
     }
 }
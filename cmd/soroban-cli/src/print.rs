@@ -65,6 +65,13 @@ impl Print {
         show_link: bool,
     ) -> Result<(), XdrError> {
         let tx_hash = transaction_hash(tx, &network.network_passphrase)?;
+        self.log_transaction_hash(&tx_hash, network, show_link);
+        Ok(())
+    }
+
+    /// Like [`Print::log_transaction`], for a hash that is already computed, e.g. that of a
+    /// fee bump transaction.
+    pub fn log_transaction_hash(&self, tx_hash: &[u8; 32], network: &Network, show_link: bool) {
         let hash = hex::encode(tx_hash);
 
         self.infoln(format!("Transaction hash is {hash}").as_str());
@@ -74,8 +81,6 @@ impl Print {
                 self.linkln(url);
             }
         }
-
-        Ok(())
     }
 }
 
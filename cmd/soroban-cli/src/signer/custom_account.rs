@@ -0,0 +1,402 @@
+use std::str::FromStr;
+
+use soroban_spec_tools::Spec;
+
+use crate::config::UnresolvedContract;
+
+use crate::xdr::{
+    self, BytesM, ScMap, ScMapEntry, ScSpecEntry, ScSpecTypeDef, ScSpecTypeMap, ScSpecTypeUdt,
+    ScSpecUdtStructV0, ScSymbol, ScVal, ScVec,
+};
+
+use super::Signer;
+
+/// Name of the function a custom account contract implements to authorize invocations
+pub const CHECK_AUTH_FUNCTION_NAME: &str = "__check_auth";
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("contract {address} does not implement {CHECK_AUTH_FUNCTION_NAME}")]
+    MissingCheckAuth { address: String },
+    #[error("cannot derive a signature format from the {CHECK_AUTH_FUNCTION_NAME} signature type {type_:?}, use --auth-signature-format")]
+    UnsupportedSignatureType { type_: ScSpecTypeDef },
+    #[error(
+        "invalid auth signer {value:?}, expected CONTRACT=KEY, e.g. `--auth-signer CC…=alice`"
+    )]
+    InvalidAuthSigner { value: String },
+    #[error("signature format \"single\" requires exactly one signer, got {count}")]
+    SingleSignatureRequiresOneSigner { count: usize },
+    #[error(transparent)]
+    Spec(#[from] soroban_spec_tools::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+}
+
+/// How the signatures for a custom account are encoded into the `signature` argument of its
+/// `__check_auth` function.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum SignatureFormat {
+    /// Derive the format from the contract's `__check_auth` spec
+    #[default]
+    Auto,
+    /// A single 64-byte ed25519 signature (`BytesN<64>`)
+    Single,
+    /// A vec of 64-byte signatures, ordered by public key (`Vec<BytesN<64>>`)
+    Signatures,
+    /// A vec of `{ public_key, signature }` structs, ordered by public key
+    Structs,
+    /// A map of public key to signature (`Map<BytesN<32>, BytesN<64>>`)
+    Map,
+}
+
+/// Resolved encoding of the `signature` argument passed to `__check_auth`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SignatureEncoding {
+    Single,
+    Signatures,
+    Structs {
+        public_key_field: String,
+        signature_field: String,
+    },
+    Map,
+}
+
+impl Default for SignatureEncoding {
+    fn default() -> Self {
+        SignatureEncoding::Structs {
+            public_key_field: "public_key".to_string(),
+            signature_field: "signature".to_string(),
+        }
+    }
+}
+
+impl SignatureFormat {
+    /// Resolve the format to an encoding, using the contract's spec when the format is `Auto`.
+    ///
+    /// # Errors
+    /// Fails if the spec has no `__check_auth` or its signature type isn't recognised.
+    pub fn encoding(
+        self,
+        address: &stellar_strkey::Contract,
+        spec: Option<&Spec>,
+    ) -> Result<SignatureEncoding, Error> {
+        Ok(match self {
+            SignatureFormat::Single => SignatureEncoding::Single,
+            SignatureFormat::Signatures => SignatureEncoding::Signatures,
+            SignatureFormat::Structs => SignatureEncoding::default(),
+            SignatureFormat::Map => SignatureEncoding::Map,
+            SignatureFormat::Auto => match spec {
+                Some(spec) => SignatureEncoding::from_spec(address, spec)?,
+                None => SignatureEncoding::default(),
+            },
+        })
+    }
+}
+
+impl SignatureEncoding {
+    /// Derive the encoding from the type of the second argument of `__check_auth`.
+    ///
+    /// # Errors
+    /// Fails if the spec has no `__check_auth` or its signature type isn't recognised.
+    pub fn from_spec(address: &stellar_strkey::Contract, spec: &Spec) -> Result<Self, Error> {
+        let check_auth =
+            spec.find_function(CHECK_AUTH_FUNCTION_NAME)
+                .map_err(|_| Error::MissingCheckAuth {
+                    address: address.to_string(),
+                })?;
+        let Some(input) = check_auth.inputs.get(1) else {
+            return Err(Error::MissingCheckAuth {
+                address: address.to_string(),
+            });
+        };
+        Self::from_type(spec, &input.type_)
+    }
+
+    fn from_type(spec: &Spec, type_: &ScSpecTypeDef) -> Result<Self, Error> {
+        let unsupported = || Error::UnsupportedSignatureType {
+            type_: type_.clone(),
+        };
+        match type_ {
+            ScSpecTypeDef::BytesN(n) if n.n == 64 => Ok(SignatureEncoding::Single),
+            ScSpecTypeDef::Vec(vec) => match vec.element_type.as_ref() {
+                ScSpecTypeDef::BytesN(n) if n.n == 64 => Ok(SignatureEncoding::Signatures),
+                ScSpecTypeDef::Udt(ScSpecTypeUdt { name }) => {
+                    let ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 { fields, .. }) =
+                        spec.find(&name.to_utf8_string_lossy())?
+                    else {
+                        return Err(unsupported());
+                    };
+                    let field_of_len = |len: u32| {
+                        fields.iter().find_map(|f| match &f.type_ {
+                            ScSpecTypeDef::BytesN(n) if n.n == len => {
+                                Some(f.name.to_utf8_string_lossy())
+                            }
+                            _ => None,
+                        })
+                    };
+                    match (field_of_len(32), field_of_len(64)) {
+                        (Some(public_key_field), Some(signature_field)) if fields.len() == 2 => {
+                            Ok(SignatureEncoding::Structs {
+                                public_key_field,
+                                signature_field,
+                            })
+                        }
+                        _ => Err(unsupported()),
+                    }
+                }
+                _ => Err(unsupported()),
+            },
+            ScSpecTypeDef::Map(map) => match map.as_ref() {
+                ScSpecTypeMap {
+                    key_type,
+                    value_type,
+                } if matches!(key_type.as_ref(), ScSpecTypeDef::BytesN(n) if n.n == 32)
+                    && matches!(value_type.as_ref(), ScSpecTypeDef::BytesN(n) if n.n == 64) =>
+                {
+                    Ok(SignatureEncoding::Map)
+                }
+                _ => Err(unsupported()),
+            },
+            ScSpecTypeDef::Option(option) => Self::from_type(spec, &option.value_type),
+            _ => Err(unsupported()),
+        }
+    }
+
+    /// Build the `signature` argument from `(public_key, signature)` pairs. Pairs are ordered by
+    /// public key, as most account contracts require.
+    ///
+    /// # Errors
+    /// Fails if the pairs don't fit the encoding.
+    pub fn to_sc_val(&self, mut signatures: Vec<([u8; 32], [u8; 64])>) -> Result<ScVal, Error> {
+        signatures.sort_by_key(|(public_key, _)| *public_key);
+        let bytes = |b: &[u8]| -> Result<ScVal, Error> {
+            Ok(ScVal::Bytes(BytesM::try_from(b.to_vec())?.into()))
+        };
+        Ok(match self {
+            SignatureEncoding::Single => {
+                let [(_, signature)] = signatures.as_slice() else {
+                    return Err(Error::SingleSignatureRequiresOneSigner {
+                        count: signatures.len(),
+                    });
+                };
+                bytes(signature)?
+            }
+            SignatureEncoding::Signatures => ScVal::Vec(Some(ScVec(
+                signatures
+                    .iter()
+                    .map(|(_, signature)| bytes(signature))
+                    .collect::<Result<Vec<_>, _>>()?
+                    .try_into()?,
+            ))),
+            SignatureEncoding::Structs {
+                public_key_field,
+                signature_field,
+            } => ScVal::Vec(Some(ScVec(
+                signatures
+                    .iter()
+                    .map(|(public_key, signature)| {
+                        let map = ScMap::sorted_from(vec![
+                            (
+                                ScVal::Symbol(ScSymbol(public_key_field.as_str().try_into()?)),
+                                bytes(public_key)?,
+                            ),
+                            (
+                                ScVal::Symbol(ScSymbol(signature_field.as_str().try_into()?)),
+                                bytes(signature)?,
+                            ),
+                        ])?;
+                        Ok(ScVal::Map(Some(map)))
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?
+                    .try_into()?,
+            ))),
+            SignatureEncoding::Map => ScVal::Map(Some(ScMap(
+                signatures
+                    .iter()
+                    .map(|(public_key, signature)| {
+                        Ok(ScMapEntry {
+                            key: bytes(public_key)?,
+                            val: bytes(signature)?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?
+                    .try_into()?,
+            ))),
+        })
+    }
+}
+
+/// A key that signs the auth entries of one custom account contract, given as `CONTRACT=KEY`
+/// where the contract is a C-address or an alias and the key an identity, a secret key or a seed
+/// phrase.
+#[derive(Clone, Debug)]
+pub struct AuthSigner {
+    pub contract: UnresolvedContract,
+    pub key: String,
+}
+
+impl FromStr for AuthSigner {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.split_once('=') {
+            Some((contract, key)) if !contract.is_empty() && !key.is_empty() => Ok(AuthSigner {
+                contract: contract.parse().unwrap_or_else(|e| match e {}),
+                key: key.to_string(),
+            }),
+            _ => Err(Error::InvalidAuthSigner {
+                value: value.to_string(),
+            }),
+        }
+    }
+}
+
+/// The keys and encoding used to sign auth entries on behalf of a custom account contract
+/// (a C-address that implements `__check_auth`).
+pub struct CustomAccount {
+    pub address: stellar_strkey::Contract,
+    pub signers: Vec<Signer>,
+    pub encoding: SignatureEncoding,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdr::{
+        ScSpecFunctionInputV0, ScSpecFunctionV0, ScSpecTypeBytesN, ScSpecTypeVec,
+        ScSpecUdtStructFieldV0,
+    };
+
+    fn check_auth_spec(signature_type: ScSpecTypeDef, extra: Vec<ScSpecEntry>) -> Spec {
+        let input = |name: &str, type_: ScSpecTypeDef| ScSpecFunctionInputV0 {
+            doc: "".try_into().unwrap(),
+            name: name.try_into().unwrap(),
+            type_,
+        };
+        let mut entries = vec![ScSpecEntry::FunctionV0(ScSpecFunctionV0 {
+            doc: "".try_into().unwrap(),
+            name: CHECK_AUTH_FUNCTION_NAME.try_into().unwrap(),
+            inputs: vec![
+                input(
+                    "signature_payload",
+                    ScSpecTypeDef::BytesN(ScSpecTypeBytesN { n: 32 }),
+                ),
+                input("signatures", signature_type),
+            ]
+            .try_into()
+            .unwrap(),
+            outputs: vec![].try_into().unwrap(),
+        })];
+        entries.extend(extra);
+        Spec::new(&entries)
+    }
+
+    fn bytes_n(n: u32) -> ScSpecTypeDef {
+        ScSpecTypeDef::BytesN(ScSpecTypeBytesN { n })
+    }
+
+    const ADDRESS: stellar_strkey::Contract = stellar_strkey::Contract([0; 32]);
+
+    #[test]
+    fn encoding_from_single_signature() {
+        let spec = check_auth_spec(bytes_n(64), vec![]);
+        let encoding = SignatureEncoding::from_spec(&ADDRESS, &spec).unwrap();
+        assert_eq!(encoding, SignatureEncoding::Single);
+    }
+
+    #[test]
+    fn encoding_from_struct_vec() {
+        let field = |name: &str, type_: ScSpecTypeDef| ScSpecUdtStructFieldV0 {
+            doc: "".try_into().unwrap(),
+            name: name.try_into().unwrap(),
+            type_,
+        };
+        let spec = check_auth_spec(
+            ScSpecTypeDef::Vec(Box::new(ScSpecTypeVec {
+                element_type: Box::new(ScSpecTypeDef::Udt(ScSpecTypeUdt {
+                    name: "Signature".try_into().unwrap(),
+                })),
+            })),
+            vec![ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+                doc: "".try_into().unwrap(),
+                lib: "".try_into().unwrap(),
+                name: "Signature".try_into().unwrap(),
+                fields: vec![field("key", bytes_n(32)), field("sig", bytes_n(64))]
+                    .try_into()
+                    .unwrap(),
+            })],
+        );
+        let encoding = SignatureEncoding::from_spec(&ADDRESS, &spec).unwrap();
+        assert_eq!(
+            encoding,
+            SignatureEncoding::Structs {
+                public_key_field: "key".to_string(),
+                signature_field: "sig".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn encoding_from_map() {
+        let spec = check_auth_spec(
+            ScSpecTypeDef::Map(Box::new(ScSpecTypeMap {
+                key_type: Box::new(bytes_n(32)),
+                value_type: Box::new(bytes_n(64)),
+            })),
+            vec![],
+        );
+        let encoding = SignatureEncoding::from_spec(&ADDRESS, &spec).unwrap();
+        assert_eq!(encoding, SignatureEncoding::Map);
+    }
+
+    #[test]
+    fn encoding_rejects_unknown_types() {
+        let spec = check_auth_spec(ScSpecTypeDef::U32, vec![]);
+        assert!(matches!(
+            SignatureEncoding::from_spec(&ADDRESS, &spec),
+            Err(Error::UnsupportedSignatureType { .. })
+        ));
+    }
+
+    #[test]
+    fn signatures_are_ordered_by_public_key() {
+        let val = SignatureEncoding::Signatures
+            .to_sc_val(vec![([2; 32], [2; 64]), ([1; 32], [1; 64])])
+            .unwrap();
+        let ScVal::Vec(Some(ScVec(sigs))) = val else {
+            panic!("expected vec");
+        };
+        assert_eq!(
+            sigs.as_slice(),
+            &[
+                ScVal::Bytes(BytesM::try_from(vec![1; 64]).unwrap().into()),
+                ScVal::Bytes(BytesM::try_from(vec![2; 64]).unwrap().into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn auth_signer_from_str() {
+        let contract = stellar_strkey::Contract([1; 32]);
+        let signer: AuthSigner = format!("{contract}=alice").parse().unwrap();
+        assert!(matches!(signer.contract, UnresolvedContract::Resolved(c) if c == contract));
+        assert_eq!(signer.key, "alice");
+        let signer: AuthSigner = "wallet=SC36=".parse().unwrap();
+        assert!(matches!(signer.contract, UnresolvedContract::Alias(alias) if alias == "wallet"));
+        assert_eq!(signer.key, "SC36=");
+        for value in ["alice", "=alice", "wallet="] {
+            assert!(matches!(
+                value.parse::<AuthSigner>(),
+                Err(Error::InvalidAuthSigner { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn single_requires_one_signature() {
+        assert!(matches!(
+            SignatureEncoding::Single.to_sc_val(vec![([1; 32], [1; 64]), ([2; 32], [2; 64])]),
+            Err(Error::SingleSignatureRequiresOneSigner { count: 2 })
+        ));
+    }
+}
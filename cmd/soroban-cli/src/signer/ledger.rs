@@ -22,6 +22,7 @@ pub enum Error {
 mod ledger_impl {
     use super::Error;
     use crate::xdr::{DecoratedSignature, Hash, Signature, SignatureHint, Transaction};
+    use ed25519_dalek::Signature as Ed25519Signature;
     use sha2::{Digest, Sha256};
     use stellar_ledger::{Blob as _, Exchange, LedgerSigner};
 
@@ -50,18 +51,25 @@ mod ledger_impl {
         hd_path: u32,
     ) -> Result<Ledger<stellar_ledger::emulator_test_support::http_transport::Emulator>, Error>
     {
-        use stellar_ledger::emulator_test_support::ledger as emulator_ledger;
         // port from SPECULOS_PORT ENV var
         let host_port: u16 = std::env::var("SPECULOS_PORT")
             .expect("SPECULOS_PORT env var not set")
             .parse()
             .expect("port must be a number");
-        let signer = emulator_ledger(host_port).await;
+        Ok(emulator(hd_path, host_port).await)
+    }
 
-        Ok(Ledger {
+    /// A ledger backed by the Speculos emulator listening on `host_port`.
+    #[cfg(feature = "emulator-tests")]
+    pub async fn emulator(
+        hd_path: u32,
+        host_port: u16,
+    ) -> Ledger<stellar_ledger::emulator_test_support::http_transport::Emulator> {
+        use stellar_ledger::emulator_test_support::ledger as emulator_ledger;
+        Ledger {
             index: hd_path,
-            signer,
-        })
+            signer: emulator_ledger(host_port).await,
+        }
     }
 
     impl<T: Exchange> Ledger<T> {
@@ -99,6 +107,15 @@ mod ledger_impl {
         pub async fn public_key(&self) -> Result<stellar_strkey::ed25519::PublicKey, Error> {
             Ok(self.signer.get_public_key(&self.index.into()).await?)
         }
+
+        /// Sign a 32 byte payload, such as the hash of a Soroban authorization entry. This uses
+        /// hash signing, so it must be enabled in the Stellar app settings on the device.
+        pub async fn sign_payload(&self, payload: [u8; 32]) -> Result<Ed25519Signature, Error> {
+            let signature = self.signer.sign_blob(&self.index.into(), &payload).await?;
+            Ok(Ed25519Signature::from_bytes(
+                signature.as_slice().try_into()?,
+            ))
+        }
     }
 }
 
@@ -106,6 +123,7 @@ mod ledger_impl {
 mod ledger_impl {
     use super::Error;
     use crate::xdr::{DecoratedSignature, Transaction};
+    use ed25519_dalek::Signature as Ed25519Signature;
     use std::marker::PhantomData;
 
     pub type LedgerType = Ledger<GenericExchange>;
@@ -142,6 +160,11 @@ mod ledger_impl {
         pub async fn public_key(&self) -> Result<stellar_strkey::ed25519::PublicKey, Error> {
             Err(Error::FeatureNotEnabled)
         }
+
+        #[allow(clippy::unused_async)]
+        pub async fn sign_payload(&self, _payload: [u8; 32]) -> Result<Ed25519Signature, Error> {
+            Err(Error::FeatureNotEnabled)
+        }
     }
 
     pub struct GenericExchange {}
@@ -1,15 +1,22 @@
 use crate::xdr::{
-    self, AccountId, DecoratedSignature, Hash, HashIdPreimage, HashIdPreimageSorobanAuthorization,
-    InvokeHostFunctionOp, Limits, Operation, OperationBody, PublicKey, ScAddress, ScMap, ScSymbol,
-    ScVal, Signature, SignatureHint, SorobanAddressCredentials, SorobanAuthorizationEntry,
-    SorobanAuthorizedFunction, SorobanCredentials, Transaction, TransactionEnvelope,
+    self, AccountId, DecoratedSignature, FeeBumpTransactionEnvelope, Hash, HashIdPreimage,
+    HashIdPreimageSorobanAuthorization, InvokeHostFunctionOp, Limits, MuxedEd25519Account,
+    Operation, OperationBody, PublicKey, ScAddress, ScMap, ScSymbol, ScVal, Signature,
+    SignatureHint, SorobanAddressCredentials, SorobanAuthorizationEntry, SorobanAuthorizedFunction,
+    SorobanAuthorizedInvocation, SorobanCredentials, Transaction, TransactionEnvelope,
     TransactionV1Envelope, Uint256, VecM, WriteXdr,
 };
 use ed25519_dalek::{ed25519::signature::Signer as _, Signature as Ed25519Signature};
 use sha2::{Digest, Sha256};
 
-use crate::{config::network::Network, print::Print, utils::transaction_hash};
+use crate::{
+    config::network::Network,
+    print::Print,
+    utils::{fee_bump_transaction_hash, transaction_hash},
+};
+use custom_account::CustomAccount;
 
+pub mod custom_account;
 pub mod ledger;
 
 #[cfg(feature = "additional-libs")]
@@ -24,13 +31,17 @@ pub enum Error {
     Ed25519(#[from] ed25519_dalek::SignatureError),
     #[error("Missing signing key for account {address}")]
     MissingSignerForAddress { address: String },
+    #[error("Claimable balance {address} cannot authorize invocations")]
+    ClaimableBalanceAddressNotSupported { address: String },
+    #[error("Liquidity pool {address} cannot authorize invocations")]
+    LiquidityPoolAddressNotSupported { address: String },
     #[error(transparent)]
     TryFromSlice(#[from] std::array::TryFromSliceError),
     #[error("User cancelled signing, perhaps need to add -y")]
     UserCancelledSigning,
     #[error(transparent)]
     Xdr(#[from] xdr::Error),
-    #[error("Only Transaction envelope V1 type is supported")]
+    #[error("Only Transaction envelope V1 and fee bump types are supported")]
     UnsupportedTransactionEnvelopeType,
     #[error(transparent)]
     Url(#[from] url::ParseError),
@@ -44,6 +55,8 @@ pub enum Error {
     Ledger(#[from] ledger::Error),
     #[error(transparent)]
     Decode(#[from] stellar_strkey::DecodeError),
+    #[error(transparent)]
+    CustomAccount(#[from] custom_account::Error),
 }
 
 fn requires_auth(txn: &Transaction) -> Option<xdr::Operation> {
@@ -62,11 +75,13 @@ fn requires_auth(txn: &Transaction) -> Option<xdr::Operation> {
 }
 
 // Use the given source_key and signers, to sign all SorobanAuthorizationEntry's in the given
-// transaction. If unable to sign, return an error.
-pub fn sign_soroban_authorizations(
+// transaction. Entries for contract addresses are signed with the matching custom account, if any.
+// If unable to sign, return an error.
+pub async fn sign_soroban_authorizations(
     raw: &Transaction,
     source_signer: &Signer,
     signers: &[Signer],
+    custom_accounts: &[CustomAccount],
     signature_expiration_ledger: u32,
     network_passphrase: &str,
 ) -> Result<Option<Transaction>, Error> {
@@ -102,28 +117,52 @@ pub fn sign_soroban_authorizations(
         // See if we have a signer for this authorizationEntry
         // If not, then we Error
         let needle: &[u8; 32] = match address {
-            ScAddress::MuxedAccount(_) => todo!("muxed accounts are not supported"),
-            ScAddress::ClaimableBalance(_) => todo!("claimable balance not supported"),
-            ScAddress::LiquidityPool(_) => todo!("liquidity pool not supported"),
-            ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(ref a)))) => a,
+            ScAddress::ClaimableBalance(_) => {
+                return Err(Error::ClaimableBalanceAddressNotSupported {
+                    address: address.to_string(),
+                });
+            }
+            ScAddress::LiquidityPool(_) => {
+                return Err(Error::LiquidityPoolAddressNotSupported {
+                    address: address.to_string(),
+                });
+            }
+            // Muxed accounts are authorized by the key of their underlying account
+            ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(ref a))))
+            | ScAddress::MuxedAccount(MuxedEd25519Account {
+                ed25519: Uint256(ref a),
+                ..
+            }) => a,
             ScAddress::Contract(stellar_xdr::curr::ContractId(Hash(c))) => {
                 // This address is for a contract. This means we're using a custom
-                // smart-contract account. Currently the CLI doesn't support that yet.
-                return Err(Error::MissingSignerForAddress {
-                    address: stellar_strkey::Strkey::Contract(stellar_strkey::Contract(*c))
-                        .to_string(),
-                });
+                // smart-contract account, which needs to be configured explicitly.
+                let Some(account) = custom_accounts.iter().find(|a| a.address.0 == *c) else {
+                    return Err(Error::MissingSignerForAddress {
+                        address: stellar_strkey::Strkey::Contract(stellar_strkey::Contract(*c))
+                            .to_string(),
+                    });
+                };
+                signed_auths.push(
+                    sign_custom_account_authorization_entry(
+                        raw_auth,
+                        account,
+                        signature_expiration_ledger,
+                        &network_id,
+                    )
+                    .await?,
+                );
+                continue;
             }
         };
 
         let mut signer: Option<&Signer> = None;
         for s in signers {
-            if needle == &s.get_public_key()? {
+            if needle == &s.get_public_key().await? {
                 signer = Some(s);
             }
         }
 
-        if needle == &source_signer.get_public_key()? {
+        if needle == &source_signer.get_public_key().await? {
             signer = Some(source_signer);
         }
 
@@ -134,7 +173,8 @@ pub fn sign_soroban_authorizations(
                     signer,
                     signature_expiration_ledger,
                     &network_id,
-                )?;
+                )
+                .await?;
                 signed_auths.push(signed_entry);
             }
             None => {
@@ -153,7 +193,38 @@ pub fn sign_soroban_authorizations(
     Ok(Some(tx))
 }
 
-fn sign_soroban_authorization_entry(
+/// The payload an address signs to authorize `invocation`.
+pub fn authorization_payload(
+    invocation: &SorobanAuthorizedInvocation,
+    nonce: i64,
+    signature_expiration_ledger: u32,
+    network_id: &Hash,
+) -> Result<[u8; 32], Error> {
+    let preimage = HashIdPreimage::SorobanAuthorization(HashIdPreimageSorobanAuthorization {
+        network_id: network_id.clone(),
+        invocation: invocation.clone(),
+        nonce,
+        signature_expiration_ledger,
+    })
+    .to_xdr(Limits::none())?;
+
+    let payload = Sha256::digest(preimage);
+    Ok(payload.as_slice().try_into()?)
+}
+
+/// Prefix of SEP-53 messages, so that a signed message can never be mistaken for a transaction.
+const MESSAGE_PREFIX: &[u8] = b"Stellar Signed Message:\n";
+
+/// The payload signed for an arbitrary message, as defined by SEP-53.
+pub fn message_payload(message: &[u8]) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(MESSAGE_PREFIX)
+        .chain_update(message)
+        .finalize()
+        .into()
+}
+
+async fn sign_soroban_authorization_entry(
     raw: &SorobanAuthorizationEntry,
     signer: &Signer,
     signature_expiration_ledger: u32,
@@ -168,20 +239,15 @@ fn sign_soroban_authorization_entry(
         // Doesn't need special signing
         return Ok(auth);
     };
-    let SorobanAddressCredentials { nonce, .. } = credentials;
 
-    let preimage = HashIdPreimage::SorobanAuthorization(HashIdPreimageSorobanAuthorization {
-        network_id: network_id.clone(),
-        invocation: auth.root_invocation.clone(),
-        nonce: *nonce,
+    let p = authorization_payload(
+        &auth.root_invocation,
+        credentials.nonce,
         signature_expiration_ledger,
-    })
-    .to_xdr(Limits::none())?;
-
-    let payload = Sha256::digest(preimage);
-    let p: [u8; 32] = payload.as_slice().try_into()?;
-    let signature = signer.sign_payload(p)?;
-    let public_key_vec = signer.get_public_key()?.to_vec();
+        network_id,
+    )?;
+    let signature = signer.sign_payload(p).await?;
+    let public_key_vec = signer.get_public_key().await?.to_vec();
 
     let map = ScMap::sorted_from(vec![
         (
@@ -208,6 +274,47 @@ fn sign_soroban_authorization_entry(
     Ok(auth)
 }
 
+async fn sign_custom_account_authorization_entry(
+    raw: &SorobanAuthorizationEntry,
+    account: &CustomAccount,
+    signature_expiration_ledger: u32,
+    network_id: &Hash,
+) -> Result<SorobanAuthorizationEntry, Error> {
+    let mut auth = raw.clone();
+    let SorobanAuthorizationEntry {
+        credentials: SorobanCredentials::Address(ref mut credentials),
+        ..
+    } = auth
+    else {
+        // Doesn't need special signing
+        return Ok(auth);
+    };
+    if account.signers.is_empty() {
+        return Err(Error::MissingSignerForAddress {
+            address: account.address.to_string(),
+        });
+    }
+
+    let p = authorization_payload(
+        &auth.root_invocation,
+        credentials.nonce,
+        signature_expiration_ledger,
+        network_id,
+    )?;
+    let mut signatures = Vec::with_capacity(account.signers.len());
+    for signer in &account.signers {
+        signatures.push((
+            signer.get_public_key().await?,
+            signer.sign_payload(p).await?.to_bytes(),
+        ));
+    }
+
+    credentials.signature = account.encoding.to_sc_val(signatures)?;
+    credentials.signature_expiration_ledger = signature_expiration_ledger;
+    auth.credentials = SorobanCredentials::Address(credentials.clone());
+    Ok(auth)
+}
+
 pub struct Signer {
     pub kind: SignerKind,
     pub print: Print,
@@ -245,12 +352,7 @@ impl Signer {
                 let tx_hash = transaction_hash(tx, &network.network_passphrase)?;
                 self.print
                     .infoln(format!("Signing transaction: {}", hex::encode(tx_hash),));
-                let decorated_signature = match &self.kind {
-                    SignerKind::Local(key) => key.sign_tx_hash(tx_hash)?,
-                    SignerKind::Lab => Lab::sign_tx_env(tx_env, network, &self.print)?,
-                    SignerKind::Ledger(ledger) => ledger.sign_transaction_hash(&tx_hash).await?,
-                    SignerKind::SecureStore(entry) => entry.sign_tx_hash(tx_hash)?,
-                };
+                let decorated_signature = self.sign_tx_hash(tx_hash, tx_env, network).await?;
                 let mut sigs = signatures.clone().into_vec();
                 sigs.push(decorated_signature);
                 Ok(TransactionEnvelope::Tx(TransactionV1Envelope {
@@ -258,15 +360,42 @@ impl Signer {
                     signatures: sigs.try_into()?,
                 }))
             }
-            _ => Err(Error::UnsupportedTransactionEnvelopeType),
+            TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope { tx, signatures }) => {
+                let tx_hash = fee_bump_transaction_hash(tx, &network.network_passphrase)?;
+                self.print.infoln(format!(
+                    "Signing fee bump transaction: {}",
+                    hex::encode(tx_hash)
+                ));
+                let decorated_signature = self.sign_tx_hash(tx_hash, tx_env, network).await?;
+                let mut sigs = signatures.clone().into_vec();
+                sigs.push(decorated_signature);
+                Ok(TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope {
+                    tx: tx.clone(),
+                    signatures: sigs.try_into()?,
+                }))
+            }
+            TransactionEnvelope::TxV0(_) => Err(Error::UnsupportedTransactionEnvelopeType),
         }
     }
 
-    // when we implement this for ledger we'll need it to be async so we can await for the ledger's public key
-    pub fn get_public_key(&self) -> Result<[u8; 32], Error> {
+    async fn sign_tx_hash(
+        &self,
+        tx_hash: [u8; 32],
+        tx_env: &TransactionEnvelope,
+        network: &Network,
+    ) -> Result<DecoratedSignature, Error> {
+        match &self.kind {
+            SignerKind::Local(key) => key.sign_tx_hash(tx_hash),
+            SignerKind::Lab => Lab::sign_tx_env(tx_env, network, &self.print),
+            SignerKind::Ledger(ledger) => Ok(ledger.sign_transaction_hash(&tx_hash).await?),
+            SignerKind::SecureStore(entry) => entry.sign_tx_hash(tx_hash),
+        }
+    }
+
+    pub async fn get_public_key(&self) -> Result<[u8; 32], Error> {
         match &self.kind {
             SignerKind::Local(local_key) => Ok(*local_key.key.verifying_key().as_bytes()),
-            SignerKind::Ledger(_ledger) => todo!("ledger device is not implemented"),
+            SignerKind::Ledger(ledger) => Ok(ledger.public_key().await?.0),
             SignerKind::Lab => Err(Error::ReturningSignatureFromLab),
             SignerKind::SecureStore(secure_store_entry) => {
                 let pk = secure_store_entry.get_public_key()?;
@@ -275,15 +404,19 @@ impl Signer {
         }
     }
 
-    // when we implement this for ledger we'll need it to be async so we can await the user approved the tx on the ledger device
-    pub fn sign_payload(&self, payload: [u8; 32]) -> Result<Ed25519Signature, Error> {
+    pub async fn sign_payload(&self, payload: [u8; 32]) -> Result<Ed25519Signature, Error> {
         match &self.kind {
             SignerKind::Local(local_key) => local_key.sign_payload(payload),
-            SignerKind::Ledger(_ledger) => todo!("ledger device is not implemented"),
+            SignerKind::Ledger(ledger) => Ok(ledger.sign_payload(payload).await?),
             SignerKind::Lab => Err(Error::ReturningSignatureFromLab),
             SignerKind::SecureStore(secure_store_entry) => secure_store_entry.sign_payload(payload),
         }
     }
+
+    /// Sign an arbitrary message following SEP-53.
+    pub async fn sign_message(&self, message: &[u8]) -> Result<Ed25519Signature, Error> {
+        self.sign_payload(message_payload(message)).await
+    }
 }
 
 pub struct LocalKey {
@@ -354,3 +487,168 @@ impl SecureStoreEntry {
         Ok(sig)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdr::{
+        ClaimableBalanceId, ContractId, InvokeContractArgs, Memo, MuxedAccount, PoolId,
+        Preconditions, SequenceNumber, SorobanAuthorizedInvocation, TransactionExt,
+    };
+    use base64::Engine as _;
+
+    const PASSPHRASE: &str = "Test SDF Network ; September 2015";
+
+    fn local_signer(seed: u8) -> Signer {
+        Signer {
+            kind: SignerKind::Local(LocalKey {
+                key: ed25519_dalek::SigningKey::from_bytes(&[seed; 32]),
+            }),
+            print: Print::new(true),
+        }
+    }
+
+    fn invoke_tx(address: ScAddress) -> Transaction {
+        let auth = SorobanAuthorizationEntry {
+            credentials: SorobanCredentials::Address(SorobanAddressCredentials {
+                address,
+                nonce: 1,
+                signature_expiration_ledger: 0,
+                signature: ScVal::Void,
+            }),
+            root_invocation: SorobanAuthorizedInvocation {
+                function: SorobanAuthorizedFunction::ContractFn(InvokeContractArgs {
+                    contract_address: ScAddress::Contract(ContractId(Hash([9; 32]))),
+                    function_name: "transfer".try_into().unwrap(),
+                    args: VecM::default(),
+                }),
+                sub_invocations: VecM::default(),
+            },
+        };
+        Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256([0; 32])),
+            fee: 100,
+            seq_num: SequenceNumber(1),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: vec![Operation {
+                source_account: None,
+                body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+                    host_function: xdr::HostFunction::InvokeContract(InvokeContractArgs {
+                        contract_address: ScAddress::Contract(ContractId(Hash([9; 32]))),
+                        function_name: "transfer".try_into().unwrap(),
+                        args: VecM::default(),
+                    }),
+                    auth: vec![auth].try_into().unwrap(),
+                }),
+            }]
+            .try_into()
+            .unwrap(),
+            ext: TransactionExt::V0,
+        }
+    }
+
+    async fn sign(tx: &Transaction, signers: &[Signer]) -> Result<Option<Transaction>, Error> {
+        sign_soroban_authorizations(tx, &local_signer(0), signers, &[], 100, PASSPHRASE).await
+    }
+
+    fn signed_credentials(tx: &Transaction) -> SorobanAddressCredentials {
+        let OperationBody::InvokeHostFunction(InvokeHostFunctionOp { auth, .. }) =
+            &tx.operations[0].body
+        else {
+            panic!("expected invoke host function");
+        };
+        let SorobanCredentials::Address(credentials) = &auth[0].credentials else {
+            panic!("expected address credentials");
+        };
+        credentials.clone()
+    }
+
+    #[tokio::test]
+    async fn signs_account_address() {
+        let signer = local_signer(1);
+        let address = ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(
+            signer.get_public_key().await.unwrap(),
+        ))));
+        let tx = sign(&invoke_tx(address), &[signer]).await.unwrap().unwrap();
+        let credentials = signed_credentials(&tx);
+        assert_eq!(credentials.signature_expiration_ledger, 100);
+        assert!(matches!(credentials.signature, ScVal::Vec(Some(_))));
+    }
+
+    #[tokio::test]
+    async fn signs_muxed_address_with_underlying_key() {
+        let signer = local_signer(1);
+        let address = ScAddress::MuxedAccount(MuxedEd25519Account {
+            id: 42,
+            ed25519: Uint256(signer.get_public_key().await.unwrap()),
+        });
+        let tx = sign(&invoke_tx(address.clone()), &[signer])
+            .await
+            .unwrap()
+            .unwrap();
+        let credentials = signed_credentials(&tx);
+        assert_eq!(credentials.address, address);
+        assert_eq!(credentials.signature_expiration_ledger, 100);
+        assert!(matches!(credentials.signature, ScVal::Vec(Some(_))));
+    }
+
+    #[tokio::test]
+    async fn muxed_address_without_signer() {
+        let address = ScAddress::MuxedAccount(MuxedEd25519Account {
+            id: 42,
+            ed25519: Uint256(local_signer(1).get_public_key().await.unwrap()),
+        });
+        assert!(matches!(
+            sign(&invoke_tx(address), &[local_signer(2)]).await,
+            Err(Error::MissingSignerForAddress { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn claimable_balance_address_is_an_error() {
+        let address = ScAddress::ClaimableBalance(ClaimableBalanceId::ClaimableBalanceIdTypeV0(
+            Hash([1; 32]),
+        ));
+        assert!(matches!(
+            sign(&invoke_tx(address), &[local_signer(1)]).await,
+            Err(Error::ClaimableBalanceAddressNotSupported { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn liquidity_pool_address_is_an_error() {
+        let address = ScAddress::LiquidityPool(PoolId(Hash([1; 32])));
+        assert!(matches!(
+            sign(&invoke_tx(address), &[local_signer(1)]).await,
+            Err(Error::LiquidityPoolAddressNotSupported { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn sign_message_sep_53() {
+        let secret: crate::config::secret::Secret =
+            "SAKICEVQLYWGSOJS4WW7HZJWAHZVEEBS527LHK5V4MLJALYKICQCJXMW"
+                .parse()
+                .unwrap();
+        let signer = secret.signer(None, Print::new(true)).await.unwrap();
+        assert_eq!(
+            stellar_strkey::ed25519::PublicKey(signer.get_public_key().await.unwrap()).to_string(),
+            "GBXFXNDLV4LSWA4VB7YIL5GBD7BVNR22SGBTDKMO2SBZZHDXSKZYCP7L"
+        );
+        let signature = signer.sign_message(b"Hello, World!").await.unwrap();
+        assert_eq!(
+            base64::engine::general_purpose::STANDARD.encode(signature.to_bytes()),
+            "fO5dbYhXUhBMhe6kId/cuVq/AfEnHRHEvsP8vXh03M1uLpi5e46yO2Q8rEBzu3feXQewcQE5GArp88u6ePK6BA=="
+        );
+    }
+
+    #[tokio::test]
+    async fn contract_address_without_custom_account() {
+        let address = ScAddress::Contract(ContractId(Hash([1; 32])));
+        assert!(matches!(
+            sign(&invoke_tx(address), &[local_signer(1)]).await,
+            Err(Error::MissingSignerForAddress { .. })
+        ));
+    }
+}
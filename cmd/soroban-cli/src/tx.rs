@@ -1,4 +1,5 @@
 pub mod builder;
+pub mod signatures;
 
 /// 10,000,000 stroops in 1 XLM
 pub const ONE_XLM: i64 = 10_000_000;
@@ -0,0 +1,365 @@
+use ed25519_dalek::{Signature as Ed25519Signature, Verifier, VerifyingKey};
+
+use crate::xdr::{
+    AccountEntry, AccountId, DecoratedSignature, MuxedAccount, Operation, OperationBody, PublicKey,
+    SetOptionsOp, SignatureHint, SignerKey, ThresholdIndexes, Transaction, Uint256,
+};
+
+/// The hint of a signature made by an ed25519 key, which is the last four bytes of the key.
+pub fn hint(public_key: &[u8; 32]) -> SignatureHint {
+    SignatureHint([
+        public_key[28],
+        public_key[29],
+        public_key[30],
+        public_key[31],
+    ])
+}
+
+/// Check that a decorated signature was made by `public_key` over `payload`.
+pub fn verify(public_key: &[u8; 32], payload: &[u8], signature: &DecoratedSignature) -> bool {
+    signature.hint == hint(public_key)
+        && verify_ed25519(public_key, payload, signature.signature.as_slice())
+}
+
+/// Check a bare ed25519 signature, as found in Soroban authorization entries.
+pub fn verify_ed25519(public_key: &[u8; 32], payload: &[u8], signature: &[u8]) -> bool {
+    let Ok(verifying_key) = VerifyingKey::from_bytes(public_key) else {
+        return false;
+    };
+    let Ok(sig) = Ed25519Signature::from_slice(signature) else {
+        return false;
+    };
+    verifying_key.verify(payload, &sig).is_ok()
+}
+
+/// Find the first candidate key that produced the signature over `payload`.
+pub fn signer_of<'a>(
+    candidates: impl IntoIterator<Item = &'a [u8; 32]>,
+    payload: &[u8],
+    signature: &DecoratedSignature,
+) -> Option<&'a [u8; 32]> {
+    candidates
+        .into_iter()
+        .find(|key| verify(key, payload, signature))
+}
+
+/// The ed25519 key of an account.
+pub fn account_key(account_id: &AccountId) -> [u8; 32] {
+    let AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key))) = account_id;
+    *key
+}
+
+/// The threshold an operation has to meet, as defined by the protocol.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum ThresholdLevel {
+    Low,
+    Medium,
+    High,
+}
+
+impl std::fmt::Display for ThresholdLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThresholdLevel::Low => write!(f, "low"),
+            ThresholdLevel::Medium => write!(f, "medium"),
+            ThresholdLevel::High => write!(f, "high"),
+        }
+    }
+}
+
+impl ThresholdLevel {
+    pub fn for_operation(body: &OperationBody) -> Self {
+        match body {
+            OperationBody::AllowTrust(_)
+            | OperationBody::SetTrustLineFlags(_)
+            | OperationBody::BumpSequence(_)
+            | OperationBody::ClaimClaimableBalance(_)
+            | OperationBody::Inflation
+            | OperationBody::ExtendFootprintTtl(_)
+            | OperationBody::RestoreFootprint(_) => ThresholdLevel::Low,
+            OperationBody::AccountMerge(_) => ThresholdLevel::High,
+            OperationBody::SetOptions(SetOptionsOp {
+                master_weight,
+                low_threshold,
+                med_threshold,
+                high_threshold,
+                signer,
+                ..
+            }) if master_weight.is_some()
+                || low_threshold.is_some()
+                || med_threshold.is_some()
+                || high_threshold.is_some()
+                || signer.is_some() =>
+            {
+                ThresholdLevel::High
+            }
+            _ => ThresholdLevel::Medium,
+        }
+    }
+}
+
+/// The signers, their weights and the thresholds of an account.
+#[derive(Clone, Debug)]
+pub struct AccountSigners {
+    pub account_id: AccountId,
+    pub thresholds: [u8; 4],
+    pub signers: Vec<([u8; 32], u32)>,
+}
+
+impl From<&AccountEntry> for AccountSigners {
+    fn from(entry: &AccountEntry) -> Self {
+        let thresholds = entry.thresholds.0;
+        let mut signers = vec![(
+            account_key(&entry.account_id),
+            u32::from(thresholds[ThresholdIndexes::MasterWeight as usize]),
+        )];
+        signers.extend(entry.signers.iter().filter_map(|s| match &s.key {
+            SignerKey::Ed25519(Uint256(key)) => Some((*key, s.weight)),
+            _ => None,
+        }));
+        AccountSigners {
+            account_id: entry.account_id.clone(),
+            thresholds,
+            signers,
+        }
+    }
+}
+
+impl AccountSigners {
+    /// Weight of `public_key` on this account, zero if it isn't a signer.
+    pub fn weight_of(&self, public_key: &[u8; 32]) -> u32 {
+        self.signers
+            .iter()
+            .filter(|(key, _)| key == public_key)
+            .map(|(_, weight)| *weight)
+            .sum()
+    }
+
+    pub fn is_signer(&self, public_key: &[u8; 32]) -> bool {
+        self.weight_of(public_key) > 0
+    }
+
+    /// Weight needed for the level. A threshold of zero still needs a signature with some weight.
+    pub fn needed(&self, level: ThresholdLevel) -> u32 {
+        let index = match level {
+            ThresholdLevel::Low => ThresholdIndexes::Low,
+            ThresholdLevel::Medium => ThresholdIndexes::Med,
+            ThresholdLevel::High => ThresholdIndexes::High,
+        };
+        u32::from(self.thresholds[index as usize]).max(1)
+    }
+
+    /// Total weight of the keys among `signed_by` that are signers of this account.
+    pub fn signed_weight(&self, signed_by: &[[u8; 32]]) -> u32 {
+        let mut keys = signed_by.to_vec();
+        keys.sort_unstable();
+        keys.dedup();
+        keys.iter().map(|key| self.weight_of(key)).sum()
+    }
+}
+
+/// A requirement of the transaction, or one of its operations, that isn't met by its signatures.
+#[derive(Clone, Debug)]
+pub struct MissingWeight {
+    /// `None` for the transaction itself, which needs the low threshold of its source account
+    pub operation: Option<usize>,
+    pub name: &'static str,
+    pub account_id: AccountId,
+    pub level: ThresholdLevel,
+    pub needed: u32,
+    pub signed: u32,
+}
+
+impl std::fmt::Display for MissingWeight {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.operation {
+            Some(index) => write!(f, "operation {index} ({})", self.name)?,
+            None => write!(f, "transaction")?,
+        }
+        write!(
+            f,
+            " needs {} threshold weight {} from {}, signed weight is {}",
+            self.level, self.needed, self.account_id, self.signed
+        )
+    }
+}
+
+/// The account id of an operation's source, defaulting to the transaction's source account.
+pub fn operation_source(tx: &Transaction, op: &Operation) -> AccountId {
+    op.source_account
+        .as_ref()
+        .unwrap_or(&tx.source_account)
+        .clone()
+        .account_id()
+}
+
+/// Every distinct account whose signatures the transaction needs.
+pub fn source_accounts(tx: &Transaction) -> Vec<AccountId> {
+    let mut accounts = vec![MuxedAccount::account_id(tx.source_account.clone())];
+    for op in tx.operations.iter() {
+        let account = operation_source(tx, op);
+        if !accounts.contains(&account) {
+            accounts.push(account);
+        }
+    }
+    accounts
+}
+
+/// Check the signature weight of the transaction and each of its operations against the
+/// thresholds of their source accounts. Accounts missing from `accounts` are not checked.
+pub fn missing_weight(
+    tx: &Transaction,
+    accounts: &[AccountSigners],
+    signed_by: &[[u8; 32]],
+) -> Vec<MissingWeight> {
+    let find = |account_id: &AccountId| accounts.iter().find(|a| &a.account_id == account_id);
+    let mut missing = Vec::new();
+    let tx_source = tx.source_account.clone().account_id();
+    let requirements = std::iter::once((None, "transaction", tx_source, ThresholdLevel::Low))
+        .chain(tx.operations.iter().enumerate().map(|(i, op)| {
+            (
+                Some(i),
+                op.body.name(),
+                operation_source(tx, op),
+                ThresholdLevel::for_operation(&op.body),
+            )
+        }));
+    for (operation, name, account_id, level) in requirements {
+        let Some(account) = find(&account_id) else {
+            continue;
+        };
+        let needed = account.needed(level);
+        let signed = account.signed_weight(signed_by);
+        if signed < needed {
+            missing.push(MissingWeight {
+                operation,
+                name,
+                account_id,
+                level,
+                needed,
+                signed,
+            });
+        }
+    }
+    missing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdr::{
+        AccountEntryExt, BumpSequenceOp, Memo, PaymentOp, Preconditions, SequenceNumber, Signer,
+        String32, Thresholds, TransactionExt,
+    };
+    use ed25519_dalek::{Signer as _, SigningKey};
+
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn public(seed: u8) -> [u8; 32] {
+        key(seed).verifying_key().to_bytes()
+    }
+
+    fn account(master: u8, thresholds: [u8; 4], signers: &[(u8, u32)]) -> AccountSigners {
+        let entry = AccountEntry {
+            account_id: AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(public(master)))),
+            balance: 0,
+            seq_num: SequenceNumber(0),
+            num_sub_entries: 0,
+            inflation_dest: None,
+            flags: 0,
+            home_domain: String32::default(),
+            thresholds: Thresholds(thresholds),
+            signers: signers
+                .iter()
+                .map(|(seed, weight)| Signer {
+                    key: SignerKey::Ed25519(Uint256(public(*seed))),
+                    weight: *weight,
+                })
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+            ext: AccountEntryExt::V0,
+        };
+        (&entry).into()
+    }
+
+    fn tx(source: u8, ops: Vec<OperationBody>) -> Transaction {
+        Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256(public(source))),
+            fee: 100,
+            seq_num: SequenceNumber(1),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: ops
+                .into_iter()
+                .map(|body| Operation {
+                    source_account: None,
+                    body,
+                })
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+            ext: TransactionExt::V0,
+        }
+    }
+
+    fn payment() -> OperationBody {
+        OperationBody::Payment(PaymentOp {
+            destination: MuxedAccount::Ed25519(Uint256([0; 32])),
+            asset: crate::xdr::Asset::Native,
+            amount: 1,
+        })
+    }
+
+    #[test]
+    fn verify_checks_hint_and_signature() {
+        let payload = [7; 32];
+        let signature = DecoratedSignature {
+            hint: hint(&public(1)),
+            signature: key(1)
+                .sign(&payload)
+                .to_bytes()
+                .to_vec()
+                .try_into()
+                .unwrap(),
+        };
+        assert!(verify(&public(1), &payload, &signature));
+        assert!(!verify(&public(2), &payload, &signature));
+        assert!(!verify(&public(1), &[8; 32], &signature));
+    }
+
+    #[test]
+    fn multisig_weights() {
+        // master weight 1, thresholds low 1, medium 2, high 3, with a second signer of weight 1
+        let accounts = [account(1, [1, 1, 2, 3], &[(2, 1)])];
+        let tx = tx(1, vec![payment()]);
+
+        let missing = missing_weight(&tx, &accounts, &[public(1)]);
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].operation, Some(0));
+        assert_eq!(missing[0].level, ThresholdLevel::Medium);
+        assert_eq!((missing[0].needed, missing[0].signed), (2, 1));
+
+        assert!(missing_weight(&tx, &accounts, &[public(1), public(2)]).is_empty());
+        // signing twice with the same key doesn't add weight
+        assert_eq!(
+            missing_weight(&tx, &accounts, &[public(1), public(1)]).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn threshold_levels() {
+        let accounts = [account(1, [1, 1, 2, 3], &[(2, 1)])];
+        let tx = tx(
+            1,
+            vec![OperationBody::BumpSequence(BumpSequenceOp {
+                bump_to: SequenceNumber(2),
+            })],
+        );
+        assert!(missing_weight(&tx, &accounts, &[public(2)]).is_empty());
+        assert!(!accounts[0].is_signer(&public(3)));
+    }
+}
@@ -5,9 +5,9 @@ use stellar_strkey::ed25519::PrivateKey;
 use crate::{
     print::Print,
     xdr::{
-        self, Asset, ContractIdPreimage, Hash, HashIdPreimage, HashIdPreimageContractId, Limits,
-        ScMap, ScMapEntry, ScVal, Transaction, TransactionSignaturePayload,
-        TransactionSignaturePayloadTaggedTransaction, WriteXdr,
+        self, Asset, ContractIdPreimage, FeeBumpTransaction, Hash, HashIdPreimage,
+        HashIdPreimageContractId, Limits, ScMap, ScMapEntry, ScVal, Transaction,
+        TransactionSignaturePayload, TransactionSignaturePayloadTaggedTransaction, WriteXdr,
     },
 };
 
@@ -36,6 +36,20 @@ pub fn transaction_hash(
     Ok(Sha256::digest(signature_payload.to_xdr(Limits::none())?).into())
 }
 
+/// # Errors
+///
+/// Might return an error
+pub fn fee_bump_transaction_hash(
+    tx: &FeeBumpTransaction,
+    network_passphrase: &str,
+) -> Result<[u8; 32], xdr::Error> {
+    let signature_payload = TransactionSignaturePayload {
+        network_id: Hash(Sha256::digest(network_passphrase).into()),
+        tagged_transaction: TransactionSignaturePayloadTaggedTransaction::TxFeeBump(tx.clone()),
+    };
+    Ok(Sha256::digest(signature_payload.to_xdr(Limits::none())?).into())
+}
+
 static EXPLORERS: phf::Map<&'static str, &'static str> = phf_map! {
     "Test SDF Network ; September 2015" => "https://stellar.expert/explorer/testnet",
     "Public Global Stellar Network ; September 2015" => "https://stellar.expert/explorer/public",